@@ -0,0 +1,108 @@
+// etika-archive-rpc/src/lib.rs
+//
+// RPC de preuve d'inclusion des transactions PoP archivées
+//
+// Seule la racine de Merkle de chaque période d'archivage est conservée on-chain. Le corps des
+// transactions archivées est lu dans la base offchain des nœuds d'archive, lancés avec
+// `--enable-offchain-indexing true`, afin que les auditeurs puissent prouver qu'un achat passé
+// a existé. La preuve retournée se vérifie avec l'API runtime `EtikaArchiveApi`.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use parking_lot::RwLock;
+use sp_core::{
+    offchain::{OffchainStorage, STORAGE_PREFIX},
+    H256,
+};
+
+use etika_blockchain_core::{
+    archive_leaf, archive_leaf_key, archive_period_key, archive_position_key, merkle_proof,
+    ArchiveCommitment, ArchiveProof, ArchiveStatus,
+};
+use etika_data_structure::PoPTransaction;
+
+/// Code d'erreur RPC: index offchain incomplet ou illisible
+const ARCHIVE_INDEX_ERROR: i64 = 1;
+
+/// API RPC des archives PoP
+#[rpc]
+pub trait EtikaArchiveApi {
+    /// Construire la preuve d'inclusion d'une transaction archivée
+    ///
+    /// Retourne `None` si la transaction n'a pas été archivée ou si sa période n'est pas encore close.
+    #[rpc(name = "etika_archiveProof")]
+    fn archive_proof(&self, transaction_id: H256) -> Result<Option<ArchiveProof>>;
+}
+
+/// Implémentation de l'API RPC des archives à partir de la base offchain du nœud
+pub struct EtikaArchive<S> {
+    storage: Arc<RwLock<S>>,
+}
+
+impl<S: OffchainStorage> EtikaArchive<S> {
+    /// Créer l'API à partir du stockage offchain du nœud
+    pub fn new(storage: S) -> Self {
+        EtikaArchive {
+            storage: Arc::new(RwLock::new(storage)),
+        }
+    }
+}
+
+impl<S: OffchainStorage + 'static> EtikaArchiveApi for EtikaArchive<S> {
+    fn archive_proof(&self, transaction_id: H256) -> Result<Option<ArchiveProof>> {
+        let storage = self.storage.read();
+        
+        // Position de la transaction, puis engagement de sa période une fois close
+        let (period, leaf_index): (u32, u32) = match read(&*storage, &archive_position_key(transaction_id.as_fixed_bytes()))? {
+            Some(position) => position,
+            None => return Ok(None),
+        };
+        let commitment: ArchiveCommitment = match read(&*storage, &archive_period_key(period))? {
+            Some(commitment) => commitment,
+            None => return Ok(None),
+        };
+        
+        // Reconstituer les feuilles de la période
+        let mut leaves = Vec::with_capacity(commitment.leaf_count as usize);
+        let mut archived = None;
+        for index in 0..commitment.leaf_count {
+            let (status, transaction): (ArchiveStatus, PoPTransaction) = read(&*storage, &archive_leaf_key(period, index))?
+                .ok_or_else(|| index_error(format!("Missing leaf {} of archive period {}", index, period)))?;
+            
+            leaves.push(archive_leaf(status, &transaction));
+            if index == leaf_index {
+                archived = Some((status, transaction));
+            }
+        }
+        
+        let (status, transaction) = archived
+            .ok_or_else(|| index_error(format!("Leaf {} out of archive period {}", leaf_index, period)))?;
+        
+        Ok(Some(ArchiveProof {
+            period,
+            leaf_index,
+            status,
+            transaction,
+            siblings: merkle_proof(&leaves, leaf_index),
+        }))
+    }
+}
+
+/// Lire et décoder une entrée de l'index offchain
+fn read<S: OffchainStorage, V: Decode>(storage: &S, key: &[u8]) -> Result<Option<V>> {
+    storage
+        .get(STORAGE_PREFIX, key)
+        .map(|raw| V::decode(&mut &raw[..]).map_err(|e| index_error(format!("Undecodable archive entry: {:?}", e))))
+        .transpose()
+}
+
+fn index_error(message: String) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(ARCHIVE_INDEX_ERROR),
+        message,
+        data: None,
+    }
+}
@@ -9,52 +9,295 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use sp_core::{crypto::KeyTypeId, sr25519, Pair, Public, H256};
+use sp_core::{crypto::KeyTypeId, H256};
 use sp_runtime::{
-    traits::{BlakeTwo256, Hash as HashT, IdentifyAccount, Verify},
-    MultiSignature, RuntimeDebug,
+    traits::{AccountIdConversion, BlakeTwo256, Hash as HashT, Verify},
+    ModuleId, MultiSignature, Perbill, RuntimeDebug, SaturatedConversion,
+};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    storage::{IterableStorageMap, StorageMap, StorageValue},
+    traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, Randomness},
+    transactional,
+    weights::{constants::RocksDbWeight, Weight},
+    Parameter,
 };
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get, Parameter};
 use frame_system::{self as system, ensure_signed};
 use sp_std::prelude::*;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 // Import des structures de données définies dans etika-data-structure
 use etika_data_structure::{
     AccountId, Balance, BlockNumber, Hash, Moment, PoPTransaction, ActorProfile,
-    ActorType, BlockchainHost, NodeInfo, PoPConsensus, TokenState, Token,
+    ActorType, BlockchainHost, ConsumerFund, NodeInfo, OnPopFinalized, PoPConsensus, ReceiptLineItem, ReceiptLineProof,
+    Signature, TokenState, Token, TokenSystem, sweep_queue, verify_receipt_line,
 };
+use etika_security_framework::SpecialAuthority;
+
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 /// Type ID pour les clés du module blockchain
 pub const BLOCKCHAIN_KEY_TYPE: KeyTypeId = KeyTypeId(*b"etik");
 
-/// Type pour la preuve cryptographique dans les transactions PoP
-pub type Proof = sr25519::Signature;
+/// Type pour la preuve cryptographique dans les transactions PoP (sr25519, ed25519 ou ecdsa)
+pub type Proof = MultiSignature;
 
-/// Nombre minimum de validateurs requis pour une transaction PoP
-const MIN_POP_VALIDATORS: usize = 2;
+/// Contexte de signature des transactions PoP (séparation de domaine)
+pub const POP_SIGNING_CONTEXT: &[u8] = b"etika/pop/v1";
 
 /// Nombre maximum de validateurs pour une transaction PoP
 const MAX_POP_VALIDATORS: usize = 10;
 
+/// Limite maximum pour les adresses réseau (en octets)
+const MAX_NETWORK_ADDRESS_LENGTH: usize = 100;
+
+/// Limite maximum pour les versions logicielles (en octets)
+const MAX_SOFTWARE_VERSION_LENGTH: usize = 50;
+
+/// Nombre maximum de connexions persistantes par nœud
+const MAX_PERSISTENT_CONNECTIONS: usize = 5;
+
+/// Nombre maximum de connexions aléatoires par nœud
+const MAX_RANDOM_CONNECTIONS: usize = 3;
+
+/// Réputation initiale d'un pair inconnu
+const INITIAL_PEER_REPUTATION: u8 = 50;
+
+/// Contexte de la graine aléatoire utilisée pour la sélection des pairs
+const PEER_SELECTION_CONTEXT: &[u8] = b"etika/peers";
+
+/// Contexte de dérivation des positions tirées dans l'index des nœuds candidats
+const PEER_SAMPLING_CONTEXT: &[u8] = b"etika/peers/sample";
+
+/// Gain de réputation pour un pair signalé disponible
+const REPUTATION_AVAILABLE_REWARD: u8 = 1;
+
+/// Perte de réputation pour un pair signalé indisponible
+const REPUTATION_UNAVAILABLE_PENALTY: u8 = 5;
+
+/// Perte de réputation pour un comportement malveillant signalé par un pair
+const REPUTATION_MISBEHAVIOUR_PENALTY: u8 = 20;
+
+/// Variation du score de disponibilité à chaque signalement de vivacité
+const AVAILABILITY_STEP: u8 = 5;
+
+/// Nombre maximum d'unités de stockage prises en compte dans la récompense d'hébergement
+const MAX_STORAGE_REWARD_UNITS: u64 = 10;
+
+/// Profondeur de l'arbre de Merkle des archives (2^32 transactions par période au plus)
+pub const ARCHIVE_TREE_DEPTH: usize = 32;
+
+/// Préfixe des clés d'indexation offchain des transactions archivées
+pub const ARCHIVE_INDEX_PREFIX: &[u8] = b"etika/archive";
+
+/// Type de vente déclaré à la création d'une transaction PoP
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SaleType {
+    /// Vente directe (producteur → consommateur)
+    DirectSale,
+    /// Vente au détail (commerçant → consommateur)
+    RetailSale,
+    /// Vente complexe (multi-fournisseurs)
+    ComplexSale,
+    /// Transaction système, réservée aux comptes ayant le rôle `SystemSaleRole`
+    System,
+}
+
+/// Politique de validation appliquée aux transactions PoP d'un type de vente
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ValidationPolicy<BlockNumber> {
+    /// Signature du consommateur requise
+    pub consumer_required: bool,
+    /// Signature du commerçant requise
+    pub merchant_required: bool,
+    /// Nombre minimum de fournisseurs déclarés
+    pub min_suppliers: u32,
+    /// Nombre maximum de fournisseurs déclarés
+    pub max_suppliers: u32,
+    /// Signatures de fournisseurs requises (k parmi n), plafonnées au nombre de fournisseurs déclarés
+    pub required_supplier_signatures: u32,
+    /// Délai de validation (en blocs), borné par `MaxPopTransactionLifetime`
+    pub validation_period: BlockNumber,
+}
+
+/// Signalement d'un nœud par l'un de ses pairs
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum NodeReport {
+    /// Le nœud a répondu dans les délais
+    Available,
+    /// Le nœud n'a pas répondu
+    Unavailable,
+    /// Le nœud a eu un comportement malveillant
+    Misbehaviour(MisbehaviourKind),
+}
+
+/// Nature d'un comportement malveillant
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum MisbehaviourKind {
+    /// Données ou blocs invalides relayés
+    InvalidData,
+    /// Messages contradictoires signés pour une même étape
+    Equivocation,
+    /// Rétention volontaire de transactions PoP
+    Censorship,
+}
+
+/// Poids des opérations de maintenance exécutées dans `on_initialize`
+///
+/// Les implémentations destinées à un runtime doivent être générées à partir des benchmarks du module.
+pub trait WeightInfo {
+    /// Expiration de `e` transactions réparties sur `b` compartiments de la file d'expiration
+    fn sweep_expired_transactions(b: u32, e: u32) -> Weight;
+    /// Versement de `h` récompenses réparties sur `b` compartiments de la file de récompenses
+    fn credit_hosting_rewards(b: u32, h: u32) -> Weight;
+    /// Rotation des pairs de `n` nœuds répartis sur `b` compartiments de la file de rotation
+    fn rotate_peers(b: u32, n: u32) -> Weight;
+    /// Archivage de `a` transactions réparties sur `b` compartiments de la file d'archivage
+    fn archive_transactions(b: u32, a: u32) -> Weight;
+    /// Règlement définitif de `s` transactions réparties sur `b` compartiments de la file des règlements
+    fn settle_transactions(b: u32, s: u32) -> Weight;
+}
+
+/// Poids de test: seuls les accès au stockage sont dénombrés, sans coût d'exécution
+///
+/// Ces valeurs ne bornent pas la durée de `on_initialize`. Un runtime doit utiliser les poids
+/// générés par `frame-benchmarking` à partir du module `benchmarking`.
+impl WeightInfo for () {
+    fn sweep_expired_transactions(b: u32, e: u32) -> Weight {
+        RocksDbWeight::get().reads_writes(
+            (1 + b as Weight).saturating_add(2 * e as Weight),
+            (1 + b as Weight).saturating_add(4 * e as Weight),
+        )
+    }
+    
+    fn credit_hosting_rewards(b: u32, h: u32) -> Weight {
+        RocksDbWeight::get().reads_writes(
+            (2 + b as Weight).saturating_add(2 * h as Weight),
+            (1 + b as Weight).saturating_add(3 * h as Weight),
+        )
+    }
+    
+    fn rotate_peers(b: u32, n: u32) -> Weight {
+        RocksDbWeight::get().reads_writes(
+            (1 + b as Weight).saturating_add(77 * n as Weight),
+            (1 + b as Weight).saturating_add(3 * n as Weight),
+        )
+    }
+    
+    fn archive_transactions(b: u32, a: u32) -> Weight {
+        RocksDbWeight::get().reads_writes(
+            (2 + b as Weight).saturating_add(2 * a as Weight),
+            (2 + b as Weight).saturating_add(2 * a as Weight),
+        )
+    }
+    
+    fn settle_transactions(b: u32, s: u32) -> Weight {
+        RocksDbWeight::get().reads_writes(
+            (1 + b as Weight).saturating_add(2 * s as Weight),
+            (1 + b as Weight).saturating_add(s as Weight),
+        )
+    }
+}
+
 /// Configuration du module blockchain
-pub trait Config: frame_system::Config {
+///
+/// Les comptes du runtime doivent être ceux de `etika-data-structure` afin que les signatures
+/// des participants puissent être vérifiées contre leur identifiant de compte.
+pub trait Config: frame_system::Config<AccountId = AccountId> {
     /// Type d'événement
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
     
     /// Durée maximum pour finaliser une transaction PoP (en nombre de blocs)
     type MaxPopTransactionLifetime: Get<Self::BlockNumber>;
     
-    /// Récompense pour l'hébergement d'un nœud (par bloc)
+    /// Monnaie dans laquelle sont payées les récompenses d'hébergement
+    type Currency: Currency<Self::AccountId>;
+    
+    /// Identifiant du compte de réserve des récompenses d'hébergement
+    type RewardPotId: Get<ModuleId>;
+    
+    /// Récompense de base pour l'hébergement d'un nœud (par période et par unité de stockage)
     type HostingReward: Get<Balance>;
     
-    /// Périodicité de la récompense d'hébergement (en nombre de blocs)
+    /// Capacité de stockage d'une unité de récompense (en bytes)
+    type RewardStorageUnit: Get<u64>;
+    
+    /// Total des récompenses d'hébergement pouvant être créditées sur une période
+    type MaxHostingRewardsPerPeriod: Get<Balance>;
+    
+    /// Périodicité de la récompense d'hébergement de chaque hôte (en nombre de blocs)
     type HostingRewardPeriod: Get<Self::BlockNumber>;
     
-    /// Ratio de brûlage des tokens pour chaque transaction (en pourcentage)
-    type TokenBurnRatio: Get<u8>;
+    /// Nombre maximum de transactions expirées traitées par bloc (le reste est reporté)
+    type MaxExpiriesPerBlock: Get<u32>;
+    
+    /// Nombre maximum de récompenses d'hébergement créditées par bloc (le reste est reporté)
+    type MaxRewardsPerBlock: Get<u32>;
+    
+    /// Durée de conservation du corps des transactions finalisées ou expirées (en blocs)
+    type RetentionPeriod: Get<Self::BlockNumber>;
+    
+    /// Durée d'une période d'archivage, dont seule la racine de Merkle est conservée (en blocs)
+    type ArchivePeriod: Get<Self::BlockNumber>;
+    
+    /// Nombre maximum de transactions archivées par bloc (le reste est reporté)
+    type MaxArchivedPerBlock: Get<u32>;
+    
+    /// Nombre maximum de transactions dont le règlement devient définitif par bloc (le reste est reporté)
+    type MaxSettlementsPerBlock: Get<u32>;
+    
+    /// Poids des opérations de maintenance
+    type WeightInfo: WeightInfo;
+    
+    /// Période de rotation des pairs (en nombre de blocs)
+    type PeerRotationPeriod: Get<Self::BlockNumber>;
     
-    /// Ratio de tokens alloués aux ONG pour chaque transaction (en pourcentage)
-    type NGOTokenRatio: Get<u8>;
+    /// Nombre maximum de nœuds dont les pairs sont renouvelés par bloc (le reste est reporté)
+    type MaxPeerRotationsPerBlock: Get<u32>;
+    
+    /// Nombre maximum de nœuds candidats lus lors d'une sélection de pairs
+    type MaxPeerCandidates: Get<u32>;
+    
+    /// Origine autorisée à modifier les politiques de validation (gouvernance)
+    type PolicyOrigin: EnsureOrigin<Self::Origin>;
+    
+    /// Source d'aléa on-chain pour la sélection des pairs (BABE, collective flip...)
+    type Randomness: Randomness<Self::Hash>;
+    
+    /// Réputation en dessous de laquelle un hôte est exclu du réseau
+    type MinHostReputation: Get<u8>;
+    
+    /// Délai minimum entre deux signalements d'un même pair sur un même nœud (en blocs)
+    type ReportCooldown: Get<Self::BlockNumber>;
+    
+    /// Nombre maximum de signalements défavorables d'un même pair pris en compte contre un même nœud
+    type MaxReportsPerReporter: Get<u32>;
+    
+    /// Système de tokens, pour l'activation des tokens échangés et leur annulation
+    type TokenSystem: TokenSystem;
+    
+    /// Fonds des consommateurs, pour l'épargne générée et son annulation
+    type ConsumerFund: ConsumerFund;
+    
+    /// Autorisations spéciales du framework de sécurité
+    type SpecialAuthority: SpecialAuthority<Self::AccountId>;
+    
+    /// Rôle spécial des arbitres des litiges
+    type ArbitratorRole: Get<[u8; 32]>;
+    
+    /// Rôle spécial des comptes autorisés à créer des transactions système
+    type SystemSaleRole: Get<[u8; 32]>;
+    
+    /// Délai après la finalisation pendant lequel un litige peut être ouvert (en blocs),
+    /// au plus égal à `RetentionPeriod`
+    type DisputeWindow: Get<Self::BlockNumber>;
+    
+    /// Modules à prévenir du règlement définitif ou de l'annulation d'une transaction PoP
+    /// (séquestres du système de tokens)
+    type OnPopFinalized: OnPopFinalized;
 }
 
 decl_storage! {
@@ -82,6 +325,387 @@ decl_storage! {
         
         /// Compteur de transactions validées
         ValidatedTransactionCount get(fn validated_transaction_count): u64;
+        
+        /// Nonce par compte pour prévenir les attaques de rejeu
+        AccountNonces get(fn account_nonce): map hasher(blake2_128_concat) AccountId => u64;
+        
+        /// Connexions par nœud pour contrer les attaques d'éclipse
+        NodeConnections get(fn node_connections): map hasher(blake2_128_concat) [u8; 32] => Vec<PeerConnection>;
+        
+        /// File de rotation: nœuds dont les pairs sont à renouveler à chaque bloc
+        PeerRotationQueue get(fn peer_rotation_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<[u8; 32]>;
+        
+        /// Plus ancien compartiment de la file de rotation non entièrement traité
+        PeerRotationCursor get(fn peer_rotation_cursor): Option<T::BlockNumber>;
+        
+        /// Index dense des nœuds candidats à la sélection de pairs (position => nœud)
+        PeerIndex get(fn peer_index): map hasher(twox_64_concat) u32 => Option<[u8; 32]>;
+        
+        /// Position de chaque nœud dans l'index des candidats
+        PeerPositions get(fn peer_position): map hasher(blake2_128_concat) [u8; 32] => Option<u32>;
+        
+        /// Nombre de nœuds dans l'index des candidats
+        PeerCount get(fn peer_count): u32;
+        
+        /// Compteur des sélections de pairs, mêlé à l'aléa pour distinguer les tirages d'un même bloc
+        PeerSelectionNonce get(fn peer_selection_nonce): u64;
+        
+        /// Transactions expirées pour analyse et audit
+        ExpiredTransactions get(fn expired_transactions): map hasher(blake2_128_concat) [u8; 32] => PoPTransaction;
+        
+        /// Effets appliqués à la finalisation, conservés tant que la transaction peut être annulée
+        Settlements get(fn settlements): 
+            map hasher(blake2_128_concat) [u8; 32] => Option<Settlement<T::BlockNumber>>;
+        
+        /// Litiges ouverts sur des transactions finalisées
+        Disputes get(fn disputes): 
+            map hasher(blake2_128_concat) [u8; 32] => Option<Dispute<AccountId, T::BlockNumber>>;
+        
+        /// Transactions annulées après un litige, jusqu'à leur archivage
+        ReversedTransactions get(fn reversed_transactions): map hasher(blake2_128_concat) [u8; 32] => PoPTransaction;
+        
+        /// Lignes du ticket divulguées par chaque fournisseur (position dans le ticket, ligne)
+        SupplierLineItems get(fn supplier_line_items): 
+            double_map hasher(blake2_128_concat) [u8; 32], hasher(blake2_128_concat) AccountId => Vec<(u32, ReceiptLineItem)>;
+        
+        /// File d'expiration: transactions PoP arrivant à échéance à chaque bloc
+        ExpiryQueue get(fn expiry_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<[u8; 32]>;
+        
+        /// Plus ancien compartiment de la file d'expiration non entièrement traité
+        ExpiryCursor get(fn expiry_cursor): Option<T::BlockNumber>;
+        
+        /// File des récompenses d'hébergement à créditer à chaque bloc
+        RewardQueue get(fn reward_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<AccountId>;
+        
+        /// Plus ancien compartiment de la file des récompenses non entièrement traité
+        RewardCursor get(fn reward_cursor): Option<T::BlockNumber>;
+        
+        /// Somme des récompenses de période des hôtes actifs, avant plafonnement
+        TotalHostingRewards get(fn total_hosting_rewards): Balance;
+        
+        /// File d'archivage: transactions finalisées ou expirées dont la conservation prend fin
+        ArchiveQueue get(fn archive_queue): 
+            map hasher(twox_64_concat) T::BlockNumber => Vec<([u8; 32], ArchiveStatus)>;
+        
+        /// Plus ancien compartiment de la file d'archivage non entièrement traité
+        ArchiveCursor get(fn archive_cursor): Option<T::BlockNumber>;
+        
+        /// File des règlements: transactions finalisées dont le délai de contestation prend fin
+        SettlementQueue get(fn settlement_queue): 
+            map hasher(twox_64_concat) T::BlockNumber => Vec<[u8; 32]>;
+        
+        /// Plus ancien compartiment de la file des règlements non entièrement traité
+        SettlementCursor get(fn settlement_cursor): Option<T::BlockNumber>;
+        
+        /// Arbre de Merkle incrémental de la période d'archivage en cours
+        CurrentArchive get(fn current_archive): Option<(u32, ArchiveFrontier)>;
+        
+        /// Racine de Merkle et nombre de transactions de chaque période d'archivage close
+        ArchiveRoots get(fn archive_roots): map hasher(twox_64_concat) u32 => Option<ArchiveCommitment>;
+        
+        /// Réputation de chaque nœud, conservée après exclusion (absente: réputation initiale)
+        NodeReputations: map hasher(blake2_128_concat) [u8; 32] => Option<u8>;
+        
+        /// Dernier signalement d'un nœud (rapporteur, nœud signalé)
+        LastNodeReport get(fn last_node_report): 
+            double_map hasher(blake2_128_concat) [u8; 32], hasher(blake2_128_concat) [u8; 32] => Option<T::BlockNumber>;
+        
+        /// Signalements défavorables retenus contre un nœud (nœud signalé, rapporteur)
+        NodeReportCounts get(fn node_report_counts): 
+            double_map hasher(blake2_128_concat) [u8; 32], hasher(blake2_128_concat) [u8; 32] => u32;
+        
+        /// Bonus d'hébergement d'hôtes exclus restant à verser faute de fonds dans la réserve
+        PendingHostingBonus get(fn pending_hosting_bonus): map hasher(blake2_128_concat) T::AccountId => Balance;
+        
+        /// Total des bonus d'hébergement confisqués
+        ForfeitedHostingBonus get(fn forfeited_hosting_bonus): Balance;
+        
+        /// Politiques de validation par type de vente
+        ValidationPolicies get(fn validation_policies): 
+            map hasher(blake2_128_concat) SaleType => Option<ValidationPolicy<T::BlockNumber>>;
+        
+        /// Type de vente et politique de validation retenus à la création de chaque transaction,
+        /// conservés jusqu'à son archivage
+        TransactionPolicies get(fn transaction_policies): 
+            map hasher(blake2_128_concat) [u8; 32] => Option<(SaleType, ValidationPolicy<T::BlockNumber>)>;
+        
+        /// Version du format de stockage (absente sur une chaîne lancée avec la première version)
+        StorageVersion get(fn storage_version) build(|_: &GenesisConfig<T>| Releases::V2): Releases;
+    }
+    add_extra_genesis {
+        config(validation_policies): Vec<(SaleType, ValidationPolicy<T::BlockNumber>)>;
+        
+        build(|config: &GenesisConfig<T>| {
+            // Les politiques initiales sont soumises aux mêmes contrôles que `set_validation_policy`
+            for (sale_type, policy) in &config.validation_policies {
+                assert!(
+                    Module::<T>::ensure_valid_policy(policy).is_ok(),
+                    "Politique de validation initiale incohérente"
+                );
+                <ValidationPolicies<T>>::insert(sale_type, policy);
+            }
+        });
+    }
+}
+
+/// Versions du format de stockage du module
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+    /// Première version: ni nonces, ni connexions entre pairs, ni politiques de validation,
+    /// ni files d'expiration, de récompenses et d'archivage
+    V1,
+    /// Format actuel
+    V2,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1
+    }
+}
+
+/// Transaction PoP telle qu'encodée par la première version du module (sans nonce)
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+struct PoPTransactionV1 {
+    id: [u8; 32],
+    consumer: AccountId,
+    merchant: AccountId,
+    suppliers: Vec<AccountId>,
+    standard_amount: Balance,
+    tokens_exchanged: Balance,
+    savings_generated: Balance,
+    timestamp: Moment,
+    receipt_hash: [u8; 32],
+    signatures: Vec<(AccountId, Signature)>,
+}
+
+impl From<PoPTransactionV1> for PoPTransaction {
+    fn from(transaction: PoPTransactionV1) -> Self {
+        PoPTransaction {
+            id: transaction.id,
+            consumer: transaction.consumer,
+            merchant: transaction.merchant,
+            suppliers: transaction.suppliers,
+            standard_amount: transaction.standard_amount,
+            tokens_exchanged: transaction.tokens_exchanged,
+            savings_generated: transaction.savings_generated,
+            timestamp: transaction.timestamp,
+            receipt_hash: transaction.receipt_hash,
+            signatures: transaction.signatures,
+            nonce: 0,
+        }
+    }
+}
+
+/// Structure pour les connexions entre pairs
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PeerConnection {
+    pub node_id: [u8; 32],
+    pub last_seen: Moment,
+    pub reputation: u8,
+}
+
+/// Statut d'une transaction PoP au moment de son archivage
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ArchiveStatus {
+    /// Transaction finalisée
+    Validated,
+    /// Transaction expirée sans avoir été finalisée
+    Expired,
+    /// Transaction finalisée puis annulée après un litige
+    Reversed,
+}
+
+/// Effets d'une transaction PoP finalisée, repris en cas d'annulation
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Settlement<BlockNumber> {
+    /// Bloc de finalisation
+    pub finalized_at: BlockNumber,
+    /// Tokens du consommateur effectivement activés
+    pub tokens_activated: Balance,
+    /// Épargne effectivement créditée au consommateur
+    pub savings_credited: Balance,
+}
+
+/// Motif d'un litige sur une transaction finalisée
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DisputeReason {
+    /// Produit retourné et remboursé
+    Refund,
+    /// Achat frauduleux
+    Fraud,
+    /// Montant erroné
+    WrongAmount,
+}
+
+/// Litige ouvert sur une transaction finalisée
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Dispute<AccountId, BlockNumber> {
+    /// Participant à l'origine du litige
+    pub opened_by: AccountId,
+    /// Motif invoqué
+    pub reason: DisputeReason,
+    /// Bloc d'ouverture
+    pub opened_at: BlockNumber,
+    /// Signataires ayant approuvé l'annulation
+    pub cancel_approvals: Vec<AccountId>,
+}
+
+/// Engagement on-chain d'une période d'archivage close
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ArchiveCommitment {
+    /// Racine de Merkle des transactions archivées
+    pub root: H256,
+    /// Nombre de transactions archivées
+    pub leaf_count: u32,
+}
+
+/// Preuve d'inclusion d'une transaction PoP dans une période d'archivage
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ArchiveProof {
+    /// Période d'archivage
+    pub period: u32,
+    /// Position de la transaction dans l'arbre de la période
+    pub leaf_index: u32,
+    /// Statut de la transaction au moment de son archivage
+    pub status: ArchiveStatus,
+    /// Corps complet de la transaction
+    pub transaction: PoPTransaction,
+    /// Nœuds frères, de la feuille vers la racine
+    pub siblings: Vec<H256>,
+}
+
+/// Bord droit d'un arbre de Merkle de profondeur fixe, alimenté feuille par feuille
+///
+/// Seul un nœud par niveau est conservé: l'ajout d'une feuille et le calcul de la racine
+/// coûtent `ARCHIVE_TREE_DEPTH` hachages, quel que soit le nombre de feuilles.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct ArchiveFrontier {
+    /// Dernier sous-arbre gauche complet de chaque niveau
+    pub branch: Vec<H256>,
+    /// Nombre de feuilles ajoutées
+    pub leaf_count: u32,
+}
+
+impl ArchiveFrontier {
+    /// Ajouter une feuille et retourner sa position
+    pub fn append(&mut self, leaf: H256) -> u32 {
+        let index = self.leaf_count;
+        self.branch.resize(ARCHIVE_TREE_DEPTH, H256::zero());
+        
+        let mut node = leaf;
+        let mut size = index + 1;
+        for height in 0..ARCHIVE_TREE_DEPTH {
+            if size & 1 == 1 {
+                self.branch[height] = node;
+                break;
+            }
+            node = merkle_parent(&self.branch[height], &node);
+            size >>= 1;
+        }
+        
+        self.leaf_count += 1;
+        index
+    }
+    
+    /// Racine de l'arbre, les positions libres étant complétées par des sous-arbres vides
+    pub fn root(&self) -> H256 {
+        let zero_hashes = merkle_zero_hashes();
+        let mut node = H256::zero();
+        let mut size = self.leaf_count;
+        
+        for height in 0..ARCHIVE_TREE_DEPTH {
+            node = if size & 1 == 1 {
+                merkle_parent(&self.branch[height], &node)
+            } else {
+                merkle_parent(&node, &zero_hashes[height])
+            };
+            size >>= 1;
+        }
+        
+        node
+    }
+}
+
+/// Feuille de l'arbre d'archivage d'une transaction PoP
+pub fn archive_leaf(status: ArchiveStatus, transaction: &PoPTransaction) -> H256 {
+    BlakeTwo256::hash_of(&(status, transaction))
+}
+
+/// Nœud parent de deux nœuds de l'arbre d'archivage
+fn merkle_parent(left: &H256, right: &H256) -> H256 {
+    BlakeTwo256::hash_of(&(left, right))
+}
+
+/// Racines des sous-arbres vides de chaque hauteur
+fn merkle_zero_hashes() -> Vec<H256> {
+    let mut zero_hashes = vec![H256::zero()];
+    for height in 1..ARCHIVE_TREE_DEPTH {
+        let below = zero_hashes[height - 1];
+        zero_hashes.push(merkle_parent(&below, &below));
+    }
+    zero_hashes
+}
+
+/// Nœuds frères de la feuille `index` parmi les feuilles d'une période, de la feuille vers la racine
+pub fn merkle_proof(leaves: &[H256], index: u32) -> Vec<H256> {
+    let zero_hashes = merkle_zero_hashes();
+    let mut layer = leaves.to_vec();
+    let mut position = index as usize;
+    let mut siblings = Vec::with_capacity(ARCHIVE_TREE_DEPTH);
+    
+    for height in 0..ARCHIVE_TREE_DEPTH {
+        let sibling = layer.get(position ^ 1).copied().unwrap_or(zero_hashes[height]);
+        siblings.push(sibling);
+        
+        layer = layer
+            .chunks(2)
+            .map(|pair| merkle_parent(&pair[0], pair.get(1).unwrap_or(&zero_hashes[height])))
+            .collect();
+        position >>= 1;
+    }
+    
+    siblings
+}
+
+/// Racine reconstruite à partir d'une feuille, de sa position et de ses nœuds frères
+pub fn merkle_root_from_proof(leaf: H256, index: u32, siblings: &[H256]) -> H256 {
+    siblings.iter().enumerate().fold(leaf, |node, (height, sibling)| {
+        if (index >> height) & 1 == 1 {
+            merkle_parent(sibling, &node)
+        } else {
+            merkle_parent(&node, sibling)
+        }
+    })
+}
+
+/// Clé d'indexation offchain d'une feuille archivée
+pub fn archive_leaf_key(period: u32, leaf_index: u32) -> Vec<u8> {
+    (ARCHIVE_INDEX_PREFIX, b"leaf", period, leaf_index).encode()
+}
+
+/// Clé d'indexation offchain de la position d'une transaction archivée
+pub fn archive_position_key(transaction_id: &[u8; 32]) -> Vec<u8> {
+    (ARCHIVE_INDEX_PREFIX, b"position", transaction_id).encode()
+}
+
+/// Clé d'indexation offchain de l'engagement d'une période close
+pub fn archive_period_key(period: u32) -> Vec<u8> {
+    (ARCHIVE_INDEX_PREFIX, b"period", period).encode()
+}
+
+sp_api::decl_runtime_apis! {
+    /// Vérification des preuves d'inclusion des transactions PoP archivées
+    pub trait EtikaArchiveApi {
+        /// Engagement d'une période d'archivage close
+        fn archive_root(period: u32) -> Option<ArchiveCommitment>;
+        
+        /// Vérifier une preuve d'inclusion contre la racine on-chain de sa période
+        fn verify_archive_proof(proof: ArchiveProof) -> bool;
     }
 }
 
@@ -114,9 +738,61 @@ decl_event!(
         /// [compte, node_id]
         NodeLeft(AccountId, [u8; 32]),
         
-        /// Récompense distribuée pour l'hébergement d'un nœud
+        /// Récompense d'hébergement créditée au bonus d'un hôte
         /// [compte, montant]
         HostingRewardPaid(AccountId, Balance),
+        
+        /// Bonus d'hébergement versé depuis la réserve de récompenses
+        /// [compte, montant]
+        HostingRewardClaimed(AccountId, Balance),
+        
+        /// Type d'acteur d'un hôte certifié par la gouvernance
+        /// [compte, type d'acteur]
+        HostActorTypeUpdated(AccountId, ActorType),
+        
+        /// Période d'archivage close, seule sa racine de Merkle est conservée
+        /// [période, racine, nombre de transactions]
+        ArchivePeriodClosed(u32, H256, u32),
+        
+        /// Rotation des pairs effectuée
+        /// [nombre de nœuds concernés]
+        PeersRotated(u32),
+        
+        /// Politique de validation d'un type de vente modifiée
+        /// [type de vente]
+        ValidationPolicyUpdated(SaleType),
+        
+        /// Un nœud a été signalé par l'un de ses pairs
+        /// [rapporteur, nœud signalé, signalement, nouvelle réputation]
+        NodeReported([u8; 32], [u8; 32], NodeReport, u8),
+        
+        /// Un hôte a été exclu du réseau pour réputation insuffisante, sans perdre son bonus
+        /// [compte, node_id, bonus d'hébergement acquis]
+        NodeEvicted(AccountId, [u8; 32], Balance),
+        
+        /// Un hôte a été sanctionné pour un comportement malveillant avéré
+        /// [compte, node_id, comportement, bonus confisqué]
+        NodeSlashed(AccountId, [u8; 32], MisbehaviourKind, Balance),
+        
+        /// Un litige a été ouvert sur une transaction finalisée
+        /// [transaction_id, participant, motif]
+        DisputeOpened([u8; 32], AccountId, DisputeReason),
+        
+        /// Un signataire a approuvé l'annulation d'une transaction contestée
+        /// [transaction_id, signataire]
+        CancellationApproved([u8; 32], AccountId),
+        
+        /// Un arbitre a tranché un litige
+        /// [transaction_id, arbitre, transaction annulée]
+        DisputeResolved([u8; 32], AccountId, bool),
+        
+        /// Une transaction finalisée a été annulée et ses effets repris
+        /// [transaction_id, tokens désactivés, épargne reprise]
+        TransactionReversed([u8; 32], Balance, Balance),
+        
+        /// Un fournisseur a prouvé la présence de son produit dans le ticket d'une transaction
+        /// [transaction_id, fournisseur, produit]
+        SupplierLineItemLinked([u8; 32], AccountId, [u8; 32]),
     }
 );
 
@@ -157,6 +833,87 @@ decl_error! {
         
         /// Le fournisseur et le commerçant doivent être différents
         SupplierMerchantSame,
+        
+        /// Nonce invalide
+        InvalidNonce,
+        
+        /// Montant invalide
+        InvalidAmount,
+        
+        /// Tokens excessifs
+        ExcessiveTokens,
+        
+        /// Épargne excessive
+        ExcessiveSavings,
+        
+        /// Input trop large
+        InputTooLarge,
+        
+        /// Compte inexistant
+        AccountDoesNotExist,
+        
+        /// Le consommateur doit valider la transaction
+        MissingConsumerValidation,
+        
+        /// Un même compte apparaît plusieurs fois parmi les participants
+        DuplicateParticipant,
+        
+        /// Aucune politique de validation pour ce type de vente
+        ValidationPolicyNotFound,
+        
+        /// Politique de validation incohérente
+        InvalidValidationPolicy,
+        
+        /// Le compte n'est pas autorisé à créer des transactions système
+        NotSystemOperator,
+        
+        /// Un nœud ne peut pas se signaler lui-même
+        CannotReportSelf,
+        
+        /// Seuls les pairs connectés à un nœud peuvent le signaler
+        NotConnectedPeer,
+        
+        /// Signalement trop rapproché du précédent
+        ReportTooFrequent,
+        
+        /// Nombre maximum de signalements défavorables de ce pair contre ce nœud atteint
+        ReportLimitReached,
+        
+        /// Réputation du nœud insuffisante pour héberger la blockchain
+        NodeReputationTooLow,
+        
+        /// Aucun bonus d'hébergement à verser
+        NoHostingReward,
+        
+        /// Réserve de récompenses insuffisante pour verser le bonus
+        RewardPotExhausted,
+        
+        /// Délai de contestation écoulé ou transaction déjà définitive
+        DisputeWindowClosed,
+        
+        /// Un litige est déjà ouvert sur cette transaction
+        DisputeAlreadyOpen,
+        
+        /// Aucun litige ouvert sur cette transaction
+        DisputeNotFound,
+        
+        /// Annulation déjà approuvée par ce signataire
+        AlreadyApproved,
+        
+        /// Le compte n'est pas arbitre des litiges
+        NotArbitrator,
+        
+        /// Les effets de la transaction n'ont pas pu être repris
+        ReversalFailed,
+        
+        /// Preuve d'appartenance au ticket invalide
+        InvalidReceiptProof,
+        
+        /// La ligne du ticket n'est pas attribuée à ce fournisseur
+        LineItemSupplierMismatch,
+        
+        /// Ligne du ticket déjà rattachée au fournisseur
+        LineItemAlreadyLinked,
     }
 }
 
@@ -168,23 +925,69 @@ decl_module! {
         /// Émission des événements
         fn deposit_event() = default;
         
-        /// Récompenses d'hébergement au changement de bloc
-        fn on_initialize(n: T::BlockNumber) -> frame_support::weights::Weight {
-            // Distribuer les récompenses d'hébergement périodiquement
-            if n % T::HostingRewardPeriod::get() == 0.into() {
-                Self::distribute_hosting_rewards();
+        /// Migration du stockage lors d'une mise à niveau du runtime
+        fn on_runtime_upgrade() -> Weight {
+            if Self::storage_version() != Releases::V1 {
+                return T::DbWeight::get().reads(1);
             }
             
+            let weight = Self::migrate_v1_to_v2();
+            <StorageVersion>::put(Releases::V2);
+            
+            weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+        }
+        
+        /// Récompenses d'hébergement et maintenance au changement de bloc
+        ///
+        /// Les files d'expiration, de récompenses et de rotation des pairs sont traitées dans la limite de
+        /// `MaxExpiriesPerBlock`, `MaxRewardsPerBlock` et `MaxPeerRotationsPerBlock`, le reste étant
+        /// reporté aux blocs suivants. Le poids retourné est celui de `T::WeightInfo` pour le travail
+        /// effectué; il ne borne ce travail que si le runtime fournit des poids mesurés.
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            // Créditer les récompenses d'hébergement arrivées à échéance
+            let (buckets, credited) = Self::distribute_hosting_rewards(n);
+            let mut weight = T::WeightInfo::credit_hosting_rewards(buckets, credited);
+            
+            // Rotation des pairs pour contrer les attaques d'éclipse
+            let (buckets, rotated) = Self::rotate_peers(n);
+            weight = weight.saturating_add(T::WeightInfo::rotate_peers(buckets, rotated));
+            
             // Nettoyer les transactions PoP expirées
-            Self::clean_expired_transactions(n);
+            let (buckets, expired) = Self::clean_expired_transactions(n);
+            weight = weight.saturating_add(T::WeightInfo::sweep_expired_transactions(buckets, expired));
             
-            0
+            // Rendre définitifs les règlements dont le délai de contestation est écoulé
+            let (buckets, settled) = Self::settle_transactions(n);
+            weight = weight.saturating_add(T::WeightInfo::settle_transactions(buckets, settled));
+            
+            // Archiver les transactions dont la durée de conservation est écoulée
+            let (buckets, archived) = Self::archive_transactions(n);
+            weight.saturating_add(T::WeightInfo::archive_transactions(buckets, archived))
+        }
+        
+        /// Cohérence de la configuration
+        ///
+        /// Un litige doit pouvoir être ouvert tant que la transaction n'est pas archivée, et
+        /// chaque file de maintenance doit progresser d'au moins un élément par bloc.
+        fn integrity_test() {
+            assert!(
+                T::DisputeWindow::get() <= T::RetentionPeriod::get(),
+                "DisputeWindow ne doit pas dépasser RetentionPeriod"
+            );
+            assert!(T::MaxExpiriesPerBlock::get() > 0, "MaxExpiriesPerBlock doit être non nul");
+            assert!(T::MaxRewardsPerBlock::get() > 0, "MaxRewardsPerBlock doit être non nul");
+            assert!(T::MaxArchivedPerBlock::get() > 0, "MaxArchivedPerBlock doit être non nul");
+            assert!(T::MaxSettlementsPerBlock::get() > 0, "MaxSettlementsPerBlock doit être non nul");
+            assert!(T::MaxPeerRotationsPerBlock::get() > 0, "MaxPeerRotationsPerBlock doit être non nul");
         }
         
         /// Créer une nouvelle transaction PoP
+        ///
+        /// Les validateurs requis et le délai de validation dépendent du type de vente déclaré.
         #[weight = 10_000]
         pub fn create_pop_transaction(
             origin,
+            sale_type: SaleType,
             consumer: T::AccountId,
             merchant: T::AccountId,
             suppliers: Vec<T::AccountId>,
@@ -196,8 +999,17 @@ decl_module! {
         ) -> frame_support::dispatch::DispatchResult {
             let sender = ensure_signed(origin)?;
             
-            // Vérifier que le nombre de validateurs est dans les limites
-            ensure!(suppliers.len() + 2 >= MIN_POP_VALIDATORS, Error::<T>::InsufficientValidators);
+            // Vérifier l'existence des comptes
+            ensure!(<frame_system::Module<T>>::account_exists(&consumer), Error::<T>::AccountDoesNotExist);
+            ensure!(<frame_system::Module<T>>::account_exists(&merchant), Error::<T>::AccountDoesNotExist);
+            for supplier in &suppliers {
+                ensure!(<frame_system::Module<T>>::account_exists(supplier), Error::<T>::AccountDoesNotExist);
+            }
+            
+            // Vérifier que le nombre de fournisseurs respecte la politique du type de vente
+            let policy = Self::validation_policies(sale_type).ok_or(Error::<T>::ValidationPolicyNotFound)?;
+            ensure!(suppliers.len() as u32 >= policy.min_suppliers, Error::<T>::InsufficientValidators);
+            ensure!(suppliers.len() as u32 <= policy.max_suppliers, Error::<T>::TooManyValidators);
             ensure!(suppliers.len() + 2 <= MAX_POP_VALIDATORS, Error::<T>::TooManyValidators);
             
             // Vérifier que commerçant et fournisseurs sont différents
@@ -205,14 +1017,59 @@ decl_module! {
                 ensure!(*supplier != merchant, Error::<T>::SupplierMerchantSame);
             }
             
-            // Créer un ID unique pour la transaction
-            let transaction_id = Self::generate_transaction_id(&consumer, &merchant, standard_amount);
+            // Chaque participant ne doit apparaître qu'une fois, afin qu'une signature ne compte qu'une fois
+            ensure!(consumer != merchant, Error::<T>::DuplicateParticipant);
+            for (index, supplier) in suppliers.iter().enumerate() {
+                ensure!(*supplier != consumer, Error::<T>::DuplicateParticipant);
+                ensure!(!suppliers[..index].contains(supplier), Error::<T>::DuplicateParticipant);
+            }
             
-            // Créer la transaction PoP initiale
-            let mut signatures = Vec::new();
+            // Le créateur doit être l'un des participants
+            let is_participant = sender == consumer || sender == merchant || suppliers.contains(&sender);
+            ensure!(is_participant, Error::<T>::UnauthorizedValidator);
+            
+            // Une transaction système peut être finalisée sans la signature du consommateur:
+            // seuls les comptes désignés par le framework de sécurité peuvent en créer
+            ensure!(
+                sale_type != SaleType::System
+                    || T::SpecialAuthority::has_special_authority(&sender, &T::SystemSaleRole::get()),
+                Error::<T>::NotSystemOperator
+            );
+            
+            // Vérifier les montants
+            ensure!(standard_amount > 0, Error::<T>::InvalidAmount);
+            ensure!(tokens_exchanged > 0, Error::<T>::InvalidAmount);
+            ensure!(tokens_exchanged <= standard_amount, Error::<T>::ExcessiveTokens);
+            ensure!(savings_generated <= standard_amount, Error::<T>::ExcessiveSavings);
+            
+            // Le nonce du créateur est inclus dans le message signé pour prévenir les attaques de rejeu
+            let sender_nonce = Self::account_nonce(sender.clone());
+            
+            // Créer un ID unique pour la transaction à partir de son contenu et du nonce
+            let transaction_id = Self::generate_transaction_id(
+                &sender,
+                &consumer,
+                &merchant,
+                &suppliers,
+                standard_amount,
+                &receipt_hash,
+                sender_nonce,
+            );
+            
+            // Vérifier la signature du créateur sur l'encodage canonique de la transaction
+            let message = Self::signing_payload(
+                &transaction_id,
+                standard_amount,
+                tokens_exchanged,
+                savings_generated,
+                &receipt_hash,
+                sender_nonce,
+            );
+            ensure!(proof.verify(&message[..], &sender), Error::<T>::InvalidSignature);
             
             // Ajouter la signature du créateur (généralement le commerçant)
-            signatures.push((sender.clone(), MultiSignature::from(proof)));
+            let mut signatures = Vec::new();
+            signatures.push((sender.clone(), proof));
             
             let transaction = PoPTransaction {
                 id: transaction_id,
@@ -225,21 +1082,36 @@ decl_module! {
                 timestamp: Self::get_current_timestamp(),
                 receipt_hash,
                 signatures,
+                nonce: sender_nonce,
             };
             
-            // Enregistrer la transaction comme en attente
+            // Enregistrer la transaction comme en attente, avec la politique en vigueur
+            let required_signatures = Self::required_signatures(&transaction, &policy);
+            let satisfied = Self::policy_satisfied(&transaction, &policy).is_ok();
+            let now = <frame_system::Module<T>>::block_number();
+            let deadline = now + policy.validation_period.max(1u32.into());
             <PendingTransactions>::insert(transaction_id, transaction);
+            <TransactionPolicies<T>>::insert(transaction_id, (sale_type, policy));
             
-            // Enregistrer le bloc de démarrage pour le suivi des expirations
-            <TransactionStartBlock<T>>::insert(transaction_id, <frame_system::Module<T>>::block_number());
+            // Enregistrer le bloc de démarrage et l'échéance pour le suivi des expirations
+            <TransactionStartBlock<T>>::insert(transaction_id, now);
+            <ExpiryQueue<T>>::append(deadline, transaction_id);
+            
+            // Incrémenter le nonce du compte
+            <AccountNonces<T>>::insert(sender.clone(), sender_nonce + 1);
             
             // Émettre un événement
             Self::deposit_event(RawEvent::PopTransactionCreated(
                 transaction_id,
                 sender,
-                (suppliers.len() as u8) + 2,
+                required_signatures,
             ));
             
+            // La signature du créateur peut suffire (transactions système)
+            if satisfied {
+                Self::finalize_pop_transaction(transaction_id)?;
+            }
+            
             Ok(())
         }
         
@@ -255,6 +1127,11 @@ decl_module! {
             // Vérifier que la transaction existe
             ensure!(<PendingTransactions>::contains_key(transaction_id), Error::<T>::PopTransactionNotFound);
             
+            // Une transaction échue ne peut plus être validée, même avant son retrait de la file
+            let (_, policy) = Self::transaction_policies(transaction_id).ok_or(Error::<T>::ValidationPolicyNotFound)?;
+            let deadline = Self::transaction_start_block(transaction_id) + policy.validation_period;
+            ensure!(<frame_system::Module<T>>::block_number() < deadline, Error::<T>::TransactionExpired);
+            
             // Récupérer la transaction
             let mut transaction = <PendingTransactions>::get(transaction_id);
             
@@ -269,8 +1146,12 @@ decl_module! {
             let already_validated = transaction.signatures.iter().any(|(account, _)| *account == validator);
             ensure!(!already_validated, Error::<T>::AlreadyValidated);
             
+            // Vérifier la signature sur l'encodage canonique de la transaction
+            let message = Self::compute_signature_message(&transaction);
+            ensure!(proof.verify(&message[..], &validator), Error::<T>::InvalidSignature);
+            
             // Ajouter la signature
-            transaction.signatures.push((validator.clone(), MultiSignature::from(proof)));
+            transaction.signatures.push((validator.clone(), proof));
             
             // Mettre à jour la transaction
             <PendingTransactions>::insert(transaction_id, transaction.clone());
@@ -278,10 +1159,8 @@ decl_module! {
             // Émettre un événement
             Self::deposit_event(RawEvent::PopTransactionValidated(transaction_id, validator));
             
-            // Vérifier si la transaction est complètement validée
-            let required_validators = transaction.suppliers.len() + 2; // consommateur + commerçant + fournisseurs
-            
-            if transaction.signatures.len() >= required_validators {
+            // Vérifier si la politique de validation est satisfaite
+            if Self::policy_satisfied(&transaction, &policy).is_ok() {
                 // Finaliser la transaction
                 Self::finalize_pop_transaction(transaction_id)?;
             }
@@ -289,6 +1168,27 @@ decl_module! {
             Ok(())
         }
         
+        /// Définir la politique de validation d'un type de vente (gouvernance)
+        ///
+        /// Les transactions déjà créées conservent la politique en vigueur à leur création.
+        #[weight = 10_000]
+        pub fn set_validation_policy(
+            origin,
+            sale_type: SaleType,
+            policy: ValidationPolicy<T::BlockNumber>,
+        ) -> frame_support::dispatch::DispatchResult {
+            T::PolicyOrigin::ensure_origin(origin)?;
+            
+            Self::ensure_valid_policy(&policy)?;
+            
+            <ValidationPolicies<T>>::insert(sale_type, policy);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::ValidationPolicyUpdated(sale_type));
+            
+            Ok(())
+        }
+        
         /// Enregistrer un nouveau nœud pour héberger la blockchain
         #[weight = 10_000]
         pub fn register_node(
@@ -303,6 +1203,13 @@ decl_module! {
             // Vérifier que le nœud n'est pas déjà enregistré
             ensure!(!<NodeIdToAccount<T>>::contains_key(node_id), Error::<T>::NodeAlreadyRegistered);
             
+            // Un nœud exclu ne peut pas revenir avec sa réputation dégradée
+            ensure!(Self::peer_reputation(node_id) >= T::MinHostReputation::get(), Error::<T>::NodeReputationTooLow);
+            
+            // Vérifier les limites de taille pour les entrées
+            ensure!(network_address.len() <= MAX_NETWORK_ADDRESS_LENGTH, Error::<T>::InputTooLarge);
+            ensure!(software_version.len() <= MAX_SOFTWARE_VERSION_LENGTH, Error::<T>::InputTooLarge);
+            
             // Créer les informations du nœud
             let node_info = NodeInfo {
                 node_id,
@@ -322,15 +1229,23 @@ decl_module! {
             };
             
             // Enregistrer l'hôte et le mapping nœud -> compte
-            <ActiveHosts<T>>::insert(account.clone(), host);
+            Self::put_host(&account, host);
             <NodeIdToAccount<T>>::insert(node_id, Some(account.clone()));
+            Self::index_peer(node_id);
             
             // Incrémenter le compteur de nœuds
             let total_nodes = <TotalActiveNodes>::get();
             <TotalActiveNodes>::put(total_nodes + 1);
             
-            // Initialiser le bloc de dernière récompense
-            <LastRewardBlock<T>>::insert(account.clone(), <frame_system::Module<T>>::block_number());
+            // Initialiser le bloc de dernière récompense et planifier la prochaine
+            let now = <frame_system::Module<T>>::block_number();
+            <LastRewardBlock<T>>::insert(account.clone(), now);
+            <RewardQueue<T>>::append(now + T::HostingRewardPeriod::get(), account.clone());
+            
+            // Initialiser les connexions avec des pairs aléatoires et planifier leur rotation
+            let initial_peers = Self::select_random_peers(node_id, MAX_RANDOM_CONNECTIONS, &[]);
+            <NodeConnections>::insert(node_id, initial_peers);
+            <PeerRotationQueue<T>>::append(now + T::PeerRotationPeriod::get(), node_id);
             
             // Émettre un événement
             Self::deposit_event(RawEvent::NodeJoined(account, node_id));
@@ -353,35 +1268,324 @@ decl_module! {
             let node_owner = <NodeIdToAccount<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
             ensure!(node_owner == account, Error::<T>::UnauthorizedValidator);
             
-            // Supprimer l'hôte et le mapping
-            <ActiveHosts<T>>::remove(account.clone());
-            <NodeIdToAccount<T>>::remove(node_id);
-            <LastRewardBlock<T>>::remove(account.clone());
+            // Verser le bonus accumulé avant le départ de l'hôte
+            let bonus = <ActiveHosts<T>>::get(&account).hosting_bonus;
+            if bonus > 0 {
+                Self::pay_hosting_bonus(&account, bonus)?;
+            }
             
-            // Décrémenter le compteur de nœuds
-            let total_nodes = <TotalActiveNodes>::get();
-            <TotalActiveNodes>::put(total_nodes - 1);
+            // Supprimer l'hôte et le mapping
+            Self::remove_host(&account, node_id);
             
             // Émettre un événement
             Self::deposit_event(RawEvent::NodeLeft(account, node_id));
             
             Ok(())
         }
-    }
-}
-
-impl<T: Config> Module<T> {
-    /// Générer un ID unique pour une transaction PoP
-    fn generate_transaction_id(consumer: &T::AccountId, merchant: &T::AccountId, amount: Balance) -> [u8; 32] {
-        let timestamp = Self::get_current_timestamp();
-        let mut data = Vec::new();
         
-        data.extend_from_slice(&consumer.encode());
-        data.extend_from_slice(&merchant.encode());
-        data.extend_from_slice(&amount.to_be_bytes());
-        data.extend_from_slice(&timestamp.to_be_bytes());
+        /// Réclamer le bonus d'hébergement accumulé
+        ///
+        /// Un hôte exclu dont le bonus n'a pu être versé faute de fonds le réclame également ici.
+        #[weight = 10_000]
+        pub fn claim_hosting_rewards(origin) -> frame_support::dispatch::DispatchResult {
+            let account = ensure_signed(origin)?;
+            
+            let pending = <PendingHostingBonus<T>>::get(&account);
+            if pending > 0 {
+                Self::pay_hosting_bonus(&account, pending)?;
+                <PendingHostingBonus<T>>::remove(&account);
+                return Ok(());
+            }
+            
+            ensure!(<ActiveHosts<T>>::contains_key(&account), Error::<T>::NodeNotFound);
+            let mut host = <ActiveHosts<T>>::get(&account);
+            let bonus = host.hosting_bonus;
+            ensure!(bonus > 0, Error::<T>::NoHostingReward);
+            
+            Self::pay_hosting_bonus(&account, bonus)?;
+            
+            host.hosting_bonus = 0;
+            <ActiveHosts<T>>::insert(&account, host);
+            
+            Ok(())
+        }
+        
+        /// Certifier le type d'acteur d'un hôte, qui module sa récompense (gouvernance)
+        #[weight = 10_000]
+        pub fn set_host_actor_type(
+            origin,
+            account: T::AccountId,
+            actor_type: ActorType,
+        ) -> frame_support::dispatch::DispatchResult {
+            T::PolicyOrigin::ensure_origin(origin)?;
+            
+            ensure!(<ActiveHosts<T>>::contains_key(&account), Error::<T>::NodeNotFound);
+            let mut host = <ActiveHosts<T>>::get(&account);
+            host.actor_type = actor_type.clone();
+            Self::put_host(&account, host);
+            
+            Self::deposit_event(RawEvent::HostActorTypeUpdated(account, actor_type));
+            
+            Ok(())
+        }
+        
+        /// Signaler la disponibilité ou le comportement d'un pair
+        ///
+        /// Soumis par l'hôte d'un nœud connecté au nœud signalé, typiquement depuis son
+        /// worker offchain. Au plus `MaxReportsPerReporter` signalements défavorables d'un même pair
+        /// sont retenus contre un nœud. Un hôte dont la réputation passe sous `MinHostReputation`
+        /// est exclu; son bonus d'hébergement lui reste acquis, seul `slash_node` le confisque.
+        #[weight = 10_000]
+        pub fn report_node(
+            origin,
+            node_id: [u8; 32],
+            report: NodeReport,
+        ) -> frame_support::dispatch::DispatchResult {
+            let account = ensure_signed(origin)?;
+            
+            // Le rapporteur doit héberger un nœud connecté au nœud signalé
+            ensure!(<ActiveHosts<T>>::contains_key(&account), Error::<T>::NodeNotFound);
+            let reporter_node = <ActiveHosts<T>>::get(&account).node_info.node_id;
+            ensure!(reporter_node != node_id, Error::<T>::CannotReportSelf);
+            
+            let owner = <NodeIdToAccount<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
+            ensure!(
+                <NodeConnections>::get(reporter_node).iter().any(|conn| conn.node_id == node_id),
+                Error::<T>::NotConnectedPeer
+            );
+            
+            // Un signalement par période de refroidissement et par couple de nœuds
+            let now = <frame_system::Module<T>>::block_number();
+            if let Some(last_report) = Self::last_node_report(reporter_node, node_id) {
+                ensure!(now >= last_report + T::ReportCooldown::get(), Error::<T>::ReportTooFrequent);
+            }
+            
+            // Nombre limité de signalements défavorables par pair, pour qu'un seul rapporteur ne puisse exclure un nœud
+            if report != NodeReport::Available {
+                let count = Self::node_report_counts(node_id, reporter_node);
+                ensure!(count < T::MaxReportsPerReporter::get(), Error::<T>::ReportLimitReached);
+                <NodeReportCounts>::insert(node_id, reporter_node, count + 1);
+            }
+            <LastNodeReport<T>>::insert(reporter_node, node_id, now);
+            
+            // Règle de mise à jour de la réputation et de la disponibilité
+            let reputation = Self::peer_reputation(node_id);
+            let mut host = <ActiveHosts<T>>::get(&owner);
+            let availability = host.node_info.availability_score;
+            
+            let (reputation, availability) = match report {
+                NodeReport::Available => (
+                    reputation.saturating_add(REPUTATION_AVAILABLE_REWARD),
+                    availability.saturating_add(AVAILABILITY_STEP).min(100),
+                ),
+                NodeReport::Unavailable => (
+                    reputation.saturating_sub(REPUTATION_UNAVAILABLE_PENALTY),
+                    availability.saturating_sub(AVAILABILITY_STEP),
+                ),
+                NodeReport::Misbehaviour(_) => (
+                    reputation.saturating_sub(REPUTATION_MISBEHAVIOUR_PENALTY),
+                    availability,
+                ),
+            };
+            
+            <NodeReputations>::insert(node_id, reputation);
+            host.node_info.availability_score = availability;
+            Self::put_host(&owner, host);
+            
+            Self::deposit_event(RawEvent::NodeReported(reporter_node, node_id, report, reputation));
+            
+            // Exclure l'hôte dont la réputation est devenue insuffisante
+            if reputation < T::MinHostReputation::get() {
+                Self::evict_host(&owner, node_id);
+            }
+            
+            Ok(())
+        }
+        
+        /// Sanctionner un comportement malveillant avéré (gouvernance)
+        ///
+        /// Le bonus d'hébergement accumulé est confisqué, la réputation du nœud est annulée
+        /// et l'hôte est exclu du réseau.
+        #[weight = 10_000]
+        pub fn slash_node(
+            origin,
+            node_id: [u8; 32],
+            kind: MisbehaviourKind,
+        ) -> frame_support::dispatch::DispatchResult {
+            T::PolicyOrigin::ensure_origin(origin)?;
+            
+            let owner = <NodeIdToAccount<T>>::get(node_id).ok_or(Error::<T>::NodeNotFound)?;
+            let forfeited = <ActiveHosts<T>>::get(&owner).hosting_bonus;
+            
+            <ForfeitedHostingBonus>::mutate(|total| *total = total.saturating_add(forfeited));
+            <NodeReputations>::insert(node_id, 0);
+            Self::remove_host(&owner, node_id);
+            
+            Self::deposit_event(RawEvent::NodeSlashed(owner, node_id, kind, forfeited));
+            
+            Ok(())
+        }
+        
+        /// Rattacher un fournisseur à une ligne du ticket d'une transaction en attente ou finalisée
+        ///
+        /// Le fournisseur divulgue sa seule ligne, avec une preuve d'appartenance à la racine
+        /// `receipt_hash` engagée par le commerçant; le reste du panier demeure confidentiel.
+        #[weight = 10_000]
+        pub fn link_supplier_line_item(
+            origin,
+            transaction_id: [u8; 32],
+            proof: ReceiptLineProof,
+        ) -> frame_support::dispatch::DispatchResult {
+            let supplier = ensure_signed(origin)?;
+            
+            let transaction = if <PendingTransactions>::contains_key(transaction_id) {
+                <PendingTransactions>::get(transaction_id)
+            } else if <ValidatedTransactions>::contains_key(transaction_id) {
+                <ValidatedTransactions>::get(transaction_id)
+            } else {
+                return Err(Error::<T>::PopTransactionNotFound.into());
+            };
+            
+            // Seul un fournisseur déclaré peut revendiquer une ligne qui lui est attribuée
+            ensure!(transaction.suppliers.contains(&supplier), Error::<T>::UnauthorizedValidator);
+            ensure!(proof.item.supplier.as_ref() == Some(&supplier), Error::<T>::LineItemSupplierMismatch);
+            ensure!(verify_receipt_line(&transaction.receipt_hash, &proof), Error::<T>::InvalidReceiptProof);
+            
+            let mut linked = <SupplierLineItems>::get(transaction_id, &supplier);
+            ensure!(
+                !linked.iter().any(|(leaf_index, _)| *leaf_index == proof.leaf_index),
+                Error::<T>::LineItemAlreadyLinked
+            );
+            
+            let product_id = proof.item.product_id;
+            linked.push((proof.leaf_index, proof.item));
+            <SupplierLineItems>::insert(transaction_id, &supplier, linked);
+            
+            Self::deposit_event(RawEvent::SupplierLineItemLinked(transaction_id, supplier, product_id));
+            
+            Ok(())
+        }
+        
+        /// Contester une transaction finalisée (remboursement, fraude, montant erroné)
+        ///
+        /// Ouvert à tout participant pendant `DisputeWindow` après la finalisation. L'ouverture
+        /// vaut approbation de l'annulation lorsque le participant a signé la transaction.
+        #[weight = 10_000]
+        pub fn open_dispute(
+            origin,
+            transaction_id: [u8; 32],
+            reason: DisputeReason,
+        ) -> frame_support::dispatch::DispatchResult {
+            let participant = ensure_signed(origin)?;
+            
+            let settlement = Self::settlements(transaction_id).ok_or(Error::<T>::DisputeWindowClosed)?;
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(now <= settlement.finalized_at + T::DisputeWindow::get(), Error::<T>::DisputeWindowClosed);
+            ensure!(!<Disputes<T>>::contains_key(transaction_id), Error::<T>::DisputeAlreadyOpen);
+            
+            let transaction = <ValidatedTransactions>::get(transaction_id);
+            let is_participant = participant == transaction.consumer
+                || participant == transaction.merchant
+                || transaction.suppliers.contains(&participant);
+            ensure!(is_participant, Error::<T>::UnauthorizedValidator);
+            
+            let mut cancel_approvals = Vec::new();
+            if transaction.signatures.iter().any(|(signer, _)| *signer == participant) {
+                cancel_approvals.push(participant.clone());
+            }
+            
+            <Disputes<T>>::insert(transaction_id, Dispute {
+                opened_by: participant.clone(),
+                reason,
+                opened_at: now,
+                cancel_approvals,
+            });
+            
+            Self::deposit_event(RawEvent::DisputeOpened(transaction_id, participant, reason));
+            
+            Ok(())
+        }
+        
+        /// Approuver l'annulation d'une transaction contestée
+        ///
+        /// La transaction est annulée dès que tous ses signataires ont approuvé.
+        #[weight = 10_000]
+        #[transactional]
+        pub fn approve_cancellation(
+            origin,
+            transaction_id: [u8; 32],
+        ) -> frame_support::dispatch::DispatchResult {
+            let signer = ensure_signed(origin)?;
+            
+            let mut dispute = Self::disputes(transaction_id).ok_or(Error::<T>::DisputeNotFound)?;
+            let transaction = <ValidatedTransactions>::get(transaction_id);
+            ensure!(
+                transaction.signatures.iter().any(|(account, _)| *account == signer),
+                Error::<T>::UnauthorizedValidator
+            );
+            ensure!(!dispute.cancel_approvals.contains(&signer), Error::<T>::AlreadyApproved);
+            
+            dispute.cancel_approvals.push(signer.clone());
+            Self::deposit_event(RawEvent::CancellationApproved(transaction_id, signer));
+            
+            let unanimous = transaction.signatures.iter().all(|(account, _)| dispute.cancel_approvals.contains(account));
+            if unanimous {
+                Self::reverse_transaction(transaction_id)?;
+            } else {
+                <Disputes<T>>::insert(transaction_id, dispute);
+            }
+            
+            Ok(())
+        }
         
-        let hash = BlakeTwo256::hash(&data);
+        /// Trancher un litige, en annulant ou en confirmant la transaction (arbitre)
+        ///
+        /// Une transaction confirmée devient définitive et ne peut plus être contestée.
+        #[weight = 10_000]
+        #[transactional]
+        pub fn resolve_dispute(
+            origin,
+            transaction_id: [u8; 32],
+            reverse: bool,
+        ) -> frame_support::dispatch::DispatchResult {
+            let arbitrator = ensure_signed(origin)?;
+            
+            ensure!(
+                T::SpecialAuthority::has_special_authority(&arbitrator, &T::ArbitratorRole::get()),
+                Error::<T>::NotArbitrator
+            );
+            ensure!(<Disputes<T>>::contains_key(transaction_id), Error::<T>::DisputeNotFound);
+            
+            if reverse {
+                Self::reverse_transaction(transaction_id)?;
+            } else {
+                <Disputes<T>>::remove(transaction_id);
+                <Settlements<T>>::remove(transaction_id);
+                T::OnPopFinalized::on_pop_finalized(&transaction_id);
+            }
+            
+            Self::deposit_event(RawEvent::DisputeResolved(transaction_id, arbitrator, reverse));
+            
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Générer un ID unique pour une transaction PoP
+    ///
+    /// L'ID est déterministe afin que le créateur puisse le calculer hors chaîne avant de signer:
+    /// le nonce du créateur garantit l'unicité entre deux transactions identiques.
+    pub fn generate_transaction_id(
+        creator: &T::AccountId,
+        consumer: &T::AccountId,
+        merchant: &T::AccountId,
+        suppliers: &[T::AccountId],
+        amount: Balance,
+        receipt_hash: &[u8; 32],
+        nonce: u64,
+    ) -> [u8; 32] {
+        let hash = BlakeTwo256::hash_of(&(creator, consumer, merchant, suppliers, amount, receipt_hash, nonce));
         *hash.as_fixed_bytes()
     }
     
@@ -393,13 +1597,79 @@ impl<T: Config> Module<T> {
         (now / 1000) as Moment
     }
     
+    /// Encodage canonique signé par chaque participant d'une transaction PoP
+    ///
+    /// [contexte, transaction_id, montant standard, tokens échangés, épargne générée, hash du ticket, nonce]
+    pub fn signing_payload(
+        transaction_id: &[u8; 32],
+        standard_amount: Balance,
+        tokens_exchanged: Balance,
+        savings_generated: Balance,
+        receipt_hash: &[u8; 32],
+        nonce: u64,
+    ) -> Vec<u8> {
+        (
+            POP_SIGNING_CONTEXT,
+            transaction_id,
+            standard_amount,
+            tokens_exchanged,
+            savings_generated,
+            receipt_hash,
+            nonce,
+        ).encode()
+    }
+    
+    /// Créer le message signé pour une transaction PoP
+    fn compute_signature_message(transaction: &PoPTransaction) -> Vec<u8> {
+        Self::signing_payload(
+            &transaction.id,
+            transaction.standard_amount,
+            transaction.tokens_exchanged,
+            transaction.savings_generated,
+            &transaction.receipt_hash,
+            transaction.nonce,
+        )
+    }
+    
+    /// Vérifier cryptographiquement toutes les signatures d'une transaction PoP
+    ///
+    /// Chaque signataire doit être un participant, ne signer qu'une fois et fournir une
+    /// signature valide (sr25519, ed25519 ou ecdsa) sur l'encodage canonique de la transaction.
+    fn verify_signatures(transaction: &PoPTransaction) -> Result<(), Error<T>> {
+        let message = Self::compute_signature_message(transaction);
+        
+        for (index, (signer, signature)) in transaction.signatures.iter().enumerate() {
+            let is_participant = *signer == transaction.consumer
+                || *signer == transaction.merchant
+                || transaction.suppliers.contains(signer);
+            ensure!(is_participant, Error::<T>::UnauthorizedValidator);
+            
+            let is_duplicate = transaction.signatures[..index].iter().any(|(account, _)| account == signer);
+            ensure!(!is_duplicate, Error::<T>::AlreadyValidated);
+            
+            ensure!(signature.verify(&message[..], signer), Error::<T>::InvalidSignature);
+        }
+        
+        Ok(())
+    }
+    
     /// Finaliser une transaction PoP
     fn finalize_pop_transaction(transaction_id: [u8; 32]) -> frame_support::dispatch::DispatchResult {
         // Vérifier que la transaction existe
         ensure!(<PendingTransactions>::contains_key(transaction_id), Error::<T>::PopTransactionNotFound);
         
-        // Récupérer et supprimer la transaction des transactions en attente
-        let transaction = <PendingTransactions>::take(transaction_id);
+        // Récupérer la transaction en attente
+        let transaction = <PendingTransactions>::get(transaction_id);
+        
+        // Vérifier que la politique de validation est satisfaite
+        let (_, policy) = Self::transaction_policies(transaction_id).ok_or(Error::<T>::ValidationPolicyNotFound)?;
+        Self::policy_satisfied(&transaction, &policy)?;
+        
+        // Revérifier l'ensemble des signatures avant la finalisation
+        Self::verify_signatures(&transaction)?;
+        
+        // Retirer la transaction des transactions en attente
+        <PendingTransactions>::remove(transaction_id);
         
         // Ajouter la transaction aux transactions validées
         <ValidatedTransactions>::insert(transaction_id, transaction.clone());
@@ -408,327 +1678,2803 @@ impl<T: Config> Module<T> {
         let count = <ValidatedTransactionCount>::get();
         <ValidatedTransactionCount>::put(count + 1);
         
+        // Appliquer les effets de la transaction; seuls les effets appliqués seront repris
+        // en cas d'annulation
+        let tokens_activated = match T::TokenSystem::activate_tokens(&transaction.consumer, transaction.tokens_exchanged) {
+            Ok(()) => transaction.tokens_exchanged,
+            Err(_) => 0,
+        };
+        let savings_credited = if transaction.savings_generated > 0 {
+            match T::ConsumerFund::add_savings(&transaction.consumer, &transaction.merchant, transaction.savings_generated) {
+                Ok(()) => transaction.savings_generated,
+                Err(_) => 0,
+            }
+        } else {
+            0
+        };
+        
+        // Conserver les effets appliqués pendant la durée de contestation, au terme de laquelle
+        // le règlement devient définitif
+        let now = <frame_system::Module<T>>::block_number();
+        <Settlements<T>>::insert(transaction_id, Settlement {
+            finalized_at: now,
+            tokens_activated,
+            savings_credited,
+        });
+        <SettlementQueue<T>>::append(now + T::DisputeWindow::get() + 1u32.into(), transaction_id);
+        
+        // Planifier l'archivage à la fin de la durée de conservation
+        Self::schedule_archive(transaction_id, ArchiveStatus::Validated);
+        
         // Nettoyer les données associées
         <TransactionStartBlock<T>>::remove(transaction_id);
         
+        // Les séquestres conditionnés à cette transaction ne sont libérés qu'une fois
+        // le délai de contestation passé (voir `settle_transaction`)
+        
         // Émettre un événement
         Self::deposit_event(RawEvent::PopTransactionFinalized(transaction_id));
         
-        // Ici, on pourrait appeler d'autres modules pour appliquer les effets de la transaction:
-        // - Mise à jour des tokens
-        // - Génération d'épargne
-        // - Affacturage, etc.
-        
         Ok(())
     }
     
-    /// Nettoyer les transactions PoP expirées
-    fn clean_expired_transactions(current_block: T::BlockNumber) {
-        for (transaction_id, start_block) in <TransactionStartBlock<T>>::iter() {
-            let block_difference = current_block.saturating_sub(start_block);
-            
-            if block_difference >= T::MaxPopTransactionLifetime::get() {
-                // La transaction a expiré
-                if <PendingTransactions>::contains_key(transaction_id) {
-                    let transaction = <PendingTransactions>::take(transaction_id);
-                    
-                    // Émettre un événement d'échec
-                    Self::deposit_event(RawEvent::PopTransactionFailed(
-                        transaction_id,
-                        b"Transaction expired".to_vec(),
-                    ));
-                }
-                
-                // Nettoyer les données associées
-                <TransactionStartBlock<T>>::remove(transaction_id);
+    /// Migrer le stockage de la première version du module (V1) vers le format actuel (V2)
+    ///
+    /// Les transactions en attente de la V1 n'ont pas été signées sur l'encodage canonique et
+    /// ne pourront jamais être finalisées: elles sont expirées. Les transactions validées sont
+    /// inscrites dans la file d'archivage, les hôtes dans la file de récompenses (à compter du
+    /// bloc de migration) et reçoivent leurs premières connexions. Les types de vente sans
+    /// politique reçoivent la règle de la V1: signature de tous les participants.
+    fn migrate_v1_to_v2() -> Weight {
+        let now = <frame_system::Module<T>>::block_number();
+        let mut reads: Weight = 0;
+        let mut writes: Weight = 0;
+        
+        let all_participants = ValidationPolicy {
+            consumer_required: true,
+            merchant_required: true,
+            min_suppliers: 0,
+            max_suppliers: (MAX_POP_VALIDATORS - 2) as u32,
+            required_supplier_signatures: (MAX_POP_VALIDATORS - 2) as u32,
+            validation_period: T::MaxPopTransactionLifetime::get(),
+        };
+        for sale_type in &[SaleType::DirectSale, SaleType::RetailSale, SaleType::ComplexSale, SaleType::System] {
+            reads += 1;
+            if Self::validation_policies(*sale_type).is_none() {
+                <ValidationPolicies<T>>::insert(*sale_type, all_participants.clone());
+                writes += 1;
             }
         }
-    }
-    
-    /// Distribuer les récompenses pour l'hébergement des nœuds
-    fn distribute_hosting_rewards() {
-        let reward_amount = T::HostingReward::get();
-        let current_block = <frame_system::Module<T>>::block_number();
         
-        for (account, mut host) in <ActiveHosts<T>>::iter() {
-            // Accumuler la récompense
-            host.hosting_bonus = host.hosting_bonus.saturating_add(reward_amount);
-            
-            // Mettre à jour l'hôte
-            <ActiveHosts<T>>::insert(account.clone(), host);
-            
-            // Mettre à jour le bloc de dernière récompense
-            <LastRewardBlock<T>>::insert(account.clone(), current_block);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::HostingRewardPaid(account, reward_amount));
-        }
-    }
-}
-
-/// Implémentation du trait PoPConsensus pour le module blockchain
-impl<T: Config> PoPConsensus for Module<T> {
-    fn validate_transaction(transaction: &PoPTransaction) -> Result<(), &'static str> {
-        // Vérifier que le nombre de validateurs est suffisant
-        if transaction.signatures.len() < MIN_POP_VALIDATORS {
-            return Err("Insufficient validators");
+        // Les transactions de la V1 sont décodées dans leur ancien format (sans nonce);
+        // les transactions en attente quittent la table, les transactions validées y sont réécrites
+        let mut expired = Vec::new();
+        <PendingTransactions as IterableStorageMap<_, _>>::translate::<PoPTransactionV1, _>(|transaction_id, transaction| {
+            expired.push((transaction_id, PoPTransaction::from(transaction)));
+            None
+        });
+        for (transaction_id, transaction) in expired {
+            <TransactionStartBlock<T>>::remove(transaction_id);
+            <ExpiredTransactions>::insert(transaction_id, transaction);
+            Self::schedule_archive(transaction_id, ArchiveStatus::Expired);
+            Self::deposit_event(RawEvent::PopTransactionFailed(
+                transaction_id,
+                b"Transaction expired by storage migration".to_vec(),
+            ));
+            reads += 1;
+            writes += 4;
         }
         
-        // Vérifier que les signatures correspondent aux participants attendus
-        let mut consumer_validated = false;
-        let mut merchant_validated = false;
-        let mut suppliers_validated = 0;
+        let mut validated = Vec::new();
+        <ValidatedTransactions as IterableStorageMap<_, _>>::translate::<PoPTransactionV1, _>(|transaction_id, transaction| {
+            validated.push(transaction_id);
+            Some(transaction.into())
+        });
+        for transaction_id in validated {
+            Self::schedule_archive(transaction_id, ArchiveStatus::Validated);
+            reads += 1;
+            writes += 2;
+        }
         
-        for (account_id, _) in &transaction.signatures {
-            if *account_id == transaction.consumer {
-                consumer_validated = true;
-            } else if *account_id == transaction.merchant {
-                merchant_validated = true;
-            } else if transaction.suppliers.contains(account_id) {
-                suppliers_validated += 1;
-            }
+        // Récompenses recalculées et replanifiées, compteur de nœuds resynchronisé
+        let hosts = <ActiveHosts<T>>::iter().collect::<Vec<_>>();
+        let mut total_rewards: Balance = 0;
+        for (account, host) in &hosts {
+            total_rewards = total_rewards.saturating_add(Self::hosting_reward(host));
+            <LastRewardBlock<T>>::insert(account, now);
+            <RewardQueue<T>>::append(now + T::HostingRewardPeriod::get(), account.clone());
+            reads += 1;
+            writes += 2;
         }
+        <TotalHostingRewards>::put(total_rewards);
+        <TotalActiveNodes>::put(hosts.len() as u32);
+        writes += 2;
         
-        // Vérifier que les validateurs requis ont participé
-        if !consumer_validated || !merchant_validated {
-            return Err("Missing required validator");
+        // Index des candidats construit avant le premier tirage, rotations planifiées
+        for (_, host) in &hosts {
+            let node_id = host.node_info.node_id;
+            Self::index_peer(node_id);
+            <PeerRotationQueue<T>>::append(now + T::PeerRotationPeriod::get(), node_id);
+            reads += 1;
+            writes += 4;
         }
+        for (_, host) in &hosts {
+            let node_id = host.node_info.node_id;
+            <NodeConnections>::insert(node_id, Self::select_random_peers(node_id, MAX_RANDOM_CONNECTIONS, &[]));
+        }
+        
+        T::DbWeight::get().reads_writes(reads, writes)
+            .saturating_add(T::WeightInfo::rotate_peers(0, hosts.len() as u32))
+    }
+    
+    /// Annuler une transaction finalisée et reprendre ses effets
+    ///
+    /// L'épargne créditée et les tokens activés sont repris auprès du fonds des consommateurs
+    /// et du système de tokens; l'appelant doit être transactionnel, car un échec de l'un
+    /// laisse l'autre repris.
+    fn reverse_transaction(transaction_id: [u8; 32]) -> frame_support::dispatch::DispatchResult {
+        let settlement = <Settlements<T>>::take(transaction_id).ok_or(Error::<T>::DisputeWindowClosed)?;
+        let transaction = <ValidatedTransactions>::take(transaction_id);
         
-        // Pour être valide, il faut au moins un fournisseur validé (si applicable)
-        if !transaction.suppliers.is_empty() && suppliers_validated == 0 {
-            return Err("No supplier validated");
+        if settlement.savings_credited > 0 {
+            T::ConsumerFund::remove_savings(&transaction.consumer, &transaction.merchant, settlement.savings_credited)
+                .map_err(|_| Error::<T>::ReversalFailed)?;
         }
+        if settlement.tokens_activated > 0 {
+            T::TokenSystem::deactivate_tokens(&transaction.consumer, settlement.tokens_activated)
+                .map_err(|_| Error::<T>::ReversalFailed)?;
+        }
+        
+        // Conserver la transaction annulée jusqu'à son archivage
+        <Disputes<T>>::remove(transaction_id);
+        <ReversedTransactions>::insert(transaction_id, transaction);
+        Self::schedule_archive(transaction_id, ArchiveStatus::Reversed);
+        
+        // Rembourser les séquestres conditionnés à cette transaction
+        T::OnPopFinalized::on_pop_reversed(&transaction_id);
+        
+        Self::deposit_event(RawEvent::TransactionReversed(
+            transaction_id,
+            settlement.tokens_activated,
+            settlement.savings_credited,
+        ));
         
         Ok(())
     }
     
-    fn finalize_transaction(transaction: &PoPTransaction) -> Result<(), &'static str> {
-        // Cette méthode serait appelée par d'autres modules pour finaliser une transaction PoP
-        if <ValidatedTransactions>::contains_key(transaction.id) {
-            return Ok(());
-        }
+    /// Vérifier la cohérence d'une politique de validation
+    ///
+    /// Au moins une signature requise, bornes de fournisseurs cohérentes, délai borné.
+    fn ensure_valid_policy(policy: &ValidationPolicy<T::BlockNumber>) -> Result<(), Error<T>> {
+        let requires_signature = policy.consumer_required
+            || policy.merchant_required
+            || (policy.required_supplier_signatures > 0 && policy.min_suppliers > 0);
+        ensure!(requires_signature, Error::<T>::InvalidValidationPolicy);
+        ensure!(policy.min_suppliers <= policy.max_suppliers, Error::<T>::InvalidValidationPolicy);
+        ensure!(
+            (policy.max_suppliers as usize).saturating_add(2) <= MAX_POP_VALIDATORS,
+            Error::<T>::InvalidValidationPolicy
+        );
+        ensure!(
+            policy.validation_period > 0u32.into()
+                && policy.validation_period <= T::MaxPopTransactionLifetime::get(),
+            Error::<T>::InvalidValidationPolicy
+        );
         
-        Err("Transaction not found or not validated")
+        Ok(())
     }
     
-    fn get_transaction(id: [u8; 32]) -> Result<PoPTransaction, &'static str> {
-        if <ValidatedTransactions>::contains_key(id) {
-            return Ok(<ValidatedTransactions>::get(id));
+    /// Nombre de signatures de fournisseurs exigées par une politique pour une transaction
+    fn required_supplier_signatures(transaction: &PoPTransaction, policy: &ValidationPolicy<T::BlockNumber>) -> usize {
+        sp_std::cmp::min(policy.required_supplier_signatures as usize, transaction.suppliers.len())
+    }
+    
+    /// Nombre total de signatures exigées par une politique pour une transaction
+    fn required_signatures(transaction: &PoPTransaction, policy: &ValidationPolicy<T::BlockNumber>) -> u8 {
+        (Self::required_supplier_signatures(transaction, policy)
+            + policy.consumer_required as usize
+            + policy.merchant_required as usize) as u8
+    }
+    
+    /// Vérifier que les signatures d'une transaction satisfont la politique de validation
+    fn policy_satisfied(
+        transaction: &PoPTransaction,
+        policy: &ValidationPolicy<T::BlockNumber>,
+    ) -> Result<(), Error<T>> {
+        let has_signed = |account: &T::AccountId| transaction.signatures.iter().any(|(signer, _)| signer == account);
+        
+        if policy.consumer_required && !has_signed(&transaction.consumer) {
+            return Err(Error::<T>::MissingConsumerValidation);
         }
         
-        if <PendingTransactions>::contains_key(id) {
-            return Ok(<PendingTransactions>::get(id));
+        if policy.merchant_required && !has_signed(&transaction.merchant) {
+            return Err(Error::<T>::InsufficientValidators);
         }
         
-        Err("Transaction not found")
+        let supplier_signatures = transaction.suppliers.iter().filter(|supplier| has_signed(supplier)).count();
+        ensure!(
+            supplier_signatures >= Self::required_supplier_signatures(transaction, policy),
+            Error::<T>::InsufficientValidators
+        );
+        
+        Ok(())
     }
-}
-
-/// Tests pour le module blockchain
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use frame_support::{assert_ok, assert_noop, parameter_types};
-    use sp_core::H256;
-    use sp_runtime::{
-        testing::Header,
-        traits::{BlakeTwo256, IdentityLookup},
-        Perbill,
-    };
     
-    type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
-    type Block = frame_system::mocking::MockBlock<Test>;
+    /// Nettoyer les transactions PoP expirées
+    ///
+    /// Le délai de chaque transaction est celui de la politique retenue à sa création; les
+    /// transactions sont inscrites à leur échéance dans la file d'expiration.
+    /// Retourne le nombre de compartiments lus et de transactions traitées.
+    fn clean_expired_transactions(now: T::BlockNumber) -> (u32, u32) {
+        sweep_queue::<ExpiryQueue<T>, ExpiryCursor<T>, _, _>(now, T::MaxExpiriesPerBlock::get(), |transaction_id, _| {
+            Self::expire_transaction(transaction_id)
+        })
+    }
     
-    frame_support::construct_runtime!(
-        pub enum Test where
-            Block = Block,
-            NodeBlock = Block,
-            UncheckedExtrinsic = UncheckedExtrinsic,
-        {
-            System: frame_system::{Module, Call, Config, Storage, Event<T>},
-            EtikaBlockchain: Module<Test>,
+    /// Expirer une transaction PoP arrivée à échéance sans avoir été finalisée
+    fn expire_transaction(transaction_id: [u8; 32]) {
+        // La transaction a expiré
+        if <PendingTransactions>::contains_key(transaction_id) {
+            let transaction = <PendingTransactions>::take(transaction_id);
+            <TransactionPolicies<T>>::remove(transaction_id);
+            
+            // Journaliser la transaction expirée pour analyse, jusqu'à son archivage
+            <ExpiredTransactions>::insert(transaction_id, transaction);
+            Self::schedule_archive(transaction_id, ArchiveStatus::Expired);
+            
+            // Émettre un événement d'échec
+            Self::deposit_event(RawEvent::PopTransactionFailed(
+                transaction_id,
+                b"Transaction expired".to_vec(),
+            ));
         }
-    );
+        
+        // Nettoyer les données associées
+        <TransactionStartBlock<T>>::remove(transaction_id);
+    }
     
-    parameter_types! {
-        pub const BlockHashCount: u64 = 250;
-        pub const MaximumBlockWeight: u32 = 1024;
-        pub const MaximumBlockLength: u32 = 2 * 1024;
-        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    /// Compte de réserve des récompenses d'hébergement
+    pub fn reward_pot_account() -> T::AccountId {
+        T::RewardPotId::get().into_account()
     }
     
-    impl frame_system::Config for Test {
-        type BaseCallFilter = ();
-        type BlockWeights = ();
-        type BlockLength = ();
-        type DbWeight = ();
-        type Origin = Origin;
-        type Index = u64;
-        type BlockNumber = u64;
-        type Call = Call;
-        type Hash = H256;
-        type Hashing = BlakeTwo256;
-        type AccountId = u64;
-        type Lookup = IdentityLookup<Self::AccountId>;
-        type Header = Header;
-        type Event = Event;
-        type BlockHashCount = BlockHashCount;
-        type Version = ();
-        type PalletInfo = PalletInfo;
-        type AccountData = ();
-        type OnNewAccount = ();
-        type OnKilledAccount = ();
-        type SystemWeightInfo = ();
-        type SS58Prefix = ();
+    /// Récompense d'une période pour un hôte
+    ///
+    /// La récompense de base est multipliée par le nombre d'unités de stockage offertes
+    /// (plafonné), puis pondérée par la disponibilité et par le type d'acteur.
+    pub fn hosting_reward(host: &BlockchainHost) -> Balance {
+        let storage_units = (host.node_info.storage_capacity / T::RewardStorageUnit::get().max(1))
+            .max(1)
+            .min(MAX_STORAGE_REWARD_UNITS);
+        let base = T::HostingReward::get().saturating_mul(Balance::from(storage_units));
+        let available = Perbill::from_percent(u32::from(host.node_info.availability_score.min(100))) * base;
+        
+        Self::actor_reward_share(&host.actor_type) * available
     }
     
-    parameter_types! {
-        pub const MaxPopTransactionLifetime: u64 = 100;
-        pub const HostingReward: Balance = 10;
-        pub const HostingRewardPeriod: u64 = 10;
-        pub const TokenBurnRatio: u8 = 5; // 5%
-        pub const NGOTokenRatio: u8 = 2; // 2%
+    /// Part de la récompense d'hébergement selon le type d'acteur
+    ///
+    /// Les sponsors et investisseurs, déjà rémunérés par ailleurs, perçoivent une part réduite.
+    fn actor_reward_share(actor_type: &ActorType) -> Perbill {
+        match actor_type {
+            ActorType::Sponsor | ActorType::Investor => Perbill::from_percent(50),
+            _ => Perbill::one(),
+        }
     }
     
-    impl Config for Test {
-        type Event = Event;
-        type MaxPopTransactionLifetime = MaxPopTransactionLifetime;
-        type HostingReward = HostingReward;
-        type HostingRewardPeriod = HostingRewardPeriod;
-        type TokenBurnRatio = TokenBurnRatio;
-        type NGOTokenRatio = NGOTokenRatio;
+    /// Verser un bonus d'hébergement depuis la réserve de récompenses
+    fn pay_hosting_bonus(account: &T::AccountId, bonus: Balance) -> frame_support::dispatch::DispatchResult {
+        T::Currency::transfer(
+            &Self::reward_pot_account(),
+            account,
+            bonus.saturated_into(),
+            ExistenceRequirement::KeepAlive,
+        ).map_err(|_| Error::<T>::RewardPotExhausted)?;
+        
+        Self::deposit_event(RawEvent::HostingRewardClaimed(account.clone(), bonus));
+        
+        Ok(())
     }
     
-    // Fonction utilitaire pour créer un environnement de test
-    fn new_test_ext() -> sp_io::TestExternalities {
+    /// Créditer les récompenses d'hébergement arrivées à échéance
+    ///
+    /// Chaque hôte est crédité une fois par `HostingRewardPeriod`, à compter de son
+    /// enregistrement. Si le total des récompenses de période dépasse `MaxHostingRewardsPerPeriod`,
+    /// elles sont réduites proportionnellement afin que la réserve ne puisse pas être vidée.
+    /// Retourne le nombre de compartiments lus et de récompenses traitées.
+    fn distribute_hosting_rewards(now: T::BlockNumber) -> (u32, u32) {
+        let total = <TotalHostingRewards>::get();
+        let cap = T::MaxHostingRewardsPerPeriod::get();
+        let ratio = if total > cap {
+            Perbill::from_rational_approximation(cap, total)
+        } else {
+            Perbill::one()
+        };
+        
+        sweep_queue::<RewardQueue<T>, RewardCursor<T>, _, _>(now, T::MaxRewardsPerBlock::get(), |account, due| {
+            Self::credit_hosting_reward(account, due, ratio)
+        })
+    }
+    
+    /// Créditer la récompense d'une période à un hôte et planifier la suivante
+    fn credit_hosting_reward(account: T::AccountId, due: T::BlockNumber, ratio: Perbill) {
+        let period = T::HostingRewardPeriod::get();
+        
+        // Hôte parti, ou planification antérieure à un nouvel enregistrement
+        if !<LastRewardBlock<T>>::contains_key(&account) || Self::last_reward_block(&account) + period != due {
+            return;
+        }
+        
+        let mut host = <ActiveHosts<T>>::get(&account);
+        let reward_amount = ratio * Self::hosting_reward(&host);
+        
+        // Accumuler la récompense
+        host.hosting_bonus = host.hosting_bonus.saturating_add(reward_amount);
+        <ActiveHosts<T>>::insert(&account, host);
+        
+        // Mettre à jour le bloc de dernière récompense et planifier la prochaine
+        <LastRewardBlock<T>>::insert(&account, due);
+        <RewardQueue<T>>::append(due + period, account.clone());
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::HostingRewardPaid(account, reward_amount));
+    }
+    
+    /// Enregistrer un hôte en tenant à jour le total des récompenses de période
+    fn put_host(account: &T::AccountId, host: BlockchainHost) {
+        let previous = if <ActiveHosts<T>>::contains_key(account) {
+            Self::hosting_reward(&<ActiveHosts<T>>::get(account))
+        } else {
+            0
+        };
+        let reward = Self::hosting_reward(&host);
+        
+        <TotalHostingRewards>::mutate(|total| *total = total.saturating_sub(previous).saturating_add(reward));
+        <ActiveHosts<T>>::insert(account, host);
+    }
+    
+    /// Inscrire une transaction finalisée, expirée ou annulée dans la file d'archivage
+    fn schedule_archive(transaction_id: [u8; 32], status: ArchiveStatus) {
+        let due = <frame_system::Module<T>>::block_number() + T::RetentionPeriod::get();
+        <ArchiveQueue<T>>::append(due, (transaction_id, status));
+    }
+    
+    /// Archiver les transactions dont la durée de conservation est écoulée
+    ///
+    /// Le corps de chaque transaction est retiré de l'état et remplacé par une feuille de l'arbre
+    /// de la période en cours; il est conservé dans l'index offchain des nœuds d'archive pour
+    /// la construction des preuves d'inclusion. À chaque changement de période, l'arbre de la
+    /// période précédente est clos et seule sa racine est conservée.
+    /// Retourne le nombre de compartiments lus et de transactions archivées.
+    fn archive_transactions(now: T::BlockNumber) -> (u32, u32) {
+        let period = (now / T::ArchivePeriod::get()).saturated_into::<u32>();
+        
+        let mut archive = match <CurrentArchive>::get() {
+            Some((current, frontier)) if current < period => {
+                Self::close_archive_period(current, &frontier);
+                (period, ArchiveFrontier::default())
+            }
+            Some(archive) => archive,
+            None => (period, ArchiveFrontier::default()),
+        };
+        
+        let swept = sweep_queue::<ArchiveQueue<T>, ArchiveCursor<T>, _, _>(
+            now,
+            T::MaxArchivedPerBlock::get(),
+            |(transaction_id, status), _| Self::archive_transaction(&mut archive, transaction_id, status),
+        );
+        
+        if archive.1.leaf_count > 0 {
+            <CurrentArchive>::put(archive);
+        } else {
+            <CurrentArchive>::kill();
+        }
+        
+        swept
+    }
+    
+    /// Rendre définitifs les règlements dont le délai de contestation est écoulé
+    ///
+    /// Retourne le nombre de compartiments lus et de transactions traitées.
+    fn settle_transactions(now: T::BlockNumber) -> (u32, u32) {
+        sweep_queue::<SettlementQueue<T>, SettlementCursor<T>, _, _>(
+            now,
+            T::MaxSettlementsPerBlock::get(),
+            |transaction_id, _| Self::settle_transaction(transaction_id),
+        )
+    }
+    
+    /// Clore le délai de contestation d'une transaction et libérer ses séquestres conditionnels
+    ///
+    /// Une transaction dont le litige est encore ouvert est réglée par sa résolution; une
+    /// transaction déjà annulée ou archivée n'a plus de règlement en attente.
+    fn settle_transaction(transaction_id: [u8; 32]) {
+        if <Disputes<T>>::contains_key(transaction_id) {
+            return;
+        }
+        
+        if <Settlements<T>>::take(transaction_id).is_some() {
+            T::OnPopFinalized::on_pop_finalized(&transaction_id);
+        }
+    }
+    
+    /// Remplacer le corps d'une transaction par une feuille de l'arbre d'archivage
+    ///
+    /// Une transaction dont le litige est encore ouvert est reportée d'un délai de contestation.
+    /// Une transaction dont le règlement n'a pas encore été traité par la file des règlements
+    /// (file en retard) est réglée à son archivage.
+    fn archive_transaction(archive: &mut (u32, ArchiveFrontier), transaction_id: [u8; 32], status: ArchiveStatus) {
+        let transaction = match status {
+            ArchiveStatus::Validated if <Disputes<T>>::contains_key(transaction_id) => {
+                let now = <frame_system::Module<T>>::block_number();
+                <ArchiveQueue<T>>::append(now + T::DisputeWindow::get().max(1u32.into()), (transaction_id, status));
+                return;
+            }
+            ArchiveStatus::Validated if <ValidatedTransactions>::contains_key(transaction_id) => {
+                if <Settlements<T>>::take(transaction_id).is_some() {
+                    T::OnPopFinalized::on_pop_finalized(&transaction_id);
+                }
+                <ValidatedTransactions>::take(transaction_id)
+            }
+            ArchiveStatus::Expired if <ExpiredTransactions>::contains_key(transaction_id) => {
+                <ExpiredTransactions>::take(transaction_id)
+            }
+            ArchiveStatus::Reversed if <ReversedTransactions>::contains_key(transaction_id) => {
+                <ReversedTransactions>::take(transaction_id)
+            }
+            _ => return,
+        };
+        
+        let leaf_index = archive.1.append(archive_leaf(status, &transaction));
+        <SupplierLineItems>::remove_prefix(transaction_id);
+        <TransactionPolicies<T>>::remove(transaction_id);
+        
+        // Corps et position conservés hors de l'état par les nœuds indexant l'offchain
+        sp_io::offchain_index::set(&archive_leaf_key(archive.0, leaf_index), &(status, transaction).encode());
+        sp_io::offchain_index::set(&archive_position_key(&transaction_id), &(archive.0, leaf_index).encode());
+    }
+    
+    /// Clore une période d'archivage en ne conservant que sa racine
+    fn close_archive_period(period: u32, frontier: &ArchiveFrontier) {
+        let commitment = ArchiveCommitment {
+            root: frontier.root(),
+            leaf_count: frontier.leaf_count,
+        };
+        
+        sp_io::offchain_index::set(&archive_period_key(period), &commitment.encode());
+        <ArchiveRoots>::insert(period, commitment.clone());
+        
+        Self::deposit_event(RawEvent::ArchivePeriodClosed(period, commitment.root, commitment.leaf_count));
+    }
+    
+    /// Vérifier une preuve d'inclusion contre la racine on-chain de sa période
+    pub fn verify_archive_proof(proof: &ArchiveProof) -> bool {
+        let commitment = match Self::archive_roots(proof.period) {
+            Some(commitment) => commitment,
+            None => return false,
+        };
+        
+        if proof.leaf_index >= commitment.leaf_count || proof.siblings.len() != ARCHIVE_TREE_DEPTH {
+            return false;
+        }
+        
+        let leaf = archive_leaf(proof.status, &proof.transaction);
+        merkle_root_from_proof(leaf, proof.leaf_index, &proof.siblings) == commitment.root
+    }
+    
+    /// Réputation courante d'un nœud
+    pub fn peer_reputation(node_id: [u8; 32]) -> u8 {
+        <NodeReputations>::get(node_id).unwrap_or(INITIAL_PEER_REPUTATION)
+    }
+    
+    /// Exclure un hôte dont la réputation est insuffisante
+    ///
+    /// Le bonus accumulé est versé; si la réserve est épuisée, il reste réclamable par `claim_hosting_rewards`.
+    fn evict_host(account: &T::AccountId, node_id: [u8; 32]) {
+        let bonus = <ActiveHosts<T>>::get(account).hosting_bonus;
+        if bonus > 0 && Self::pay_hosting_bonus(account, bonus).is_err() {
+            <PendingHostingBonus<T>>::mutate(account, |pending| *pending = pending.saturating_add(bonus));
+        }
+        
+        Self::remove_host(account, node_id);
+        Self::deposit_event(RawEvent::NodeEvicted(account.clone(), node_id, bonus));
+    }
+    
+    /// Retirer un hôte et son nœud du réseau
+    ///
+    /// Le bonus d'hébergement n'est pas traité ici: l'appelant le verse, le conserve ou le confisque.
+    fn remove_host(account: &T::AccountId, node_id: [u8; 32]) {
+        let reward = Self::hosting_reward(&<ActiveHosts<T>>::take(account));
+        <TotalHostingRewards>::mutate(|total| *total = total.saturating_sub(reward));
+        <NodeIdToAccount<T>>::remove(node_id);
+        <LastRewardBlock<T>>::remove(account);
+        <NodeConnections>::remove(node_id);
+        <NodeReportCounts>::remove_prefix(node_id);
+        Self::unindex_peer(node_id);
+        
+        // Décrémenter le compteur de nœuds
+        <TotalActiveNodes>::mutate(|total| *total = total.saturating_sub(1));
+    }
+    
+    /// Sélectionner des pairs aléatoires pour un nœud (protection contre attaques d'éclipse)
+    ///
+    /// Les pairs sont tirés sans remise à partir de l'aléa on-chain, avec une probabilité
+    /// proportionnelle à leur réputation, parmi au plus `MaxPeerCandidates` candidats.
+    /// Le nœud lui-même et les pairs `excluded` ne sont pas tirés.
+    fn select_random_peers(
+        node_id: [u8; 32],
+        count: usize,
+        excluded: &[[u8; 32]],
+    ) -> Vec<PeerConnection> {
+        let mut result = Vec::new();
+        let mut candidates = Vec::new();
+        
+        // Graine propre à chaque sélection, y compris entre deux sélections d'un même bloc
+        let nonce = <PeerSelectionNonce>::mutate(|nonce| {
+            *nonce = nonce.wrapping_add(1);
+            *nonce
+        });
+        let seed = T::Randomness::random(&(PEER_SELECTION_CONTEXT, node_id, nonce).encode());
+        
+        // Collecter les candidats avec leur poids
+        for candidate in Self::peer_candidates(&seed, T::MaxPeerCandidates::get()) {
+            if candidate == node_id || excluded.contains(&candidate) {
+                continue;
+            }
+            
+            let reputation = Self::peer_reputation(candidate);
+            
+            // Un pair de réputation nulle reste sélectionnable, avec une probabilité minimale
+            candidates.push((candidate, reputation, u64::from(reputation.max(1))));
+        }
+        
+        // Ordre canonique, indépendant de l'ordre de l'index
+        candidates.sort_by(|a, b| a.0.cmp(&b.0));
+        
+        let mut total_weight: u64 = candidates.iter().map(|(_, _, weight)| weight).sum();
+        
+        // Tirage pondéré sans remise
+        for draw in 0..count {
+            if candidates.is_empty() {
+                break;
+            }
+            
+            let mut target = Self::random_u64(&seed, draw as u32) % total_weight;
+            let mut index = 0;
+            while target >= candidates[index].2 {
+                target -= candidates[index].2;
+                index += 1;
+            }
+            
+            let (selected, reputation, weight) = candidates.remove(index);
+            total_weight -= weight;
+            
+            result.push(PeerConnection {
+                node_id: selected,
+                last_seen: Self::get_current_timestamp(),
+                reputation,
+            });
+        }
+        
+        result
+    }
+    
+    /// Nœuds candidats à une sélection de pairs, lus dans l'index dense des nœuds
+    ///
+    /// Au-delà de `limit` nœuds indexés, `limit` positions sont tirées au hasard (les doublons
+    /// étant ignorés): le coût d'une sélection ne dépend pas de la taille du réseau.
+    fn peer_candidates(seed: &T::Hash, limit: u32) -> Vec<[u8; 32]> {
+        let count = <PeerCount>::get();
+        
+        let positions = if count <= limit {
+            (0..count).collect::<Vec<_>>()
+        } else {
+            let seed = T::Hashing::hash_of(&(seed, PEER_SAMPLING_CONTEXT));
+            let mut positions = (0..limit)
+                .map(|draw| (Self::random_u64(&seed, draw) % u64::from(count)) as u32)
+                .collect::<Vec<_>>();
+            positions.sort();
+            positions.dedup();
+            positions
+        };
+        
+        positions.into_iter().filter_map(|position| <PeerIndex>::get(position)).collect()
+    }
+    
+    /// Inscrire un nœud dans l'index des candidats à la sélection de pairs
+    fn index_peer(node_id: [u8; 32]) {
+        if <PeerPositions>::contains_key(node_id) {
+            return;
+        }
+        
+        let position = <PeerCount>::get();
+        <PeerIndex>::insert(position, node_id);
+        <PeerPositions>::insert(node_id, position);
+        <PeerCount>::put(position + 1);
+    }
+    
+    /// Retirer un nœud de l'index des candidats; le dernier nœud indexé prend sa position
+    fn unindex_peer(node_id: [u8; 32]) {
+        let position = match <PeerPositions>::take(node_id) {
+            Some(position) => position,
+            None => return,
+        };
+        
+        let last = <PeerCount>::get().saturating_sub(1);
+        if position != last {
+            if let Some(moved) = <PeerIndex>::get(last) {
+                <PeerIndex>::insert(position, moved);
+                <PeerPositions>::insert(moved, position);
+            }
+        }
+        <PeerIndex>::remove(last);
+        <PeerCount>::put(last);
+    }
+    
+    /// Dériver un entier aléatoire de la graine pour le tirage de rang `draw`
+    fn random_u64(seed: &T::Hash, draw: u32) -> u64 {
+        let hash = T::Hashing::hash_of(&(seed, draw));
+        u64::decode(&mut hash.as_ref()).unwrap_or_default()
+    }
+    
+    /// Rotation des pairs pour contrer les attaques d'éclipse
+    ///
+    /// Les nœuds arrivés à échéance dans la file de rotation sont traités dans la limite de
+    /// `MaxPeerRotationsPerBlock`, puis replanifiés une période de rotation plus tard.
+    /// Retourne le nombre de compartiments lus et de nœuds concernés.
+    fn rotate_peers(now: T::BlockNumber) -> (u32, u32) {
+        let (buckets, rotated) = sweep_queue::<PeerRotationQueue<T>, PeerRotationCursor<T>, _, _>(
+            now,
+            T::MaxPeerRotationsPerBlock::get(),
+            |node_id, _| {
+                // Les nœuds retirés du réseau ne sont plus replanifiés
+                if <NodeIdToAccount<T>>::contains_key(node_id) {
+                    Self::rotate_node_peers(node_id);
+                    <PeerRotationQueue<T>>::append(now + T::PeerRotationPeriod::get(), node_id);
+                }
+            },
+        );
+        
+        // Émettre un événement pour le monitoring
+        if rotated > 0 {
+            Self::deposit_event(RawEvent::PeersRotated(rotated));
+        }
+        
+        (buckets, rotated)
+    }
+    
+    /// Renouveler les connexions d'un nœud: les plus réputées sont conservées, les autres retirées au sort
+    fn rotate_node_peers(node_id: [u8; 32]) {
+        // Oublier les pairs exclus et actualiser la réputation des autres
+        let mut sorted_connections = <NodeConnections>::get(node_id).into_iter()
+            .filter(|conn| <NodeIdToAccount<T>>::contains_key(conn.node_id))
+            .map(|mut conn| {
+                conn.reputation = Self::peer_reputation(conn.node_id);
+                conn
+            })
+            .collect::<Vec<_>>();
+        
+        // Trier les connexions par réputation
+        sorted_connections.sort_by(|a, b| b.reputation.cmp(&a.reputation));
+        
+        // Garder les top connexions et en ajouter de nouvelles aléatoirement
+        let top_connections = sorted_connections.iter()
+            .take(MAX_PERSISTENT_CONNECTIONS)
+            .cloned()
+            .collect::<Vec<_>>();
+        
+        // Les connexions conservées ne sont pas retirées au sort une seconde fois
+        let kept = top_connections.iter().map(|conn| conn.node_id).collect::<Vec<_>>();
+        let random_peers = Self::select_random_peers(node_id, MAX_RANDOM_CONNECTIONS, &kept);
+        
+        // Fusionner les connexions conservées et les nouveaux pairs
+        let mut new_connections = top_connections;
+        new_connections.extend(random_peers);
+        
+        // Mettre à jour les connexions
+        <NodeConnections>::insert(node_id, new_connections);
+    }
+}
+
+/// Implémentation du trait PoPConsensus pour le module blockchain
+impl<T: Config> PoPConsensus for Module<T> {
+    fn validate_transaction(transaction: &PoPTransaction) -> Result<(), &'static str> {
+        // Retrouver la politique de validation retenue à la création
+        let (_, policy) = Self::transaction_policies(transaction.id).ok_or("Unknown validation policy")?;
+        
+        // Vérifier cryptographiquement chaque signature et rejeter les doublons et signataires étrangers
+        Self::verify_signatures(transaction).map_err(|error| match error {
+            Error::<T>::UnauthorizedValidator => "Signer is not a participant",
+            Error::<T>::AlreadyValidated => "Duplicate signature",
+            _ => "Invalid signature",
+        })?;
+        
+        // Vérifier que les validateurs requis par la politique ont participé
+        Self::policy_satisfied(transaction, &policy).map_err(|error| match error {
+            Error::<T>::MissingConsumerValidation => "Consumer must validate the transaction",
+            _ => "Insufficient validators",
+        })
+    }
+    
+    fn finalize_transaction(transaction: &PoPTransaction) -> Result<(), &'static str> {
+        // Cette méthode serait appelée par d'autres modules pour finaliser une transaction PoP
+        if <ValidatedTransactions>::contains_key(transaction.id) {
+            return Ok(());
+        }
+        
+        Err("Transaction not found or not validated")
+    }
+    
+    fn get_transaction(id: [u8; 32]) -> Result<PoPTransaction, &'static str> {
+        if <ValidatedTransactions>::contains_key(id) {
+            return Ok(<ValidatedTransactions>::get(id));
+        }
+        
+        if <PendingTransactions>::contains_key(id) {
+            return Ok(<PendingTransactions>::get(id));
+        }
+        
+        Err("Transaction not found")
+    }
+}
+
+/// Benchmarks des opérations de maintenance exécutées dans `on_initialize`
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+    use super::*;
+    use frame_benchmarking::{account, benchmarks};
+    
+    fn node_id(index: u32) -> [u8; 32] {
+        *BlakeTwo256::hash_of(&(b"node", index)).as_fixed_bytes()
+    }
+    
+    fn pop_transaction(index: u32) -> PoPTransaction {
+        PoPTransaction {
+            id: *BlakeTwo256::hash_of(&(b"transaction", index)).as_fixed_bytes(),
+            consumer: account("consumer", index, 0),
+            merchant: account("merchant", index, 0),
+            suppliers: Vec::new(),
+            standard_amount: 100,
+            tokens_exchanged: 10,
+            savings_generated: 5,
+            timestamp: 0,
+            receipt_hash: [0; 32],
+            signatures: Vec::new(),
+            nonce: 0,
+        }
+    }
+    
+    // Transaction PoP en attente, inscrite dans la file d'expiration au bloc `due`
+    fn pending_transaction<T: Config>(index: u32, due: T::BlockNumber) {
+        let transaction = pop_transaction(index);
+        let transaction_id = transaction.id;
+        
+        <PendingTransactions>::insert(transaction_id, transaction);
+        <TransactionStartBlock<T>>::insert(transaction_id, T::BlockNumber::from(0u32));
+        <ExpiryQueue<T>>::append(due, transaction_id);
+    }
+    
+    // Transaction PoP finalisée, inscrite dans la file d'archivage au bloc `due`
+    fn validated_transaction<T: Config>(index: u32, due: T::BlockNumber) {
+        let transaction = pop_transaction(index);
+        let transaction_id = transaction.id;
+        
+        <ValidatedTransactions>::insert(transaction_id, transaction);
+        <ArchiveQueue<T>>::append(due, (transaction_id, ArchiveStatus::Validated));
+    }
+    
+    // Hôte actif dont la récompense est due au bloc `due` (au moins une période après la genèse)
+    fn active_host<T: Config>(index: u32, due: T::BlockNumber) -> T::AccountId {
+        let host: T::AccountId = account("host", index, 0);
+        let node_id = node_id(index);
+        
+        Module::<T>::put_host(&host, BlockchainHost {
+            account_id: host.clone(),
+            actor_type: ActorType::Merchant,
+            node_info: NodeInfo {
+                node_id,
+                network_address: vec![0; MAX_NETWORK_ADDRESS_LENGTH],
+                storage_capacity: u64::max_value(),
+                availability_score: 100,
+                software_version: vec![0; MAX_SOFTWARE_VERSION_LENGTH],
+            },
+            hosting_bonus: 0,
+            hosting_since: 0,
+        });
+        <NodeIdToAccount<T>>::insert(node_id, Some(host.clone()));
+        Module::<T>::index_peer(node_id);
+        <LastRewardBlock<T>>::insert(&host, due - T::HostingRewardPeriod::get());
+        <RewardQueue<T>>::append(due, host.clone());
+        
+        host
+    }
+    
+    benchmarks! {
+        // `e` transactions dans le dernier de `b` compartiments en retard
+        sweep_expired_transactions {
+            let b in 1 .. T::MaxExpiriesPerBlock::get();
+            let e in 0 .. T::MaxExpiriesPerBlock::get();
+            
+            let start = T::BlockNumber::from(1u32);
+            let now = start + (b - 1).into();
+            for index in 0 .. e {
+                pending_transaction::<T>(index, now);
+            }
+            <ExpiryCursor<T>>::put(start);
+        }: { Module::<T>::clean_expired_transactions(now) }
+        verify {
+            assert_eq!(<PendingTransactions>::iter().count(), 0);
+            assert_eq!(Module::<T>::expiry_cursor(), None);
+        }
+        
+        // Une file deux fois plus longue que la limite n'est traitée que jusqu'à la limite
+        sweep_expired_transactions_backlog {
+            let limit = T::MaxExpiriesPerBlock::get();
+            let now = T::BlockNumber::from(1u32);
+            for index in 0 .. 2 * limit {
+                pending_transaction::<T>(index, now);
+            }
+        }: { Module::<T>::clean_expired_transactions(now) }
+        verify {
+            assert_eq!(Module::<T>::expiry_queue(now).len() as u32, limit);
+            assert_eq!(Module::<T>::expiry_cursor(), Some(now));
+        }
+        
+        // `h` récompenses dans le dernier de `b` compartiments en retard
+        credit_hosting_rewards {
+            let b in 1 .. T::MaxRewardsPerBlock::get();
+            let h in 0 .. T::MaxRewardsPerBlock::get();
+            
+            let start = T::HostingRewardPeriod::get();
+            let now = start + (b - 1).into();
+            let hosts = (0 .. h).map(|index| active_host::<T>(index, now)).collect::<Vec<_>>();
+            <RewardCursor<T>>::put(start);
+        }: { Module::<T>::distribute_hosting_rewards(now) }
+        verify {
+            for host in hosts {
+                assert!(<ActiveHosts<T>>::get(&host).hosting_bonus > 0);
+            }
+            assert_eq!(Module::<T>::reward_cursor(), None);
+        }
+        
+        // Une file deux fois plus longue que la limite n'est traitée que jusqu'à la limite
+        credit_hosting_rewards_backlog {
+            let limit = T::MaxRewardsPerBlock::get();
+            let now = T::HostingRewardPeriod::get();
+            for index in 0 .. 2 * limit {
+                active_host::<T>(index, now);
+            }
+        }: { Module::<T>::distribute_hosting_rewards(now) }
+        verify {
+            assert_eq!(Module::<T>::reward_queue(now).len() as u32, limit);
+            assert_eq!(Module::<T>::reward_cursor(), Some(now));
+        }
+        
+        // `a` transactions dans le dernier de `b` compartiments en retard, une période étant à clore
+        archive_transactions {
+            let b in 1 .. T::MaxArchivedPerBlock::get();
+            let a in 0 .. T::MaxArchivedPerBlock::get();
+            
+            let start = T::ArchivePeriod::get();
+            let now = start + (b - 1).into();
+            for index in 0 .. a {
+                validated_transaction::<T>(index, now);
+            }
+            <ArchiveCursor<T>>::put(start);
+            
+            let mut frontier = ArchiveFrontier::default();
+            frontier.append(H256::zero());
+            <CurrentArchive>::put((0, frontier));
+        }: { Module::<T>::archive_transactions(now) }
+        verify {
+            assert_eq!(<ValidatedTransactions>::iter().count(), 0);
+            assert!(Module::<T>::archive_roots(0).is_some());
+        }
+        
+        // `s` règlements à rendre définitifs dans le dernier de `b` compartiments en retard
+        settle_transactions {
+            let b in 1 .. T::MaxSettlementsPerBlock::get();
+            let s in 0 .. T::MaxSettlementsPerBlock::get();
+            
+            let start = T::DisputeWindow::get() + 1u32.into();
+            let now = start + (b - 1).into();
+            for index in 0 .. s {
+                let transaction_id = pop_transaction(index).id;
+                <Settlements<T>>::insert(transaction_id, Settlement {
+                    finalized_at: 0u32.into(),
+                    tokens_activated: 1,
+                    savings_credited: 1,
+                });
+                <SettlementQueue<T>>::append(now, transaction_id);
+            }
+            <SettlementCursor<T>>::put(start);
+        }: { Module::<T>::settle_transactions(now) }
+        verify {
+            assert_eq!(<Settlements<T>>::iter().count(), 0);
+            assert_eq!(Module::<T>::settlement_cursor(), None);
+        }
+        
+        // `n` nœuds disposant chacun d'un jeu complet de connexions, dans le dernier de `b`
+        // compartiments en retard, parmi au moins `MaxPeerCandidates` nœuds indexés
+        rotate_peers {
+            let b in 1 .. T::MaxPeerRotationsPerBlock::get();
+            let n in 0 .. T::MaxPeerRotationsPerBlock::get();
+            
+            let period = T::HostingRewardPeriod::get();
+            let nodes = T::MaxPeerCandidates::get().max(n).max(1);
+            for index in 0 .. nodes {
+                active_host::<T>(index, period);
+            }
+            
+            let start = T::BlockNumber::from(1u32);
+            let now = start + (b - 1).into();
+            for index in 0 .. n {
+                let connections = (1 ..= (MAX_PERSISTENT_CONNECTIONS + MAX_RANDOM_CONNECTIONS) as u32)
+                    .map(|offset| PeerConnection {
+                        node_id: node_id((index + offset) % nodes),
+                        last_seen: 0,
+                        reputation: INITIAL_PEER_REPUTATION,
+                    })
+                    .collect::<Vec<_>>();
+                <NodeConnections>::insert(node_id(index), connections);
+                <PeerRotationQueue<T>>::append(now, node_id(index));
+            }
+            <PeerRotationCursor<T>>::put(start);
+        }: { Module::<T>::rotate_peers(now) }
+        verify {
+            assert_eq!(<PeerRotationQueue<T>>::get(now + T::PeerRotationPeriod::get()).len() as u32, n);
+        }
+    }
+}
+
+/// Tests pour le module blockchain
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_support::{
+        assert_ok, assert_noop, parameter_types,
+        dispatch::{DispatchError, DispatchResult},
+        traits::{OnInitialize, OnRuntimeUpgrade},
+    };
+    use sp_core::{ecdsa, ed25519, sr25519, Pair, H256};
+    use std::{cell::RefCell, collections::BTreeMap};
+    use sp_runtime::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentifyAccount, IdentityLookup},
+        DispatchError::BadOrigin, MultiSigner, Perbill,
+    };
+    
+    type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+    type Block = frame_system::mocking::MockBlock<Test>;
+    
+    frame_support::construct_runtime!(
+        pub enum Test where
+            Block = Block,
+            NodeBlock = Block,
+            UncheckedExtrinsic = UncheckedExtrinsic,
+        {
+            System: frame_system::{Module, Call, Config, Storage, Event<T>},
+            Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+            EtikaBlockchain: Module<Test>,
+        }
+    );
+    
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    
+    impl frame_system::Config for Test {
+        type BaseCallFilter = ();
+        type BlockWeights = ();
+        type BlockLength = ();
+        type DbWeight = ();
+        type Origin = Origin;
+        type Index = u64;
+        type BlockNumber = u64;
+        type Call = Call;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = AccountId;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = Event;
+        type BlockHashCount = BlockHashCount;
+        type Version = ();
+        type PalletInfo = PalletInfo;
+        type AccountData = pallet_balances::AccountData<Balance>;
+        type OnNewAccount = ();
+        type OnKilledAccount = ();
+        type SystemWeightInfo = ();
+        type SS58Prefix = ();
+    }
+    
+    parameter_types! {
+        pub const ExistentialDeposit: Balance = 1;
+        pub const MaxLocks: u32 = 50;
+    }
+    
+    impl pallet_balances::Config for Test {
+        type MaxLocks = MaxLocks;
+        type Balance = Balance;
+        type Event = Event;
+        type DustRemoval = ();
+        type ExistentialDeposit = ExistentialDeposit;
+        type AccountStore = System;
+        type WeightInfo = ();
+    }
+    
+    parameter_types! {
+        pub const MaxPopTransactionLifetime: u64 = 100;
+        pub const HostingRewardPotId: ModuleId = ModuleId(*b"etk/host");
+        pub const HostingReward: Balance = 10;
+        pub const RewardStorageUnit: u64 = 1024;
+        pub const MaxHostingRewardsPerPeriod: Balance = 25;
+        pub const HostingRewardPeriod: u64 = 10;
+        pub const MaxExpiriesPerBlock: u32 = 2;
+        pub const MaxRewardsPerBlock: u32 = 4;
+        pub const RetentionPeriod: u64 = 20;
+        pub const ArchivePeriod: u64 = 50;
+        pub const MaxArchivedPerBlock: u32 = 4;
+        pub const MaxSettlementsPerBlock: u32 = 4;
+        pub const PeerRotationPeriod: u64 = 20; // rotation des pairs tous les 20 blocs
+        pub const MaxPeerRotationsPerBlock: u32 = 2;
+        pub const MaxPeerCandidates: u32 = 16;
+        pub const MinHostReputation: u8 = 20;
+        pub const ReportCooldown: u64 = 5;
+        pub const MaxReportsPerReporter: u32 = 1;
+        pub const ArbitratorRole: [u8; 32] = [7; 32];
+        pub const SystemSaleRole: [u8; 32] = [8; 32];
+        pub const DisputeWindow: u64 = 10;
+    }
+    
+    thread_local! {
+        static RANDOM_SEED: RefCell<u64> = RefCell::new(0);
+    }
+    
+    /// Source d'aléa déterministe, contrôlée par les tests via `set_random_seed`
+    pub struct MockRandomness;
+    
+    impl Randomness<H256> for MockRandomness {
+        fn random(subject: &[u8]) -> H256 {
+            let seed = RANDOM_SEED.with(|seed| *seed.borrow());
+            BlakeTwo256::hash_of(&(subject, seed))
+        }
+    }
+    
+    fn set_random_seed(seed: u64) {
+        RANDOM_SEED.with(|value| *value.borrow_mut() = seed);
+    }
+    
+    thread_local! {
+        static ACTIVE_TOKENS: RefCell<BTreeMap<AccountId, Balance>> = RefCell::new(BTreeMap::new());
+        static SAVINGS: RefCell<BTreeMap<AccountId, Balance>> = RefCell::new(BTreeMap::new());
+        static SPECIAL_AUTHORITIES: RefCell<Vec<([u8; 32], AccountId)>> = RefCell::new(Vec::new());
+        static POP_NOTIFICATIONS: RefCell<Vec<([u8; 32], bool)>> = RefCell::new(Vec::new());
+    }
+    
+    type Ledger = &'static std::thread::LocalKey<RefCell<BTreeMap<AccountId, Balance>>>;
+    
+    fn credit(ledger: Ledger, account: &AccountId, amount: Balance) {
+        ledger.with(|ledger| *ledger.borrow_mut().entry(account.clone()).or_insert(0) += amount);
+    }
+    
+    fn debit(ledger: Ledger, account: &AccountId, amount: Balance) -> Result<(), &'static str> {
+        ledger.with(|ledger| {
+            let mut ledger = ledger.borrow_mut();
+            let balance = ledger.entry(account.clone()).or_insert(0);
+            *balance = balance.checked_sub(amount).ok_or("Insufficient balance")?;
+            Ok(())
+        })
+    }
+    
+    fn balance_of(ledger: Ledger, account: &AccountId) -> Balance {
+        ledger.with(|ledger| ledger.borrow().get(account).copied().unwrap_or(0))
+    }
+    
+    /// Système de tokens de test: seuls les tokens actifs sont suivis
+    pub struct MockTokenSystem;
+    
+    impl TokenSystem for MockTokenSystem {
+        fn distribute_tokens(_to: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
+        
+        fn activate_tokens(from: &AccountId, amount: Balance) -> Result<(), &'static str> {
+            credit(&ACTIVE_TOKENS, from, amount);
+            Ok(())
+        }
+        
+        fn deactivate_tokens(from: &AccountId, amount: Balance) -> Result<(), &'static str> {
+            debit(&ACTIVE_TOKENS, from, amount)
+        }
+        
+        fn burn_tokens(from: &AccountId, amount: Balance) -> Result<(), &'static str> {
+            debit(&ACTIVE_TOKENS, from, amount)
+        }
+        
+        fn transfer_tokens(from: &AccountId, to: &AccountId, amount: Balance) -> Result<(), &'static str> {
+            debit(&ACTIVE_TOKENS, from, amount)?;
+            credit(&ACTIVE_TOKENS, to, amount);
+            Ok(())
+        }
+        
+        fn active_balance(account: &AccountId) -> Balance {
+            balance_of(&ACTIVE_TOKENS, account)
+        }
+        
+        fn reserve_tokens(_account: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
+        
+        fn unreserve_tokens(_account: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
+    }
+    
+    /// Fonds des consommateurs de test: épargne totale par consommateur
+    pub struct MockConsumerFund;
+    
+    impl ConsumerFund for MockConsumerFund {
+        fn add_savings(consumer: &AccountId, _contributor: &AccountId, amount: Balance) -> Result<(), &'static str> {
+            credit(&SAVINGS, consumer, amount);
+            Ok(())
+        }
+        
+        fn remove_savings(consumer: &AccountId, _contributor: &AccountId, amount: Balance) -> Result<(), &'static str> {
+            debit(&SAVINGS, consumer, amount)
+        }
+        
+        fn get_savings_balance(consumer: &AccountId) -> Result<(Balance, Balance), &'static str> {
+            Ok((balance_of(&SAVINGS, consumer), 0))
+        }
+        
+        fn calculate_credit_rate(_consumer: &AccountId) -> Result<u32, &'static str> {
+            Ok(500)
+        }
+    }
+    
+    /// Autorisations spéciales de test: les rôles accordés par `grant_role`
+    pub struct MockSpecialAuthority;
+    
+    impl SpecialAuthority<AccountId> for MockSpecialAuthority {
+        fn has_special_authority(account: &AccountId, role_id: &[u8; 32]) -> bool {
+            SPECIAL_AUTHORITIES.with(|roles| roles.borrow().iter().any(|(role, holder)| role == role_id && holder == account))
+        }
+    }
+    
+    fn grant_role(role: [u8; 32], account: AccountId) {
+        SPECIAL_AUTHORITIES.with(|roles| roles.borrow_mut().push((role, account)));
+    }
+    
+    fn add_arbitrator(account: AccountId) {
+        grant_role(ArbitratorRole::get(), account);
+    }
+    
+    fn add_system_operator(account: AccountId) {
+        grant_role(SystemSaleRole::get(), account);
+    }
+    
+    /// Notifications de règlement de test: (transaction, définitive) dans l'ordre d'appel
+    pub struct MockPopNotifier;
+    
+    impl OnPopFinalized for MockPopNotifier {
+        fn on_pop_finalized(transaction_id: &[u8; 32]) {
+            POP_NOTIFICATIONS.with(|notifications| notifications.borrow_mut().push((*transaction_id, true)));
+        }
+        
+        fn on_pop_reversed(transaction_id: &[u8; 32]) {
+            POP_NOTIFICATIONS.with(|notifications| notifications.borrow_mut().push((*transaction_id, false)));
+        }
+    }
+    
+    fn pop_notifications() -> Vec<([u8; 32], bool)> {
+        POP_NOTIFICATIONS.with(|notifications| notifications.borrow().clone())
+    }
+    
+    impl Config for Test {
+        type Event = Event;
+        type MaxPopTransactionLifetime = MaxPopTransactionLifetime;
+        type Currency = Balances;
+        type RewardPotId = HostingRewardPotId;
+        type HostingReward = HostingReward;
+        type RewardStorageUnit = RewardStorageUnit;
+        type MaxHostingRewardsPerPeriod = MaxHostingRewardsPerPeriod;
+        type HostingRewardPeriod = HostingRewardPeriod;
+        type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+        type MaxRewardsPerBlock = MaxRewardsPerBlock;
+        type RetentionPeriod = RetentionPeriod;
+        type ArchivePeriod = ArchivePeriod;
+        type MaxArchivedPerBlock = MaxArchivedPerBlock;
+        type MaxSettlementsPerBlock = MaxSettlementsPerBlock;
+        type WeightInfo = ();
+        type PeerRotationPeriod = PeerRotationPeriod;
+        type MaxPeerRotationsPerBlock = MaxPeerRotationsPerBlock;
+        type MaxPeerCandidates = MaxPeerCandidates;
+        type PolicyOrigin = frame_system::EnsureRoot<AccountId>;
+        type Randomness = MockRandomness;
+        type MinHostReputation = MinHostReputation;
+        type ReportCooldown = ReportCooldown;
+        type MaxReportsPerReporter = MaxReportsPerReporter;
+        type TokenSystem = MockTokenSystem;
+        type ConsumerFund = MockConsumerFund;
+        type SpecialAuthority = MockSpecialAuthority;
+        type ArbitratorRole = ArbitratorRole;
+        type SystemSaleRole = SystemSaleRole;
+        type DisputeWindow = DisputeWindow;
+        type OnPopFinalized = MockPopNotifier;
+    }
+    
+    // Politique de validation de test
+    fn policy(
+        consumer_required: bool,
+        merchant_required: bool,
+        suppliers: (u32, u32),
+        required_supplier_signatures: u32,
+        validation_period: u64,
+    ) -> ValidationPolicy<u64> {
+        ValidationPolicy {
+            consumer_required,
+            merchant_required,
+            min_suppliers: suppliers.0,
+            max_suppliers: suppliers.1,
+            required_supplier_signatures,
+            validation_period,
+        }
+    }
+    
+    // Fonction utilitaire pour créer un environnement de test
+    fn new_test_ext() -> sp_io::TestExternalities {
         let mut t = frame_system::GenesisConfig::default()
             .build_storage::<Test>()
             .unwrap();
+        
+        // Politiques par défaut: tous les fournisseurs signent une vente au détail,
+        // deux fournisseurs suffisent pour une vente complexe
+        super::GenesisConfig::<Test> {
+            validation_policies: vec![
+                (SaleType::DirectSale, policy(true, true, (0, 0), 0, 50)),
+                (SaleType::RetailSale, policy(true, true, (1, 8), 8, 100)),
+                (SaleType::ComplexSale, policy(true, true, (2, 8), 2, 100)),
+                (SaleType::System, policy(false, true, (0, 0), 0, 10)),
+            ],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
             
         t.into()
     }
     
+    /// Clé d'un participant de test dans l'un des schémas de signature supportés
+    enum TestKey {
+        Sr25519(sr25519::Pair),
+        Ed25519(ed25519::Pair),
+        Ecdsa(ecdsa::Pair),
+    }
+    
+    impl TestKey {
+        fn sr25519(seed: u8) -> Self {
+            TestKey::Sr25519(sr25519::Pair::from_seed(&[seed; 32]))
+        }
+        
+        fn ed25519(seed: u8) -> Self {
+            TestKey::Ed25519(ed25519::Pair::from_seed(&[seed; 32]))
+        }
+        
+        fn ecdsa(seed: u8) -> Self {
+            TestKey::Ecdsa(ecdsa::Pair::from_seed(&[seed; 32]))
+        }
+        
+        fn account(&self) -> AccountId {
+            let signer: MultiSigner = match self {
+                TestKey::Sr25519(pair) => pair.public().into(),
+                TestKey::Ed25519(pair) => pair.public().into(),
+                TestKey::Ecdsa(pair) => pair.public().into(),
+            };
+            signer.into_account()
+        }
+        
+        fn sign(&self, message: &[u8]) -> MultiSignature {
+            match self {
+                TestKey::Sr25519(pair) => pair.sign(message).into(),
+                TestKey::Ed25519(pair) => pair.sign(message).into(),
+                TestKey::Ecdsa(pair) => pair.sign(message).into(),
+            }
+        }
+    }
+    
+    // Enregistrer les comptes des participants dans le système
+    fn register_accounts(keys: &[&TestKey]) {
+        for key in keys {
+            frame_system::Module::<Test>::inc_providers(&key.account());
+        }
+    }
+    
+    // Déclarer les nœuds [1; 32] à [count; 32] sans passer par l'enregistrement complet
+    fn register_nodes(count: u8) {
+        for seed in 1..=count {
+            <NodeIdToAccount<Test>>::insert([seed; 32], Some(TestKey::sr25519(seed).account()));
+            EtikaBlockchain::index_peer([seed; 32]);
+        }
+    }
+    
+    // Enregistrer un hôte et son nœud [seed; 32]
+    fn register_host(seed: u8) -> (AccountId, [u8; 32]) {
+        let host = TestKey::sr25519(seed).account();
+        assert_ok!(EtikaBlockchain::register_node(
+            Origin::signed(host.clone()),
+            [seed; 32],
+            b"192.168.1.1:8080".to_vec(),
+            1024,
+            b"1.0.0".to_vec(),
+        ));
+        (host, [seed; 32])
+    }
+    
+    fn set_reputations(reputations: &[(u8, u8)]) {
+        for (seed, reputation) in reputations {
+            <NodeReputations>::insert([*seed; 32], *reputation);
+        }
+    }
+    
+    fn peer(seed: u8, reputation: u8) -> PeerConnection {
+        PeerConnection {
+            node_id: [seed; 32],
+            last_seen: 0,
+            reputation,
+        }
+    }
+    
+    // Signature factice, pour les cas rejetés avant la vérification cryptographique
+    fn dummy_proof() -> Proof {
+        sr25519::Signature::from_raw([0; 64]).into()
+    }
+    
+    // Créer une vente au détail signée par le créateur et retourner son identifiant
+    fn create_signed_transaction(
+        creator: &TestKey,
+        consumer: &TestKey,
+        merchant: &TestKey,
+        suppliers: &[&TestKey],
+        standard_amount: Balance,
+    ) -> Result<[u8; 32], DispatchError> {
+        create_signed_sale(SaleType::RetailSale, creator, consumer, merchant, suppliers, standard_amount)
+    }
+    
+    // Créer une transaction PoP du type de vente donné, signée par le créateur
+    fn create_signed_sale(
+        sale_type: SaleType,
+        creator: &TestKey,
+        consumer: &TestKey,
+        merchant: &TestKey,
+        suppliers: &[&TestKey],
+        standard_amount: Balance,
+    ) -> Result<[u8; 32], DispatchError> {
+        create_signed_receipt(sale_type, creator, consumer, merchant, suppliers, standard_amount, [0; 32])
+    }
+    
+    // Créer une transaction PoP engageant la racine de ticket donnée, signée par le créateur
+    fn create_signed_receipt(
+        sale_type: SaleType,
+        creator: &TestKey,
+        consumer: &TestKey,
+        merchant: &TestKey,
+        suppliers: &[&TestKey],
+        standard_amount: Balance,
+        receipt_hash: [u8; 32],
+    ) -> Result<[u8; 32], DispatchError> {
+        let creator_id = creator.account();
+        let suppliers: Vec<AccountId> = suppliers.iter().map(|key| key.account()).collect();
+        let nonce = EtikaBlockchain::account_nonce(creator_id.clone());
+        
+        let transaction_id = EtikaBlockchain::generate_transaction_id(
+            &creator_id,
+            &consumer.account(),
+            &merchant.account(),
+            &suppliers,
+            standard_amount,
+            &receipt_hash,
+            nonce,
+        );
+        let message = EtikaBlockchain::signing_payload(&transaction_id, standard_amount, 10, 5, &receipt_hash, nonce);
+        
+        EtikaBlockchain::create_pop_transaction(
+            Origin::signed(creator_id),
+            sale_type,
+            consumer.account(),
+            merchant.account(),
+            suppliers,
+            standard_amount,
+            10,
+            5,
+            receipt_hash,
+            creator.sign(&message),
+        )?;
+        
+        Ok(transaction_id)
+    }
+    
+    // Signer une transaction en attente au nom d'un participant
+    fn validate_signed(key: &TestKey, transaction_id: [u8; 32]) -> DispatchResult {
+        let transaction = <PendingTransactions>::get(transaction_id);
+        let message = EtikaBlockchain::compute_signature_message(&transaction);
+        
+        EtikaBlockchain::validate_pop_transaction(
+            Origin::signed(key.account()),
+            transaction_id,
+            key.sign(&message),
+        )
+    }
+    
+    // Dérouler une transaction complète avec les clés fournies
+    fn assert_full_validation(consumer: TestKey, merchant: TestKey, supplier: TestKey) {
+        register_accounts(&[&consumer, &merchant, &supplier]);
+        
+        let transaction_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+        assert_ok!(validate_signed(&consumer, transaction_id));
+        assert_ok!(validate_signed(&supplier, transaction_id));
+        
+        // La transaction est finalisée et ses signatures sont vérifiables par les autres modules
+        assert!(<ValidatedTransactions>::contains_key(transaction_id));
+        let transaction = <ValidatedTransactions>::get(transaction_id);
+        assert_eq!(transaction.signatures.len(), 3);
+        assert_ok!(<EtikaBlockchain as PoPConsensus>::validate_transaction(&transaction));
+    }
+    
+    #[test]
+    fn test_create_pop_transaction() {
+        new_test_ext().execute_with(|| {
+            // Simuler les comptes
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &merchant, &supplier]);
+            
+            // Créer une transaction PoP
+            let transaction_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+            
+            // Vérifier que la transaction a été créée
+            assert!(<PendingTransactions>::contains_key(transaction_id));
+            
+            // Vérifier les détails de la transaction
+            let transaction = <PendingTransactions>::get(transaction_id);
+            assert_eq!(transaction.consumer, consumer.account());
+            assert_eq!(transaction.merchant, merchant.account());
+            assert_eq!(transaction.suppliers, vec![supplier.account()]);
+            assert_eq!(transaction.standard_amount, 100);
+            assert_eq!(transaction.tokens_exchanged, 10);
+            assert_eq!(transaction.savings_generated, 5);
+            assert_eq!(transaction.nonce, 0);
+            
+            // Vérifier que la signature initiale est présente
+            assert_eq!(transaction.signatures.len(), 1);
+            assert_eq!(transaction.signatures[0].0, merchant.account());
+            
+            // Vérifier que le nonce a été incrémenté
+            assert_eq!(EtikaBlockchain::account_nonce(merchant.account()), 1);
+        });
+    }
+    
+    #[test]
+    fn test_validate_pop_transaction() {
+        new_test_ext().execute_with(|| {
+            // Simuler les comptes
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &merchant, &supplier]);
+            
+            // Créer une transaction PoP
+            let transaction_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+            
+            // Le consommateur valide la transaction
+            assert_ok!(validate_signed(&consumer, transaction_id));
+            
+            // Le fournisseur valide la transaction
+            assert_ok!(validate_signed(&supplier, transaction_id));
+            
+            // Vérifier que la transaction a été finalisée
+            assert!(!<PendingTransactions>::contains_key(transaction_id));
+            assert!(<ValidatedTransactions>::contains_key(transaction_id));
+            
+            // Vérifier que le compteur a été incrémenté
+            assert_eq!(<ValidatedTransactionCount>::get(), 1);
+        });
+    }
+    
+    #[test]
+    fn test_consumer_validation_required() {
+        new_test_ext().execute_with(|| {
+            // Simuler les comptes
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier_1 = TestKey::sr25519(3);
+            let supplier_2 = TestKey::sr25519(4);
+            register_accounts(&[&consumer, &merchant, &supplier_1, &supplier_2]);
+            
+            // Créer une transaction PoP avec 2 fournisseurs
+            let transaction_id = create_signed_transaction(
+                &merchant,
+                &consumer,
+                &merchant,
+                &[&supplier_1, &supplier_2],
+                100,
+            ).unwrap();
+            
+            // Les deux fournisseurs valident la transaction
+            assert_ok!(validate_signed(&supplier_1, transaction_id));
+            assert_ok!(validate_signed(&supplier_2, transaction_id));
+            
+            // La transaction ne devrait pas être finalisée car le consommateur n'a pas validé
+            assert!(<PendingTransactions>::contains_key(transaction_id));
+            assert!(!<ValidatedTransactions>::contains_key(transaction_id));
+        });
+    }
+    
+    #[test]
+    fn test_negative_amounts_rejected() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &merchant, &supplier]);
+            
+            // Tenter de créer une transaction avec un montant négatif
+            let result = EtikaBlockchain::create_pop_transaction(
+                Origin::signed(merchant.account()),
+                SaleType::RetailSale,
+                consumer.account(),
+                merchant.account(),
+                vec![supplier.account()],
+                -100, // Montant négatif
+                10,
+                5,
+                [0; 32],
+                dummy_proof(),
+            );
+            
+            // La transaction devrait être rejetée
+            assert_noop!(result, Error::<Test>::InvalidAmount);
+        });
+    }
+    
+    #[test]
+    fn test_excessive_tokens_rejected() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &merchant, &supplier]);
+            
+            // Tenter de créer une transaction avec des tokens excessifs
+            let result = EtikaBlockchain::create_pop_transaction(
+                Origin::signed(merchant.account()),
+                SaleType::RetailSale,
+                consumer.account(),
+                merchant.account(),
+                vec![supplier.account()],
+                100,
+                200, // Plus de tokens que le montant standard
+                5,
+                [0; 32],
+                dummy_proof(),
+            );
+            
+            // La transaction devrait être rejetée
+            assert_noop!(result, Error::<Test>::ExcessiveTokens);
+        });
+    }
+    
+    #[test]
+    fn test_node_registration() {
+        new_test_ext().execute_with(|| {
+            // Simuler un compte
+            let host = TestKey::sr25519(1).account();
+            let node_id = [1; 32];
+            
+            // Enregistrer un nœud
+            assert_ok!(EtikaBlockchain::register_node(
+                Origin::signed(host.clone()),
+                node_id,
+                b"192.168.1.1:8080".to_vec(),
+                1024 * 1024 * 1024, // 1 GB
+                b"1.0.0".to_vec(),
+            ));
+            
+            // Vérifier que le nœud est enregistré
+            assert!(<NodeIdToAccount<Test>>::contains_key(node_id));
+            assert!(<ActiveHosts<Test>>::contains_key(&host));
+            
+            // Vérifier le nombre de nœuds
+            assert_eq!(<TotalActiveNodes>::get(), 1);
+            
+            // Vérifier que des connexions initiales ont été créées
+            assert!(<NodeConnections>::contains_key(node_id));
+            
+            // Désinscrire le nœud
+            assert_ok!(EtikaBlockchain::unregister_node(
+                Origin::signed(host.clone()),
+                node_id,
+            ));
+            
+            // Vérifier que le nœud est désactivé
+            assert!(!<NodeIdToAccount<Test>>::contains_key(node_id));
+            assert!(!<ActiveHosts<Test>>::contains_key(&host));
+            assert!(!<NodeConnections>::contains_key(node_id));
+            
+            // Vérifier le nombre de nœuds
+            assert_eq!(<TotalActiveNodes>::get(), 0);
+        });
+    }
+    
+    #[test]
+    fn test_node_address_too_large() {
+        new_test_ext().execute_with(|| {
+            let host = TestKey::sr25519(1).account();
+            let node_id = [1; 32];
+            
+            // Créer une adresse réseau trop grande
+            let large_address = vec![0; MAX_NETWORK_ADDRESS_LENGTH + 1];
+            
+            // Cette opération devrait être rejetée
+            let result = EtikaBlockchain::register_node(
+                Origin::signed(host),
+                node_id,
+                large_address,
+                1024 * 1024 * 1024,
+                b"1.0.0".to_vec(),
+            );
+            
+            assert_noop!(result, Error::<Test>::InputTooLarge);
+        });
+    }
+    
+    #[test]
+    fn test_replay_attack_prevention() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &merchant, &supplier]);
+            
+            // Créer une première transaction et conserver la preuve signée
+            let first_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+            let first_proof = <PendingTransactions>::get(first_id).signatures[0].1.clone();
+            
+            // Rejouer la même preuve: le nonce a changé, la signature ne correspond plus
+            assert_noop!(
+                EtikaBlockchain::create_pop_transaction(
+                    Origin::signed(merchant.account()),
+                    SaleType::RetailSale,
+                    consumer.account(),
+                    merchant.account(),
+                    vec![supplier.account()],
+                    100,
+                    10,
+                    5,
+                    [0; 32],
+                    first_proof,
+                ),
+                Error::<Test>::InvalidSignature
+            );
+            
+            // Une transaction identique signée avec le nouveau nonce reçoit un autre identifiant
+            let second_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+            assert_ne!(first_id, second_id);
+            
+            // Vérifier que le nonce a été incrémenté deux fois
+            assert_eq!(EtikaBlockchain::account_nonce(merchant.account()), 2);
+        });
+    }
+    
+    #[test]
+    fn test_sr25519_signatures_verified() {
+        new_test_ext().execute_with(|| {
+            assert_full_validation(TestKey::sr25519(1), TestKey::sr25519(2), TestKey::sr25519(3));
+        });
+    }
+    
+    #[test]
+    fn test_ed25519_signatures_verified() {
+        new_test_ext().execute_with(|| {
+            assert_full_validation(TestKey::ed25519(1), TestKey::ed25519(2), TestKey::ed25519(3));
+        });
+    }
+    
+    #[test]
+    fn test_ecdsa_signatures_verified() {
+        new_test_ext().execute_with(|| {
+            assert_full_validation(TestKey::ecdsa(1), TestKey::ecdsa(2), TestKey::ecdsa(3));
+        });
+    }
+    
+    #[test]
+    fn test_mixed_signature_schemes_verified() {
+        new_test_ext().execute_with(|| {
+            assert_full_validation(TestKey::sr25519(1), TestKey::ed25519(2), TestKey::ecdsa(3));
+        });
+    }
+    
+    #[test]
+    fn test_invalid_signatures_rejected() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::ed25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::ecdsa(3);
+            register_accounts(&[&consumer, &merchant, &supplier]);
+            
+            let transaction_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+            let transaction = <PendingTransactions>::get(transaction_id);
+            
+            // Signature sur un autre montant
+            let mut tampered = transaction.clone();
+            tampered.standard_amount = 1_000;
+            let tampered_message = EtikaBlockchain::compute_signature_message(&tampered);
+            assert_noop!(
+                EtikaBlockchain::validate_pop_transaction(
+                    Origin::signed(consumer.account()),
+                    transaction_id,
+                    consumer.sign(&tampered_message),
+                ),
+                Error::<Test>::InvalidSignature
+            );
+            
+            // Signature valide, mais produite par la clé d'un autre participant
+            let message = EtikaBlockchain::compute_signature_message(&transaction);
+            assert_noop!(
+                EtikaBlockchain::validate_pop_transaction(
+                    Origin::signed(consumer.account()),
+                    transaction_id,
+                    supplier.sign(&message),
+                ),
+                Error::<Test>::InvalidSignature
+            );
+            
+            // Une transaction dont le contenu a été modifié après signature est rejetée
+            assert_ok!(validate_signed(&consumer, transaction_id));
+            assert_ok!(validate_signed(&supplier, transaction_id));
+            let mut finalized = <ValidatedTransactions>::get(transaction_id);
+            finalized.tokens_exchanged = 20;
+            assert_eq!(
+                <EtikaBlockchain as PoPConsensus>::validate_transaction(&finalized),
+                Err("Invalid signature")
+            );
+        });
+    }
+    
+    #[test]
+    fn test_foreign_and_duplicate_signers_rejected() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::sr25519(3);
+            let outsider = TestKey::ed25519(4);
+            register_accounts(&[&consumer, &merchant, &supplier, &outsider]);
+            
+            // Un tiers ne peut ni créer ni valider la transaction
+            assert_noop!(
+                create_signed_transaction(&outsider, &consumer, &merchant, &[&supplier], 100),
+                Error::<Test>::UnauthorizedValidator
+            );
+            
+            let transaction_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+            assert_noop!(validate_signed(&outsider, transaction_id), Error::<Test>::UnauthorizedValidator);
+            
+            // Un participant ne peut signer qu'une fois
+            assert_noop!(validate_signed(&merchant, transaction_id), Error::<Test>::AlreadyValidated);
+            
+            // Un participant ne peut pas occuper deux rôles
+            assert_noop!(
+                create_signed_transaction(&merchant, &consumer, &merchant, &[&consumer], 100),
+                Error::<Test>::DuplicateParticipant
+            );
+            
+            // Le trait rejette une signature dupliquée ou étrangère insérée dans la transaction
+            assert_ok!(validate_signed(&consumer, transaction_id));
+            let mut transaction = <PendingTransactions>::get(transaction_id);
+            let duplicated = transaction.signatures[0].clone();
+            transaction.signatures.push(duplicated);
+            assert_eq!(
+                <EtikaBlockchain as PoPConsensus>::validate_transaction(&transaction),
+                Err("Duplicate signature")
+            );
+            
+            let mut transaction = <PendingTransactions>::get(transaction_id);
+            let message = EtikaBlockchain::compute_signature_message(&transaction);
+            transaction.signatures.push((outsider.account(), outsider.sign(&message)));
+            assert_eq!(
+                <EtikaBlockchain as PoPConsensus>::validate_transaction(&transaction),
+                Err("Signer is not a participant")
+            );
+        });
+    }
+    
+    #[test]
+    fn test_direct_sale_policy() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let producer = TestKey::ed25519(2);
+            let supplier = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &producer, &supplier]);
+            
+            // Une vente directe n'a pas de fournisseur
+            assert_noop!(
+                create_signed_sale(SaleType::DirectSale, &producer, &consumer, &producer, &[&supplier], 100),
+                Error::<Test>::TooManyValidators
+            );
+            
+            // La signature du producteur et celle du consommateur suffisent
+            let transaction_id = create_signed_sale(SaleType::DirectSale, &producer, &consumer, &producer, &[], 100).unwrap();
+            assert!(<PendingTransactions>::contains_key(transaction_id));
+            assert_ok!(validate_signed(&consumer, transaction_id));
+            
+            assert!(<ValidatedTransactions>::contains_key(transaction_id));
+            let transaction = <ValidatedTransactions>::get(transaction_id);
+            assert_ok!(<EtikaBlockchain as PoPConsensus>::validate_transaction(&transaction));
+            assert_eq!(
+                EtikaBlockchain::transaction_policies(transaction_id).map(|(sale_type, _)| sale_type),
+                Some(SaleType::DirectSale)
+            );
+        });
+    }
+    
+    #[test]
+    fn test_complex_sale_k_of_n_suppliers() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let suppliers = [TestKey::sr25519(3), TestKey::ed25519(4), TestKey::ecdsa(5)];
+            register_accounts(&[&consumer, &merchant, &suppliers[0], &suppliers[1], &suppliers[2]]);
+            
+            // Une vente complexe exige au moins deux fournisseurs
+            assert_noop!(
+                create_signed_sale(SaleType::ComplexSale, &merchant, &consumer, &merchant, &[&suppliers[0]], 100),
+                Error::<Test>::InsufficientValidators
+            );
+            
+            let transaction_id = create_signed_sale(
+                SaleType::ComplexSale,
+                &merchant,
+                &consumer,
+                &merchant,
+                &[&suppliers[0], &suppliers[1], &suppliers[2]],
+                100,
+            ).unwrap();
+            
+            // Consommateur et un seul fournisseur: la transaction reste en attente
+            assert_ok!(validate_signed(&consumer, transaction_id));
+            assert_ok!(validate_signed(&suppliers[2], transaction_id));
+            assert!(<PendingTransactions>::contains_key(transaction_id));
+            assert_eq!(
+                <EtikaBlockchain as PoPConsensus>::validate_transaction(&<PendingTransactions>::get(transaction_id)),
+                Err("Insufficient validators")
+            );
+            
+            // Deux fournisseurs sur trois suffisent
+            assert_ok!(validate_signed(&suppliers[1], transaction_id));
+            assert!(<ValidatedTransactions>::contains_key(transaction_id));
+        });
+    }
+    
+    #[test]
+    fn test_system_transaction_finalized_on_creation() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            register_accounts(&[&consumer, &merchant]);
+            
+            // Sans le rôle d'opérateur système, un commerçant ne peut pas se passer du consommateur
+            assert_noop!(
+                create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100),
+                Error::<Test>::NotSystemOperator
+            );
+            
+            // Seul le commerçant doit signer une transaction système
+            add_system_operator(merchant.account());
+            let transaction_id = create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            assert!(!<PendingTransactions>::contains_key(transaction_id));
+            assert!(<ValidatedTransactions>::contains_key(transaction_id));
+        });
+    }
+    
+    #[test]
+    #[should_panic(expected = "Politique de validation initiale incohérente")]
+    fn test_genesis_policies_are_validated() {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+        
+        // Un délai de validation nul est refusé comme par `set_validation_policy`
+        super::GenesisConfig::<Test> {
+            validation_policies: vec![(SaleType::DirectSale, policy(true, true, (0, 0), 0, 0))],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+    }
+    
     #[test]
-    fn test_create_pop_transaction() {
+    fn test_set_validation_policy() {
         new_test_ext().execute_with(|| {
-            // Simuler les comptes
-            let consumer = 1;
-            let merchant = 2;
-            let supplier = 3;
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &merchant, &supplier]);
             
-            // Créer une transaction PoP
-            assert_ok!(EtikaBlockchain::create_pop_transaction(
-                Origin::signed(merchant),
-                consumer,
-                merchant,
-                vec![supplier],
-                100,
-                10,
-                5,
-                [0; 32],
-                sr25519::Signature::from_raw([0; 64]), // Signature factice pour les tests
-            ));
+            let short_deadline = policy(true, true, (1, 8), 1, 10);
             
-            // Vérifier que la transaction a été créée
-            let transaction_id = EtikaBlockchain::generate_transaction_id(&consumer, &merchant, 100);
-            assert!(<PendingTransactions>::contains_key(transaction_id));
+            // Seule la gouvernance peut modifier une politique
+            assert_noop!(
+                EtikaBlockchain::set_validation_policy(
+                    Origin::signed(merchant.account()),
+                    SaleType::RetailSale,
+                    short_deadline.clone(),
+                ),
+                BadOrigin
+            );
             
-            // Vérifier les détails de la transaction
-            let transaction = <PendingTransactions>::get(transaction_id);
-            assert_eq!(transaction.consumer, consumer);
-            assert_eq!(transaction.merchant, merchant);
-            assert_eq!(transaction.suppliers, vec![supplier]);
-            assert_eq!(transaction.standard_amount, 100);
-            assert_eq!(transaction.tokens_exchanged, 10);
-            assert_eq!(transaction.savings_generated, 5);
+            // Politiques incohérentes rejetées
+            assert_noop!(
+                EtikaBlockchain::set_validation_policy(Origin::root(), SaleType::RetailSale, policy(false, false, (0, 0), 0, 10)),
+                Error::<Test>::InvalidValidationPolicy
+            );
+            assert_noop!(
+                EtikaBlockchain::set_validation_policy(Origin::root(), SaleType::RetailSale, policy(true, true, (1, 8), 1, 1_000)),
+                Error::<Test>::InvalidValidationPolicy
+            );
             
-            // Vérifier que la signature initiale est présente
-            assert_eq!(transaction.signatures.len(), 1);
-            assert_eq!(transaction.signatures[0].0, merchant);
+            // Une transaction créée avant la modification conserve l'ancienne politique
+            let before_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+            
+            assert_ok!(EtikaBlockchain::set_validation_policy(Origin::root(), SaleType::RetailSale, short_deadline.clone()));
+            assert_eq!(EtikaBlockchain::validation_policies(SaleType::RetailSale), Some(short_deadline));
+            
+            let after_id = create_signed_transaction(&merchant, &consumer, &merchant, &[&supplier], 100).unwrap();
+            
+            // Seule la transaction soumise au nouveau délai expire au bloc 10
+            System::set_block_number(10);
+            EtikaBlockchain::on_initialize(10);
+            assert!(<PendingTransactions>::contains_key(before_id));
+            assert!(!<PendingTransactions>::contains_key(after_id));
+            assert!(<ExpiredTransactions>::contains_key(after_id));
         });
     }
     
     #[test]
-    fn test_validate_pop_transaction() {
+    fn test_peer_selection_is_random_and_deterministic() {
         new_test_ext().execute_with(|| {
-            // Simuler les comptes
-            let consumer = 1;
-            let merchant = 2;
-            let supplier = 3;
+            register_nodes(6);
+            let node_id = [1; 32];
             
-            // Créer une transaction PoP
-            assert_ok!(EtikaBlockchain::create_pop_transaction(
-                Origin::signed(merchant),
-                consumer,
-                merchant,
-                vec![supplier],
-                100,
-                10,
-                5,
-                [0; 32],
-                sr25519::Signature::from_raw([0; 64]),
-            ));
+            let peers = EtikaBlockchain::select_random_peers(node_id, 3, &[]);
+            let mut selected: Vec<[u8; 32]> = peers.iter().map(|peer| peer.node_id).collect();
             
-            let transaction_id = EtikaBlockchain::generate_transaction_id(&consumer, &merchant, 100);
+            // Tirage sans remise, sans le nœud lui-même
+            assert_eq!(selected.len(), 3);
+            assert!(!selected.contains(&node_id));
+            selected.sort();
+            selected.dedup();
+            assert_eq!(selected.len(), 3);
             
-            // Le consommateur valide la transaction
-            assert_ok!(EtikaBlockchain::validate_pop_transaction(
-                Origin::signed(consumer),
-                transaction_id,
-                sr25519::Signature::from_raw([0; 64]),
-            ));
+            // Même graine et même compteur de sélection, même sélection
+            <PeerSelectionNonce>::put(0);
+            let again = EtikaBlockchain::select_random_peers(node_id, 3, &[]);
+            assert_eq!(peers, again);
             
-            // Le fournisseur valide la transaction
-            assert_ok!(EtikaBlockchain::validate_pop_transaction(
-                Origin::signed(supplier),
-                transaction_id,
-                sr25519::Signature::from_raw([0; 64]),
-            ));
+            // Les pairs exclus ne sont jamais tirés
+            let excluded = [[2; 32], [3; 32]];
+            let peers = EtikaBlockchain::select_random_peers(node_id, 3, &excluded);
+            assert!(peers.iter().all(|peer| !excluded.contains(&peer.node_id)));
             
-            // Vérifier que la transaction a été finalisée
-            assert!(!<PendingTransactions>::contains_key(transaction_id));
-            assert!(<ValidatedTransactions>::contains_key(transaction_id));
+            // Pas assez de candidats: tous les nœuds disponibles sont retournés
+            let peers = EtikaBlockchain::select_random_peers(node_id, 10, &[]);
+            assert_eq!(peers.len(), 5);
+            assert!(peers.iter().all(|peer| peer.reputation == INITIAL_PEER_REPUTATION));
+        });
+    }
+    
+    #[test]
+    fn test_peer_selection_weighted_by_reputation() {
+        new_test_ext().execute_with(|| {
+            register_nodes(5);
+            set_reputations(&[(2, 200), (3, 1), (4, 1), (5, 1)]);
             
-            // Vérifier que le compteur a été incrémenté
-            assert_eq!(<ValidatedTransactionCount>::get(), 1);
+            let mut trusted_picks = 0;
+            for seed in 0..100 {
+                set_random_seed(seed);
+                let peers = EtikaBlockchain::select_random_peers([1; 32], 1, &[]);
+                if peers[0].node_id == [2; 32] {
+                    trusted_picks += 1;
+                }
+            }
+            
+            // Probabilité attendue de 200/203 pour le pair le plus réputé
+            assert!(trusted_picks >= 90);
+            
+            // Un pair de réputation nulle reste sélectionnable
+            set_reputations(&[(2, 0), (3, 0), (4, 0), (5, 0)]);
+            let peers = EtikaBlockchain::select_random_peers([1; 32], 4, &[]);
+            assert_eq!(peers.len(), 4);
         });
     }
     
     #[test]
-    fn test_node_registration() {
+    fn test_peer_rotation_keeps_top_connections() {
         new_test_ext().execute_with(|| {
-            // Simuler un compte
-            let host = 1;
+            register_nodes(12);
             let node_id = [1; 32];
             
-            // Enregistrer un nœud
-            assert_ok!(EtikaBlockchain::register_node(
-                Origin::signed(host),
-                node_id,
-                b"192.168.1.1:8080".to_vec(),
-                1024 * 1024 * 1024, // 1 GB
-                b"1.0.0".to_vec(),
-            ));
+            set_reputations(&[(2, 10), (3, 90), (4, 80), (5, 70), (6, 60), (7, 50), (8, 40)]);
+            let connections = (2..=8).map(|seed| peer(seed, INITIAL_PEER_REPUTATION)).collect::<Vec<_>>();
+            <NodeConnections>::insert(node_id, connections);
             
-            // Vérifier que le nœud est enregistré
-            assert!(<NodeIdToAccount<Test>>::contains_key(node_id));
-            assert!(<ActiveHosts<Test>>::contains_key(host));
+            EtikaBlockchain::rotate_node_peers(node_id);
             
-            // Vérifier le nombre de nœuds
-            assert_eq!(<TotalActiveNodes>::get(), 1);
+            let rotated: Vec<[u8; 32]> = <NodeConnections>::get(node_id).iter().map(|conn| conn.node_id).collect();
             
-            // Désinscrire le nœud
-            assert_ok!(EtikaBlockchain::unregister_node(
-                Origin::signed(host),
-                node_id,
-            ));
+            // Les connexions les plus réputées, à réputation actualisée, sont conservées en tête
+            assert_eq!(&rotated[..MAX_PERSISTENT_CONNECTIONS], &[[3; 32], [4; 32], [5; 32], [6; 32], [7; 32]]);
+            assert_eq!(rotated.len(), MAX_PERSISTENT_CONNECTIONS + MAX_RANDOM_CONNECTIONS);
             
-            // Vérifier que le nœud est désactivé
-            assert!(!<NodeIdToAccount<Test>>::contains_key(node_id));
-            assert!(!<ActiveHosts<Test>>::contains_key(host));
+            // Aucun doublon ni connexion vers soi-même
+            assert!(!rotated.contains(&node_id));
+            let mut unique = rotated.clone();
+            unique.sort();
+            unique.dedup();
+            assert_eq!(unique.len(), rotated.len());
+        });
+    }
+    
+    #[test]
+    fn test_peer_rotation_is_bounded_per_block() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            for seed in 1..=3 {
+                register_host(seed);
+            }
+            assert_eq!(EtikaBlockchain::peer_rotation_queue(21).len(), 3);
             
-            // Vérifier le nombre de nœuds
+            // Deux nœuds au plus par bloc, le troisième est reporté au bloc suivant
+            assert_eq!(EtikaBlockchain::rotate_peers(21), (1, 2));
+            assert_eq!(EtikaBlockchain::peer_rotation_queue(21).len(), 1);
+            assert_eq!(EtikaBlockchain::peer_rotation_cursor(), Some(21));
+            
+            assert_eq!(EtikaBlockchain::rotate_peers(22), (2, 1));
+            assert_eq!(EtikaBlockchain::peer_rotation_cursor(), None);
+            
+            // Chaque nœud est replanifié une période après sa rotation
+            assert_eq!(EtikaBlockchain::peer_rotation_queue(41).len(), 2);
+            assert_eq!(EtikaBlockchain::peer_rotation_queue(42), vec![[3; 32]]);
+        });
+    }
+    
+    #[test]
+    fn test_peer_candidates_are_bounded() {
+        new_test_ext().execute_with(|| {
+            register_nodes(40);
+            assert_eq!(EtikaBlockchain::peer_count(), 40);
+            
+            // Au-delà de `MaxPeerCandidates`, seules des positions tirées au hasard sont lues
+            let candidates = EtikaBlockchain::peer_candidates(&H256::repeat_byte(1), MaxPeerCandidates::get());
+            assert!(!candidates.is_empty());
+            assert!(candidates.len() as u32 <= MaxPeerCandidates::get());
+            
+            let peers = EtikaBlockchain::select_random_peers([1; 32], 3, &[]);
+            assert_eq!(peers.len(), 3);
+            assert!(peers.iter().all(|peer| peer.node_id != [1; 32]));
+            
+            // Deux sélections d'un même bloc ne partagent pas leur graine
+            assert_eq!(EtikaBlockchain::peer_selection_nonce(), 1);
+            
+            // Un nœud retiré de l'index est remplacé par le dernier nœud indexé
+            EtikaBlockchain::unindex_peer([2; 32]);
+            assert_eq!(EtikaBlockchain::peer_count(), 39);
+            assert_eq!(EtikaBlockchain::peer_position([40; 32]), Some(1));
+            assert_eq!(EtikaBlockchain::peer_index(1), Some([40; 32]));
+            assert_eq!(EtikaBlockchain::peer_index(39), None);
+            assert_eq!(EtikaBlockchain::peer_position([2; 32]), None);
+        });
+    }
+    
+    #[test]
+    fn test_node_reports_update_reputation() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let (host_a, node_a) = register_host(1);
+            let (host_b, node_b) = register_host(2);
+            
+            // Le nœud B, enregistré en second, est connecté au nœud A
+            assert_ok!(EtikaBlockchain::report_node(Origin::signed(host_b.clone()), node_a, NodeReport::Unavailable));
+            assert_eq!(EtikaBlockchain::peer_reputation(node_a), INITIAL_PEER_REPUTATION - REPUTATION_UNAVAILABLE_PENALTY);
+            assert_eq!(<ActiveHosts<Test>>::get(&host_a).node_info.availability_score, 100 - AVAILABILITY_STEP);
+            
+            // Un seul signalement par période de refroidissement
+            assert_noop!(
+                EtikaBlockchain::report_node(Origin::signed(host_b.clone()), node_a, NodeReport::Available),
+                Error::<Test>::ReportTooFrequent
+            );
+            
+            System::set_block_number(6);
+            assert_ok!(EtikaBlockchain::report_node(Origin::signed(host_b.clone()), node_a, NodeReport::Available));
+            assert_eq!(
+                EtikaBlockchain::peer_reputation(node_a),
+                INITIAL_PEER_REPUTATION - REPUTATION_UNAVAILABLE_PENALTY + REPUTATION_AVAILABLE_REWARD
+            );
+            assert_eq!(<ActiveHosts<Test>>::get(&host_a).node_info.availability_score, 100);
+            
+            // Seuls les pairs connectés, hébergeant un nœud, peuvent signaler
+            assert_noop!(
+                EtikaBlockchain::report_node(Origin::signed(host_a.clone()), node_b, NodeReport::Unavailable),
+                Error::<Test>::NotConnectedPeer
+            );
+            assert_noop!(
+                EtikaBlockchain::report_node(Origin::signed(host_b.clone()), node_b, NodeReport::Available),
+                Error::<Test>::CannotReportSelf
+            );
+            assert_noop!(
+                EtikaBlockchain::report_node(Origin::signed(TestKey::sr25519(3).account()), node_a, NodeReport::Available),
+                Error::<Test>::NodeNotFound
+            );
+        });
+    }
+    
+    #[test]
+    fn test_misbehaving_node_evicted() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let (host_a, node_a) = register_host(1);
+            let (host_b, _) = register_host(2);
+            let (host_c, _) = register_host(3);
+            <ActiveHosts<Test>>::mutate(&host_a, |host| host.hosting_bonus = 10);
+            let _ = Balances::deposit_creating(&EtikaBlockchain::reward_pot_account(), 100);
+            
+            let report = NodeReport::Misbehaviour(MisbehaviourKind::InvalidData);
+            assert_ok!(EtikaBlockchain::report_node(Origin::signed(host_b.clone()), node_a, report));
+            assert!(<ActiveHosts<Test>>::contains_key(&host_a));
+            
+            // Un même pair ne peut pas, à lui seul, faire exclure le nœud
+            System::set_block_number(6);
+            assert_noop!(
+                EtikaBlockchain::report_node(Origin::signed(host_b.clone()), node_a, report),
+                Error::<Test>::ReportLimitReached
+            );
+            
+            // Sous le seuil de réputation, l'hôte est exclu du réseau
+            assert_ok!(EtikaBlockchain::report_node(Origin::signed(host_c.clone()), node_a, report));
+            assert!(EtikaBlockchain::peer_reputation(node_a) < MinHostReputation::get());
+            assert!(!<ActiveHosts<Test>>::contains_key(&host_a));
+            assert!(!<NodeIdToAccount<Test>>::contains_key(node_a));
+            assert_eq!(<TotalActiveNodes>::get(), 2);
+            assert_eq!(EtikaBlockchain::node_report_counts(node_a, [2; 32]), 0);
+            
+            // Le bonus d'un hôte exclu sans preuve n'est pas confisqué
+            assert_eq!(Balances::free_balance(&host_a), 10);
+            assert_eq!(EtikaBlockchain::forfeited_hosting_bonus(), 0);
+            
+            // Le nœud exclu ne peut pas se réenregistrer avec sa réputation dégradée
+            assert_noop!(
+                EtikaBlockchain::register_node(
+                    Origin::signed(host_a.clone()),
+                    node_a,
+                    b"192.168.1.1:8080".to_vec(),
+                    1024,
+                    b"1.0.0".to_vec(),
+                ),
+                Error::<Test>::NodeReputationTooLow
+            );
+            
+            // La rotation oublie les connexions vers le nœud exclu
+            EtikaBlockchain::rotate_node_peers([2; 32]);
+            assert!(EtikaBlockchain::node_connections([2; 32]).iter().all(|conn| conn.node_id != node_a));
+        });
+    }
+    
+    #[test]
+    fn test_evicted_host_keeps_unpaid_bonus() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            let (host_a, node_a) = register_host(1);
+            let (host_b, _) = register_host(2);
+            <ActiveHosts<Test>>::mutate(&host_a, |host| host.hosting_bonus = 10);
+            <NodeReputations>::insert(node_a, MinHostReputation::get());
+            
+            // Réserve vide: le bonus reste réclamable après l'exclusion
+            assert_ok!(EtikaBlockchain::report_node(Origin::signed(host_b.clone()), node_a, NodeReport::Unavailable));
+            assert!(!<ActiveHosts<Test>>::contains_key(&host_a));
+            assert_eq!(EtikaBlockchain::pending_hosting_bonus(&host_a), 10);
+            
+            let _ = Balances::deposit_creating(&EtikaBlockchain::reward_pot_account(), 100);
+            assert_ok!(EtikaBlockchain::claim_hosting_rewards(Origin::signed(host_a.clone())));
+            assert_eq!(Balances::free_balance(&host_a), 10);
+            assert_eq!(EtikaBlockchain::pending_hosting_bonus(&host_a), 0);
+        });
+    }
+    
+    #[test]
+    fn test_slash_node_forfeits_hosting_bonus() {
+        new_test_ext().execute_with(|| {
+            let (host, node_id) = register_host(1);
+            
+            // Accumuler un bonus d'hébergement
+            EtikaBlockchain::on_initialize(10);
+            assert_eq!(<ActiveHosts<Test>>::get(&host).hosting_bonus, HostingReward::get());
+            
+            // Seule la gouvernance peut sanctionner un nœud
+            assert_noop!(
+                EtikaBlockchain::slash_node(Origin::signed(host.clone()), node_id, MisbehaviourKind::Equivocation),
+                BadOrigin
+            );
+            
+            assert_ok!(EtikaBlockchain::slash_node(Origin::root(), node_id, MisbehaviourKind::Equivocation));
+            assert_eq!(EtikaBlockchain::forfeited_hosting_bonus(), HostingReward::get());
+            assert_eq!(EtikaBlockchain::peer_reputation(node_id), 0);
+            assert!(!<ActiveHosts<Test>>::contains_key(&host));
             assert_eq!(<TotalActiveNodes>::get(), 0);
         });
     }
+    
+    #[test]
+    fn test_hosting_reward_scaling() {
+        new_test_ext().execute_with(|| {
+            let (host, _) = register_host(1);
+            let mut host = <ActiveHosts<Test>>::get(&host);
+            
+            // Une unité de stockage, pleine disponibilité
+            assert_eq!(EtikaBlockchain::hosting_reward(&host), 10);
+            
+            // Quatre unités de stockage, plafonnées au-delà de MAX_STORAGE_REWARD_UNITS
+            host.node_info.storage_capacity = 4 * 1024;
+            assert_eq!(EtikaBlockchain::hosting_reward(&host), 40);
+            host.node_info.storage_capacity = 1024 * 1024;
+            assert_eq!(EtikaBlockchain::hosting_reward(&host), 100);
+            
+            // Disponibilité de 50%, puis part réduite d'un sponsor
+            host.node_info.storage_capacity = 4 * 1024;
+            host.node_info.availability_score = 50;
+            assert_eq!(EtikaBlockchain::hosting_reward(&host), 20);
+            host.actor_type = ActorType::Sponsor;
+            assert_eq!(EtikaBlockchain::hosting_reward(&host), 10);
+        });
+    }
+    
+    #[test]
+    fn test_hosting_rewards_capped_per_period() {
+        new_test_ext().execute_with(|| {
+            let hosts = [register_host(1).0, register_host(2).0, register_host(3).0, register_host(4).0, register_host(5).0];
+            
+            // 5 hôtes à 10 pour une enveloppe de 25: réduction de moitié, 4 hôtes crédités par bloc
+            EtikaBlockchain::on_initialize(10);
+            assert_eq!(<ActiveHosts<Test>>::get(&hosts[4]).hosting_bonus, 0);
+            assert_eq!(EtikaBlockchain::reward_cursor(), Some(10));
+            
+            EtikaBlockchain::on_initialize(11);
+            assert_eq!(EtikaBlockchain::reward_cursor(), None);
+            for host in hosts.iter() {
+                assert_eq!(<ActiveHosts<Test>>::get(host).hosting_bonus, 5);
+            }
+            
+            // Seule la gouvernance certifie le type d'acteur d'un hôte
+            assert_noop!(
+                EtikaBlockchain::set_host_actor_type(Origin::signed(hosts[0].clone()), hosts[0].clone(), ActorType::NGO),
+                BadOrigin
+            );
+            assert_ok!(EtikaBlockchain::set_host_actor_type(Origin::root(), hosts[0].clone(), ActorType::Investor));
+            assert_eq!(<ActiveHosts<Test>>::get(&hosts[0]).actor_type, ActorType::Investor);
+        });
+    }
+    
+    #[test]
+    fn test_claim_hosting_rewards() {
+        new_test_ext().execute_with(|| {
+            let (host, node_id) = register_host(1);
+            let pot = EtikaBlockchain::reward_pot_account();
+            
+            // Rien à réclamer avant la première distribution
+            assert_noop!(
+                EtikaBlockchain::claim_hosting_rewards(Origin::signed(host.clone())),
+                Error::<Test>::NoHostingReward
+            );
+            
+            EtikaBlockchain::on_initialize(10);
+            
+            // Réserve vide: le bonus reste acquis
+            assert_noop!(
+                EtikaBlockchain::claim_hosting_rewards(Origin::signed(host.clone())),
+                Error::<Test>::RewardPotExhausted
+            );
+            
+            let _ = Balances::deposit_creating(&pot, 100);
+            assert_ok!(EtikaBlockchain::claim_hosting_rewards(Origin::signed(host.clone())));
+            assert_eq!(Balances::free_balance(&host), 10);
+            assert_eq!(Balances::free_balance(&pot), 90);
+            assert_eq!(<ActiveHosts<Test>>::get(&host).hosting_bonus, 0);
+            
+            // Le bonus restant est versé au départ de l'hôte
+            EtikaBlockchain::on_initialize(20);
+            assert_ok!(EtikaBlockchain::unregister_node(Origin::signed(host.clone()), node_id));
+            assert_eq!(Balances::free_balance(&host), 20);
+            assert_eq!(Balances::free_balance(&pot), 80);
+        });
+    }
+    
+    #[test]
+    fn test_expiry_sweep_is_bounded_with_carry_over() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            register_accounts(&[&consumer, &merchant]);
+            
+            // Trois ventes directes arrivant à échéance au bloc 50
+            let ids = (0..3)
+                .map(|_| create_signed_sale(SaleType::DirectSale, &merchant, &consumer, &merchant, &[], 100).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(EtikaBlockchain::expiry_queue(50).len(), 3);
+            
+            // Deux expirations par bloc: la troisième est reportée
+            System::set_block_number(50);
+            let weight = EtikaBlockchain::on_initialize(50);
+            assert_eq!(
+                weight,
+                <() as WeightInfo>::credit_hosting_rewards(1, 0)
+                    + <() as WeightInfo>::rotate_peers(1, 0)
+                    + <() as WeightInfo>::sweep_expired_transactions(1, 2)
+                    + <() as WeightInfo>::settle_transactions(1, 0)
+                    + <() as WeightInfo>::archive_transactions(1, 0)
+            );
+            assert!(<ExpiredTransactions>::contains_key(ids[0]));
+            assert!(<ExpiredTransactions>::contains_key(ids[1]));
+            assert!(<PendingTransactions>::contains_key(ids[2]));
+            assert_eq!(EtikaBlockchain::expiry_cursor(), Some(50));
+            
+            // Une transaction échue ne peut plus être validée en attendant son retrait
+            assert_noop!(validate_signed(&consumer, ids[2]), Error::<Test>::TransactionExpired);
+            
+            // Le report est traité au bloc suivant
+            System::set_block_number(51);
+            EtikaBlockchain::on_initialize(51);
+            assert!(<ExpiredTransactions>::contains_key(ids[2]));
+            assert_eq!(EtikaBlockchain::expiry_queue(50).len(), 0);
+            assert_eq!(EtikaBlockchain::expiry_cursor(), None);
+        });
+    }
+    
+    #[test]
+    fn test_archive_frontier_matches_merkle_proofs() {
+        let leaves = (0u32..5).map(|index| BlakeTwo256::hash_of(&index)).collect::<Vec<_>>();
+        let mut frontier = ArchiveFrontier::default();
+        
+        for (index, leaf) in leaves.iter().enumerate() {
+            assert_eq!(frontier.append(*leaf), index as u32);
+            
+            // Chaque feuille déjà ajoutée est prouvable contre la racine courante
+            let added = &leaves[..=index];
+            for (position, added_leaf) in added.iter().enumerate() {
+                let siblings = merkle_proof(added, position as u32);
+                assert_eq!(siblings.len(), ARCHIVE_TREE_DEPTH);
+                assert_eq!(merkle_root_from_proof(*added_leaf, position as u32, &siblings), frontier.root());
+            }
+        }
+        
+        // Une feuille à une autre position ne donne pas la même racine
+        let siblings = merkle_proof(&leaves, 1);
+        assert_ne!(merkle_root_from_proof(leaves[1], 2, &siblings), frontier.root());
+    }
+    
+    #[test]
+    fn test_transactions_archived_after_retention() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            register_accounts(&[&consumer, &merchant]);
+            add_system_operator(merchant.account());
+            
+            // Une transaction finalisée et une transaction expirée
+            System::set_block_number(1);
+            let validated_id = create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            let expired_id = create_signed_sale(SaleType::DirectSale, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            let validated = <ValidatedTransactions>::get(validated_id);
+            assert!(EtikaBlockchain::transaction_policies(validated_id).is_some());
+            
+            // Le règlement reste contestable jusqu'à l'archivage
+            assert!(pop_notifications().is_empty());
+            
+            System::set_block_number(21);
+            EtikaBlockchain::on_initialize(21);
+            assert!(!<ValidatedTransactions>::contains_key(validated_id));
+            assert_eq!(pop_notifications(), vec![(validated_id, true)]);
+            assert_eq!(EtikaBlockchain::transaction_policies(validated_id), None);
+            assert_eq!(EtikaBlockchain::current_archive().map(|(period, frontier)| (period, frontier.leaf_count)), Some((0, 1)));
+            
+            System::set_block_number(51);
+            EtikaBlockchain::on_initialize(51);
+            let expired = <ExpiredTransactions>::get(expired_id);
+            
+            // Période 0 close à l'entrée dans la période 1: seule sa racine est conservée
+            let commitment = EtikaBlockchain::archive_roots(0).unwrap();
+            assert_eq!(commitment.leaf_count, 1);
+            assert_eq!(EtikaBlockchain::current_archive(), None);
+            
+            let leaf = archive_leaf(ArchiveStatus::Validated, &validated);
+            let mut proof = ArchiveProof {
+                period: 0,
+                leaf_index: 0,
+                status: ArchiveStatus::Validated,
+                transaction: validated,
+                siblings: merkle_proof(&[leaf], 0),
+            };
+            assert!(EtikaBlockchain::verify_archive_proof(&proof));
+            
+            // Un corps ou un statut altéré n'est pas prouvable
+            proof.status = ArchiveStatus::Expired;
+            assert!(!EtikaBlockchain::verify_archive_proof(&proof));
+            proof.status = ArchiveStatus::Validated;
+            proof.transaction.standard_amount += 1;
+            assert!(!EtikaBlockchain::verify_archive_proof(&proof));
+            
+            // La transaction expirée est archivée dans la période suivante
+            System::set_block_number(71);
+            EtikaBlockchain::on_initialize(71);
+            assert!(!<ExpiredTransactions>::contains_key(expired_id));
+            
+            System::set_block_number(100);
+            EtikaBlockchain::on_initialize(100);
+            let leaf = archive_leaf(ArchiveStatus::Expired, &expired);
+            assert_eq!(EtikaBlockchain::archive_roots(1).unwrap().root, merkle_root_from_proof(leaf, 0, &merkle_proof(&[leaf], 0)));
+        });
+    }
+    
+    #[test]
+    fn test_dispute_window() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let outsider = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &merchant]);
+            add_system_operator(merchant.account());
+            
+            // Les effets de la finalisation sont appliqués et conservés pour une éventuelle annulation
+            System::set_block_number(1);
+            let transaction_id = create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            assert_eq!(MockTokenSystem::active_balance(&consumer.account()), 10);
+            assert_eq!(MockConsumerFund::get_savings_balance(&consumer.account()), Ok((5, 0)));
+            assert_eq!(
+                EtikaBlockchain::settlements(transaction_id),
+                Some(Settlement { finalized_at: 1, tokens_activated: 10, savings_credited: 5 })
+            );
+            
+            // Seuls les participants peuvent contester
+            assert_noop!(
+                EtikaBlockchain::open_dispute(Origin::signed(outsider.account()), transaction_id, DisputeReason::Fraud),
+                Error::<Test>::UnauthorizedValidator
+            );
+            
+            // Un seul litige à la fois; l'ouverture par un non-signataire ne vaut pas approbation
+            System::set_block_number(11);
+            assert_ok!(EtikaBlockchain::open_dispute(Origin::signed(consumer.account()), transaction_id, DisputeReason::Refund));
+            let dispute = EtikaBlockchain::disputes(transaction_id).unwrap();
+            assert_eq!(dispute.opened_by, consumer.account());
+            assert!(dispute.cancel_approvals.is_empty());
+            assert_noop!(
+                EtikaBlockchain::open_dispute(Origin::signed(merchant.account()), transaction_id, DisputeReason::Refund),
+                Error::<Test>::DisputeAlreadyOpen
+            );
+            
+            // Passé le délai de contestation, la transaction est définitive
+            let late_id = create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            System::set_block_number(22);
+            assert_noop!(
+                EtikaBlockchain::open_dispute(Origin::signed(consumer.account()), late_id, DisputeReason::Refund),
+                Error::<Test>::DisputeWindowClosed
+            );
+        });
+    }
+    
+    #[test]
+    fn test_settlement_final_after_dispute_window() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            register_accounts(&[&consumer, &merchant]);
+            add_system_operator(merchant.account());
+            
+            System::set_block_number(1);
+            let settled_id = create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            let disputed_id = create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            assert_ok!(EtikaBlockchain::open_dispute(Origin::signed(consumer.account()), disputed_id, DisputeReason::Refund));
+            
+            // Le règlement reste contestable jusqu'à la fin du délai de contestation
+            System::set_block_number(11);
+            EtikaBlockchain::on_initialize(11);
+            assert!(pop_notifications().is_empty());
+            assert!(EtikaBlockchain::settlements(settled_id).is_some());
+            
+            // Il devient ensuite définitif, bien avant l'archivage; le litige ouvert attend sa résolution
+            System::set_block_number(12);
+            EtikaBlockchain::on_initialize(12);
+            assert_eq!(pop_notifications(), vec![(settled_id, true)]);
+            assert_eq!(EtikaBlockchain::settlements(settled_id), None);
+            assert!(EtikaBlockchain::settlements(disputed_id).is_some());
+            assert!(<ValidatedTransactions>::contains_key(settled_id));
+            assert_noop!(
+                EtikaBlockchain::open_dispute(Origin::signed(consumer.account()), settled_id, DisputeReason::Fraud),
+                Error::<Test>::DisputeWindowClosed
+            );
+        });
+    }
+    
+    #[test]
+    fn test_mutual_cancellation_reverses_transaction() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::ed25519(2);
+            register_accounts(&[&consumer, &merchant]);
+            
+            System::set_block_number(1);
+            let transaction_id = create_signed_sale(SaleType::DirectSale, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            assert_ok!(validate_signed(&consumer, transaction_id));
+            assert!(<ValidatedTransactions>::contains_key(transaction_id));
+            
+            // Le consommateur conteste et approuve l'annulation du même coup
+            System::set_block_number(3);
+            assert_ok!(EtikaBlockchain::open_dispute(Origin::signed(consumer.account()), transaction_id, DisputeReason::Fraud));
+            assert_noop!(
+                EtikaBlockchain::approve_cancellation(Origin::signed(consumer.account()), transaction_id),
+                Error::<Test>::AlreadyApproved
+            );
+            
+            // L'approbation du commerçant rend l'annulation unanime
+            assert_ok!(EtikaBlockchain::approve_cancellation(Origin::signed(merchant.account()), transaction_id));
+            assert!(!<ValidatedTransactions>::contains_key(transaction_id));
+            assert!(<ReversedTransactions>::contains_key(transaction_id));
+            assert_eq!(EtikaBlockchain::disputes(transaction_id), None);
+            assert_eq!(EtikaBlockchain::settlements(transaction_id), None);
+            assert_eq!(MockTokenSystem::active_balance(&consumer.account()), 0);
+            assert_eq!(MockConsumerFund::get_savings_balance(&consumer.account()), Ok((0, 0)));
+            
+            // La transaction annulée est archivée avec son statut à la fin de sa conservation
+            System::set_block_number(21);
+            EtikaBlockchain::on_initialize(21);
+            assert_eq!(EtikaBlockchain::current_archive(), None);
+            
+            System::set_block_number(23);
+            EtikaBlockchain::on_initialize(23);
+            assert!(!<ReversedTransactions>::contains_key(transaction_id));
+            assert_eq!(EtikaBlockchain::current_archive().map(|(period, frontier)| (period, frontier.leaf_count)), Some((0, 1)));
+        });
+    }
+    
+    #[test]
+    fn test_arbitrator_resolves_dispute() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let arbitrator = TestKey::sr25519(3);
+            register_accounts(&[&consumer, &merchant]);
+            add_system_operator(merchant.account());
+            
+            System::set_block_number(1);
+            let confirmed_id = create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            let reversed_id = create_signed_sale(SaleType::System, &merchant, &consumer, &merchant, &[], 100).unwrap();
+            assert_ok!(EtikaBlockchain::open_dispute(Origin::signed(consumer.account()), confirmed_id, DisputeReason::WrongAmount));
+            assert_ok!(EtikaBlockchain::open_dispute(Origin::signed(consumer.account()), reversed_id, DisputeReason::Refund));
+            
+            // Seuls les arbitres désignés par le framework de sécurité tranchent
+            assert_noop!(
+                EtikaBlockchain::resolve_dispute(Origin::signed(arbitrator.account()), confirmed_id, true),
+                Error::<Test>::NotArbitrator
+            );
+            add_arbitrator(arbitrator.account());
+            
+            // Un litige ouvert reporte l'archivage de la transaction
+            System::set_block_number(21);
+            EtikaBlockchain::on_initialize(21);
+            assert!(<ValidatedTransactions>::contains_key(confirmed_id));
+            
+            // Transaction confirmée: elle devient définitive
+            assert!(pop_notifications().is_empty());
+            assert_ok!(EtikaBlockchain::resolve_dispute(Origin::signed(arbitrator.account()), confirmed_id, false));
+            assert!(<ValidatedTransactions>::contains_key(confirmed_id));
+            assert_eq!(EtikaBlockchain::disputes(confirmed_id), None);
+            assert_eq!(pop_notifications(), vec![(confirmed_id, true)]);
+            assert_noop!(
+                EtikaBlockchain::open_dispute(Origin::signed(consumer.account()), confirmed_id, DisputeReason::Fraud),
+                Error::<Test>::DisputeWindowClosed
+            );
+            
+            // L'annulation échoue sans rien modifier si l'épargne a déjà été utilisée
+            assert_ok!(MockConsumerFund::remove_savings(&consumer.account(), &merchant.account(), 10));
+            assert_noop!(
+                EtikaBlockchain::resolve_dispute(Origin::signed(arbitrator.account()), reversed_id, true),
+                Error::<Test>::ReversalFailed
+            );
+            
+            // Une fois l'épargne reconstituée, l'annulation reprend les effets de la transaction
+            assert_ok!(MockConsumerFund::add_savings(&consumer.account(), &merchant.account(), 5));
+            assert_ok!(EtikaBlockchain::resolve_dispute(Origin::signed(arbitrator.account()), reversed_id, true));
+            assert!(<ReversedTransactions>::contains_key(reversed_id));
+            assert_eq!(MockTokenSystem::active_balance(&consumer.account()), 10);
+            assert_eq!(MockConsumerFund::get_savings_balance(&consumer.account()), Ok((0, 0)));
+            assert_eq!(pop_notifications(), vec![(confirmed_id, true), (reversed_id, false)]);
+            
+            // La transaction confirmée est archivée au terme du report, sans nouvelle notification
+            System::set_block_number(31);
+            EtikaBlockchain::on_initialize(31);
+            assert!(!<ValidatedTransactions>::contains_key(confirmed_id));
+            assert_eq!(EtikaBlockchain::settlements(confirmed_id), None);
+            assert_eq!(pop_notifications().len(), 2);
+        });
+    }
+    
+    #[test]
+    fn test_supplier_links_line_item() {
+        new_test_ext().execute_with(|| {
+            let consumer = TestKey::sr25519(1);
+            let merchant = TestKey::sr25519(2);
+            let supplier = TestKey::sr25519(3);
+            let other_supplier = TestKey::ed25519(4);
+            register_accounts(&[&consumer, &merchant, &supplier, &other_supplier]);
+            
+            // Ticket de trois lignes: une par fournisseur et un produit propre au commerçant
+            let line = |product: u8, supplier: Option<AccountId>| ReceiptLineItem {
+                product_id: [product; 32],
+                quantity: 2,
+                unit_price: 25,
+                supplier,
+            };
+            let items = vec![
+                line(1, Some(supplier.account())),
+                line(2, Some(other_supplier.account())),
+                line(3, None),
+            ];
+            let transaction_id = create_signed_receipt(
+                SaleType::RetailSale,
+                &merchant,
+                &consumer,
+                &merchant,
+                &[&supplier, &other_supplier],
+                150,
+                etika_data_structure::receipt_root(&items),
+            ).unwrap();
+            let proof = |index| etika_data_structure::receipt_line_proof(&items, index).unwrap();
+            
+            // Le fournisseur prouve la présence de son produit sans divulguer le reste du panier
+            assert_ok!(EtikaBlockchain::link_supplier_line_item(Origin::signed(supplier.account()), transaction_id, proof(0)));
+            assert_eq!(EtikaBlockchain::supplier_line_items(transaction_id, supplier.account()), vec![(0, items[0].clone())]);
+            assert_noop!(
+                EtikaBlockchain::link_supplier_line_item(Origin::signed(supplier.account()), transaction_id, proof(0)),
+                Error::<Test>::LineItemAlreadyLinked
+            );
+            
+            // Une ligne attribuée à un autre fournisseur ou au commerçant ne peut pas être revendiquée
+            assert_noop!(
+                EtikaBlockchain::link_supplier_line_item(Origin::signed(supplier.account()), transaction_id, proof(1)),
+                Error::<Test>::LineItemSupplierMismatch
+            );
+            assert_noop!(
+                EtikaBlockchain::link_supplier_line_item(Origin::signed(merchant.account()), transaction_id, proof(2)),
+                Error::<Test>::UnauthorizedValidator
+            );
+            
+            // Une ligne altérée n'appartient pas au ticket engagé
+            let mut forged = proof(1);
+            forged.item.quantity = 20;
+            assert_noop!(
+                EtikaBlockchain::link_supplier_line_item(Origin::signed(other_supplier.account()), transaction_id, forged),
+                Error::<Test>::InvalidReceiptProof
+            );
+            assert_ok!(EtikaBlockchain::link_supplier_line_item(Origin::signed(other_supplier.account()), transaction_id, proof(1)));
+        });
+    }
+    
+    // Transaction telle qu'enregistrée par la première version du module
+    fn legacy_transaction(seed: u8) -> PoPTransactionV1 {
+        PoPTransactionV1 {
+            id: [seed; 32],
+            consumer: TestKey::sr25519(1).account(),
+            merchant: TestKey::sr25519(2).account(),
+            suppliers: vec![TestKey::sr25519(4).account()],
+            standard_amount: 100 + seed as Balance,
+            tokens_exchanged: 10,
+            savings_generated: 5,
+            timestamp: seed as Moment,
+            receipt_hash: [seed; 32],
+            signatures: vec![(TestKey::sr25519(2).account(), dummy_proof())],
+        }
+    }
+    
+    // Écrire une transaction encodée au format de la V1 dans une table de transactions
+    fn put_legacy_transaction(key: Vec<u8>, seed: u8) {
+        frame_support::storage::unhashed::put_raw(&key, &legacy_transaction(seed).encode());
+    }
+    
+    #[test]
+    fn test_migration_from_v1_storage() {
+        new_test_ext().execute_with(|| {
+            // Stockage laissé par la première version: pas de version, de politique, de files ni de connexions
+            <StorageVersion>::kill();
+            <ValidationPolicies<Test>>::remove(SaleType::RetailSale);
+            put_legacy_transaction(<PendingTransactions>::hashed_key_for([1; 32]), 1);
+            put_legacy_transaction(<PendingTransactions>::hashed_key_for([5; 32]), 5);
+            <TransactionStartBlock<Test>>::insert([1; 32], 5);
+            put_legacy_transaction(<ValidatedTransactions>::hashed_key_for([2; 32]), 2);
+            put_legacy_transaction(<ValidatedTransactions>::hashed_key_for([6; 32]), 6);
+            
+            // L'ancien encodage n'est pas lisible avec le format actuel
+            assert_eq!(
+                frame_support::storage::unhashed::get::<PoPTransaction>(&<ValidatedTransactions>::hashed_key_for([2; 32])),
+                None
+            );
+            
+            let host = TestKey::sr25519(3).account();
+            <ActiveHosts<Test>>::insert(&host, BlockchainHost {
+                account_id: host.clone(),
+                actor_type: ActorType::Merchant,
+                node_info: NodeInfo {
+                    node_id: [3; 32],
+                    network_address: b"192.168.1.1:8080".to_vec(),
+                    storage_capacity: 1024,
+                    availability_score: 100,
+                    software_version: b"0.9.0".to_vec(),
+                },
+                hosting_bonus: 40,
+                hosting_since: 0,
+            });
+            <NodeIdToAccount<Test>>::insert([3; 32], Some(host.clone()));
+            <LastRewardBlock<Test>>::insert(&host, 3);
+            <TotalActiveNodes>::put(7);
+            
+            System::set_block_number(10);
+            EtikaBlockchain::on_runtime_upgrade();
+            assert_eq!(EtikaBlockchain::storage_version(), Releases::V2);
+            
+            // Règle de la V1 pour les types de vente sans politique, les autres sont conservées
+            let retail = EtikaBlockchain::validation_policies(SaleType::RetailSale).unwrap();
+            assert!(retail.consumer_required && retail.merchant_required);
+            assert_eq!(retail.required_supplier_signatures, 8);
+            assert_eq!(retail.validation_period, 100);
+            assert_eq!(EtikaBlockchain::validation_policies(SaleType::DirectSale), Some(policy(true, true, (0, 0), 0, 50)));
+            
+            // Toutes les transactions de la V1 sont conservées au format actuel, avec un nonce nul:
+            // les transactions en attente sont expirées, toutes sont inscrites dans la file d'archivage
+            for seed in &[1u8, 5] {
+                assert!(!<PendingTransactions>::contains_key([*seed; 32]));
+                assert_eq!(EtikaBlockchain::expired_transactions([*seed; 32]), PoPTransaction::from(legacy_transaction(*seed)));
+            }
+            for seed in &[2u8, 6] {
+                let transaction = EtikaBlockchain::validated_transactions([*seed; 32]);
+                assert_eq!(transaction, PoPTransaction::from(legacy_transaction(*seed)));
+                assert_eq!(transaction.nonce, 0);
+            }
+            assert!(!<TransactionStartBlock<Test>>::contains_key([1; 32]));
+            let archived = EtikaBlockchain::archive_queue(30);
+            assert_eq!(archived.len(), 4);
+            for entry in &[
+                ([1; 32], ArchiveStatus::Expired),
+                ([2; 32], ArchiveStatus::Validated),
+                ([5; 32], ArchiveStatus::Expired),
+                ([6; 32], ArchiveStatus::Validated),
+            ] {
+                assert!(archived.contains(entry));
+            }
+            
+            // Hôte replanifié, totaux resynchronisés, bonus accumulé conservé
+            assert_eq!(EtikaBlockchain::last_reward_block(&host), 10);
+            assert_eq!(EtikaBlockchain::reward_queue(20), vec![host.clone()]);
+            assert_eq!(EtikaBlockchain::total_active_nodes(), 1);
+            assert_eq!(EtikaBlockchain::total_hosting_rewards(), 10);
+            assert_eq!(EtikaBlockchain::active_hosts(&host).hosting_bonus, 40);
+            assert!(<NodeConnections>::contains_key([3; 32]));
+            
+            // Le stockage migré est exploité par le module: récompense créditée à l'échéance
+            System::set_block_number(20);
+            EtikaBlockchain::on_initialize(20);
+            assert_eq!(EtikaBlockchain::active_hosts(&host).hosting_bonus, 50);
+            
+            // Une seconde mise à niveau ne rejoue pas la migration
+            <PendingTransactions>::insert([4; 32], PoPTransaction::from(legacy_transaction(4)));
+            EtikaBlockchain::on_runtime_upgrade();
+            assert!(<PendingTransactions>::contains_key([4; 32]));
+        });
+    }
 }
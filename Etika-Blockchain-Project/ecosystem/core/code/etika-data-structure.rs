@@ -5,14 +5,17 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, FullCodec};
 use sp_runtime::{
     generic,
-    traits::{BlakeTwo256, IdentifyAccount, Verify},
+    traits::{AtLeast32BitUnsigned, BlakeTwo256, Hash as HashT, IdentifyAccount, Verify},
     MultiSignature, RuntimeDebug,
 };
 use sp_std::prelude::*;
-use frame_support::Parameter;
+use frame_support::{
+    storage::{StorageMap, StorageValue},
+    Parameter,
+};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
@@ -69,15 +72,17 @@ pub enum TokenState {
 }
 
 /// Représente un token Étika avec son état et ses métadonnées
+///
+/// En mode suivi, un token représente un lot de tokens dont la provenance est conservée.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct Token {
+pub struct Token<Account = AccountId> {
     /// Identifiant unique du token
     pub id: [u8; 32],
     /// État actuel du token
     pub state: TokenState,
     /// Propriétaire actuel du token
-    pub owner: AccountId,
+    pub owner: Account,
     /// Moment de création du token
     pub created_at: Moment,
     /// Moment de la dernière mise à jour du token
@@ -85,9 +90,14 @@ pub struct Token {
     /// Valeur actuelle du token (peut varier selon l'état)
     pub value: Balance,
     /// Historique des transferts (limité aux 5 derniers pour économiser l'espace)
-    pub transfer_history: Vec<(AccountId, Moment)>,
+    pub transfer_history: Vec<(Account, Moment)>,
+    /// Identifiants des lots dont ce token est issu, parents directs en premier (borné)
+    pub lineage: Vec<[u8; 32]>,
 }
 
+/// Nombre maximum d'entrées conservées dans l'historique des transferts d'un token
+pub const TOKEN_TRANSFER_HISTORY_LIMIT: usize = 5;
+
 /// Types d'acteurs dans l'écosystème Étika
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -177,10 +187,193 @@ pub struct PoPTransaction {
     pub savings_generated: Balance,
     /// Horodatage de la transaction
     pub timestamp: Moment,
-    /// Hash du ticket de caisse numérique
+    /// Racine de Merkle des lignes du ticket de caisse numérique (voir `receipt_root`)
     pub receipt_hash: [u8; 32],
     /// Signatures des participants
     pub signatures: Vec<(AccountId, Signature)>,
+    /// Nonce du créateur de la transaction, inclus dans le message signé
+    pub nonce: u64,
+}
+
+/// Contextes de hachage de l'arbre de Merkle des tickets (séparation de domaine)
+pub const RECEIPT_LEAF_CONTEXT: &[u8] = b"etika/receipt/leaf";
+pub const RECEIPT_NODE_CONTEXT: &[u8] = b"etika/receipt/node";
+pub const RECEIPT_ROOT_CONTEXT: &[u8] = b"etika/receipt/root";
+
+/// Ligne d'un ticket de caisse
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReceiptLineItem {
+    /// Identifiant du produit (hash de la référence produit)
+    pub product_id: [u8; 32],
+    /// Quantité achetée
+    pub quantity: u32,
+    /// Prix unitaire
+    pub unit_price: Balance,
+    /// Fournisseur du produit (aucun pour les produits du commerçant)
+    pub supplier: Option<AccountId>,
+}
+
+/// Preuve qu'une ligne appartient au ticket d'une transaction PoP, sans révéler les autres lignes
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReceiptLineProof {
+    /// Ligne divulguée
+    pub item: ReceiptLineItem,
+    /// Position de la ligne dans le ticket
+    pub leaf_index: u32,
+    /// Nombre de lignes du ticket
+    pub leaf_count: u32,
+    /// Nœuds frères, de la feuille vers la racine
+    pub siblings: Vec<[u8; 32]>,
+}
+
+/// Feuille de l'arbre d'un ticket
+pub fn receipt_leaf(item: &ReceiptLineItem) -> [u8; 32] {
+    BlakeTwo256::hash_of(&(RECEIPT_LEAF_CONTEXT, item)).to_fixed_bytes()
+}
+
+/// Nœud parent de deux nœuds de l'arbre d'un ticket
+fn receipt_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    BlakeTwo256::hash_of(&(RECEIPT_NODE_CONTEXT, left, right)).to_fixed_bytes()
+}
+
+/// Racine engageant l'arbre et le nombre de lignes, afin qu'une preuve ne vaille que pour ce ticket
+fn receipt_commitment(leaf_count: u32, tree_root: &[u8; 32]) -> [u8; 32] {
+    BlakeTwo256::hash_of(&(RECEIPT_ROOT_CONTEXT, leaf_count, tree_root)).to_fixed_bytes()
+}
+
+/// Racine de Merkle des lignes d'un ticket, à renseigner dans `PoPTransaction::receipt_hash`
+///
+/// Les feuilles sont combinées deux à deux; un nœud sans frère est promu tel quel au niveau supérieur.
+pub fn receipt_root(items: &[ReceiptLineItem]) -> [u8; 32] {
+    let mut layer: Vec<[u8; 32]> = items.iter().map(receipt_leaf).collect();
+    
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => receipt_node(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    
+    receipt_commitment(items.len() as u32, &layer.first().copied().unwrap_or([0; 32]))
+}
+
+/// Preuve d'appartenance de la ligne `index` d'un ticket
+pub fn receipt_line_proof(items: &[ReceiptLineItem], index: u32) -> Option<ReceiptLineProof> {
+    let item = items.get(index as usize)?.clone();
+    let mut layer: Vec<[u8; 32]> = items.iter().map(receipt_leaf).collect();
+    let mut position = index as usize;
+    let mut siblings = Vec::new();
+    
+    while layer.len() > 1 {
+        if let Some(sibling) = layer.get(position ^ 1) {
+            siblings.push(*sibling);
+        }
+        
+        layer = layer
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => receipt_node(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        position >>= 1;
+    }
+    
+    Some(ReceiptLineProof {
+        item,
+        leaf_index: index,
+        leaf_count: items.len() as u32,
+        siblings,
+    })
+}
+
+/// Vérifier qu'une ligne divulguée appartient au ticket engagé par `receipt_hash`
+pub fn verify_receipt_line(receipt_hash: &[u8; 32], proof: &ReceiptLineProof) -> bool {
+    if proof.leaf_index >= proof.leaf_count {
+        return false;
+    }
+    
+    let mut node = receipt_leaf(&proof.item);
+    let mut position = proof.leaf_index;
+    let mut width = proof.leaf_count;
+    let mut siblings = proof.siblings.iter();
+    
+    while width > 1 {
+        // Le dernier nœud d'un niveau impair est promu sans frère
+        if position ^ 1 < width {
+            let sibling = match siblings.next() {
+                Some(sibling) => sibling,
+                None => return false,
+            };
+            node = if position & 1 == 1 {
+                receipt_node(sibling, &node)
+            } else {
+                receipt_node(&node, sibling)
+            };
+        }
+        
+        position >>= 1;
+        width = (width + 1) / 2;
+    }
+    
+    siblings.next().is_none() && receipt_commitment(proof.leaf_count, &node) == *receipt_hash
+}
+
+/// Parcourir une file indexée par bloc, du plus ancien compartiment en attente au bloc courant
+///
+/// Au plus `limit` compartiments sont lus et `limit` éléments traités. Les éléments non
+/// traités restent dans leur compartiment, d'où le parcours reprend au bloc suivant.
+/// Une limite nulle bloquerait la file: les modules l'écartent dans leur `integrity_test`.
+/// Retourne le nombre de compartiments lus et d'éléments traités.
+pub fn sweep_queue<Queue, Cursor, Item, BlockNumber>(
+    now: BlockNumber,
+    limit: u32,
+    mut process: impl FnMut(Item, BlockNumber),
+) -> (u32, u32)
+where
+    Queue: StorageMap<BlockNumber, Vec<Item>, Query = Vec<Item>>,
+    Cursor: StorageValue<BlockNumber, Query = Option<BlockNumber>>,
+    Item: FullCodec,
+    BlockNumber: AtLeast32BitUnsigned + FullCodec + Copy,
+{
+    let mut block = Cursor::get().unwrap_or(now);
+    let mut buckets = 0;
+    let mut processed = 0;
+    
+    while block <= now && buckets < limit && processed < limit {
+        let mut items = Queue::take(block);
+        buckets += 1;
+        
+        let count = items.len().min((limit - processed) as usize);
+        let carried_over = items.split_off(count);
+        for item in items {
+            process(item, block);
+        }
+        processed += count as u32;
+        
+        // Report des éléments non traités
+        if !carried_over.is_empty() {
+            Queue::insert(block, carried_over);
+            break;
+        }
+        
+        block += 1u32.into();
+    }
+    
+    if block > now {
+        Cursor::kill();
+    } else {
+        Cursor::put(block);
+    }
+    
+    (buckets, processed)
 }
 
 /// Données d'une enchère pour sélection des sponsors
@@ -417,12 +610,22 @@ pub enum ProductStatus {
 pub trait TokenSystem: Sized {
     fn distribute_tokens(to: &AccountId, amount: Balance) -> Result<(), &'static str>;
     fn activate_tokens(from: &AccountId, amount: Balance) -> Result<(), &'static str>;
+    fn deactivate_tokens(from: &AccountId, amount: Balance) -> Result<(), &'static str>;
     fn burn_tokens(from: &AccountId, amount: Balance) -> Result<(), &'static str>;
     fn transfer_tokens(from: &AccountId, to: &AccountId, amount: Balance) -> Result<(), &'static str>;
+    fn active_balance(account: &AccountId) -> Balance;
+    fn reserve_tokens(account: &AccountId, amount: Balance) -> Result<(), &'static str>;
+    fn unreserve_tokens(account: &AccountId, amount: Balance) -> Result<(), &'static str>;
+}
+
+/// Programmation par la gouvernance des taux de brûlage et de la part ONG (en centièmes de pourcentage)
+pub trait TokenFeeGovernance {
+    fn schedule_fee_rates(burn_rate: u32, ngo_rate: u32) -> Result<(), &'static str>;
 }
 
 pub trait ConsumerFund: Sized {
-    fn add_savings(consumer: &AccountId, amount: Balance) -> Result<(), &'static str>;
+    fn add_savings(consumer: &AccountId, contributor: &AccountId, amount: Balance) -> Result<(), &'static str>;
+    fn remove_savings(consumer: &AccountId, contributor: &AccountId, amount: Balance) -> Result<(), &'static str>;
     fn get_savings_balance(consumer: &AccountId) -> Result<(Balance, Balance), &'static str>;
     fn calculate_credit_rate(consumer: &AccountId) -> Result<u32, &'static str>;
 }
@@ -431,6 +634,7 @@ pub trait AuctionSystem: Sized {
     fn create_auction(category: Vec<u8>, start_time: Moment, duration: u64, starting_price: Balance) -> Result<[u8; 32], &'static str>;
     fn place_bid(auction_id: [u8; 32], bidder: &AccountId, amount: Balance) -> Result<(), &'static str>;
     fn finalize_auction(auction_id: [u8; 32]) -> Result<Option<AccountId>, &'static str>;
+    fn get_category_sponsor(category: Vec<u8>) -> Option<AccountId>;
 }
 
 pub trait FactoringSystem: Sized {
@@ -445,12 +649,31 @@ pub trait Marketplace: Sized {
     fn match_orders() -> Result<(), &'static str>;
 }
 
+pub trait TradeMonitor: Sized {
+    fn check_trade(volume: Balance, price: Balance) -> Result<(), &'static str>;
+}
+
 pub trait PoPConsensus: Sized {
     fn validate_transaction(transaction: &PoPTransaction) -> Result<(), &'static str>;
     fn finalize_transaction(transaction: &PoPTransaction) -> Result<(), &'static str>;
     fn get_transaction(id: [u8; 32]) -> Result<PoPTransaction, &'static str>;
 }
 
+/// Notification du règlement d'une transaction PoP (libération des séquestres conditionnels)
+///
+/// `on_pop_finalized` n'est appelée qu'une fois la transaction définitive (délai de contestation
+/// écoulé sans litige, ou confirmation par un arbitre); `on_pop_reversed` à l'annulation d'une
+/// transaction contestée.
+pub trait OnPopFinalized {
+    fn on_pop_finalized(transaction_id: &[u8; 32]);
+    fn on_pop_reversed(transaction_id: &[u8; 32]);
+}
+
+impl OnPopFinalized for () {
+    fn on_pop_finalized(_transaction_id: &[u8; 32]) {}
+    fn on_pop_reversed(_transaction_id: &[u8; 32]) {}
+}
+
 /// Tests unitaires pour les structures de données
 #[cfg(test)]
 mod tests {
@@ -492,4 +715,41 @@ mod tests {
         
         assert!(LoyaltyTier::Diamond > LoyaltyTier::Bronze);
     }
+    
+    #[test]
+    fn test_receipt_line_proofs() {
+        let items: Vec<ReceiptLineItem> = (0u8..5)
+            .map(|index| ReceiptLineItem {
+                product_id: [index; 32],
+                quantity: u32::from(index) + 1,
+                unit_price: 100,
+                supplier: None,
+            })
+            .collect();
+        let root = receipt_root(&items);
+        
+        // Chaque ligne est prouvable, y compris la dernière ligne promue sans frère
+        for index in 0..items.len() as u32 {
+            let proof = receipt_line_proof(&items, index).unwrap();
+            assert!(verify_receipt_line(&root, &proof));
+        }
+        assert!(receipt_line_proof(&items, 5).is_none());
+        
+        // Une ligne altérée, déplacée ou rattachée à un ticket tronqué n'est pas prouvable
+        let mut proof = receipt_line_proof(&items, 2).unwrap();
+        proof.item.quantity += 1;
+        assert!(!verify_receipt_line(&root, &proof));
+        
+        let mut proof = receipt_line_proof(&items, 2).unwrap();
+        proof.leaf_index = 3;
+        assert!(!verify_receipt_line(&root, &proof));
+        
+        let proof = receipt_line_proof(&items[..4], 2).unwrap();
+        assert!(!verify_receipt_line(&root, &proof));
+        
+        // Un ticket d'une seule ligne n'a pas de nœud frère
+        let single = receipt_line_proof(&items[..1], 0).unwrap();
+        assert!(single.siblings.is_empty());
+        assert!(verify_receipt_line(&receipt_root(&items[..1]), &single));
+    }
 }
@@ -1,41 +1,469 @@
-            relationship.factoring_conditions = conditions;
-            
-            // Enregistrer la relation mise à jour
-            <CommercialRelationships<T>>::insert((merchant.clone(), supplier.clone()), relationship);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::FactoringConditionsUpdated(
-                merchant,
-                supplier,
-                immediate_payment_percent,
-                remaining_payment_delay,
-                interest_rate
-            ));
+// etika-factoring-system/src/lib.rs
+//
+// Ce module implémente le système d'affacturage innovant de l'écosystème Étika:
+// - Enregistrement des relations commerciales entre commerçants et fournisseurs
+// - Paiement instantané des fournisseurs selon le principe "c'est vendu c'est payé"
+// - Gestion des conditions d'affacturage et des flux financiers
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult,
+    ensure, traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, ReservableCurrency, WithdrawReasons},
+    transactional, Parameter, weights::Weight,
+};
+use frame_system::{self as system, ensure_signed};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Member, Zero, SaturatedConversion},
+    DispatchError, Perbill, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+// Import des structures de données définies dans etika-data-structure
+use etika_data_structure::{
+    AccountId, Balance, Moment, CommercialRelationship, FactoringConditions, RelationshipStatus,
+    PoPTransaction, FactoringSystem, ActorType, ActorProfile, sweep_queue,
+};
+
+/// Type monétaire utilisé pour le module
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Configuration du module factoring system
+pub trait Config: frame_system::Config {
+    /// Type d'événement
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+    
+    /// Type de monnaie utilisé
+    type Currency: ReservableCurrency<Self::AccountId>;
+    
+    /// Compte pour les fonds de liquidité d'affacturage
+    type FactoringLiquidityAccount: Get<Self::AccountId>;
+    
+    /// Taux d'intérêt maximum pour l'affacturage (en centièmes de pourcentage)
+    type MaxInterestRate: Get<u32>;
+    
+    /// Pourcentage minimum du paiement immédiat (en pourcentage)
+    type MinImmediatePaymentPercent: Get<u8>;
+    
+    /// Délai maximum pour le reste du paiement (en blocs)
+    type MaxPaymentDelay: Get<Self::BlockNumber>;
+    
+    /// Montant minimum pour les transactions d'affacturage
+    type MinFactoringAmount: Get<BalanceOf<Self>>;
+    
+    /// Période de suspension automatique en cas de défaut de paiement (en blocs)
+    type DefaultSuspensionPeriod: Get<Self::BlockNumber>;
+    
+    /// Nombre maximum de paiements restants traités par bloc
+    type MaxPaymentsPerBlock: Get<u32>;
+    
+    /// Origine autorisée à déclencher un paiement d'affacturage hors du flux PoP
+    type FactoringOrigin: EnsureOrigin<Self::Origin>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Config> as EtikaFactoringSystem {
+        /// Relations commerciales entre commerçants et fournisseurs
+        CommercialRelationships get(fn commercial_relationships): 
+            map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => CommercialRelationship;
+        
+        /// Liquidité disponible dans le système d'affacturage
+        FactoringLiquidity get(fn factoring_liquidity): BalanceOf<T>;
+        
+        /// Paiements d'affacturage en attente (montant restant à payer après le paiement immédiat)
+        PendingPayments get(fn pending_payments): 
+            map hasher(blake2_128_concat) (T::AccountId, T::AccountId, [u8; 32]) => BalanceOf<T>;
+        
+        /// Bloc auquel le paiement restant est dû
+        PaymentDueBlock get(fn payment_due_block): 
+            map hasher(blake2_128_concat) (T::AccountId, T::AccountId, [u8; 32]) => T::BlockNumber;
+        
+        /// Paiements restants par bloc d'échéance
+        PaymentQueue get(fn payment_queue): 
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(T::AccountId, T::AccountId, [u8; 32])>;
+        
+        /// Plus ancien bloc d'échéance dont la file n'a pas été entièrement traitée
+        PaymentCursor get(fn payment_cursor): Option<T::BlockNumber>;
+        
+        /// Paiements d'affacturage déjà traités par transaction PoP (protection contre les doublons)
+        ProcessedPayments get(fn processed_payments): 
+            map hasher(blake2_128_concat) (T::AccountId, T::AccountId, [u8; 32]) => bool;
+        
+        /// Montant total d'affacturage traité
+        TotalFactoringProcessed get(fn total_factoring_processed): BalanceOf<T>;
+        
+        /// Montant total des intérêts générés
+        TotalInterestGenerated get(fn total_interest_generated): BalanceOf<T>;
+        
+        /// Nombre total de relations commerciales
+        TotalCommercialRelationships get(fn total_commercial_relationships): u32;
+        
+        /// Nombre total de paiements d'affacturage effectués
+        TotalFactoringPayments get(fn total_factoring_payments): u64;
+        
+        /// Relations commerciales par commerçant
+        MerchantRelationships get(fn merchant_relationships): 
+            map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+        
+        /// Relations commerciales par fournisseur
+        SupplierRelationships get(fn supplier_relationships): 
+            map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+        
+        /// Historique des paiements d'affacturage par relation commerciale
+        PaymentHistory get(fn payment_history): 
+            map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => Vec<(BalanceOf<T>, T::BlockNumber)>;
+        
+        /// Mapping des types d'acteur par compte
+        ActorTypes get(fn actor_types): map hasher(blake2_128_concat) T::AccountId => ActorType;
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        AccountId = <T as frame_system::Config>::AccountId,
+        Balance = BalanceOf<T>,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
+    {
+        /// Nouvelle relation commerciale enregistrée
+        /// [commerçant, fournisseur, catégorie]
+        CommercialRelationshipRegistered(AccountId, AccountId, Vec<u8>),
+        
+        /// Conditions d'affacturage mises à jour
+        /// [commerçant, fournisseur, pourcentage immédiat, délai, taux d'intérêt]
+        FactoringConditionsUpdated(AccountId, AccountId, u8, u64, u32),
+        
+        /// État de la relation commerciale modifié
+        /// [commerçant, fournisseur, nouvel état]
+        RelationshipStatusUpdated(AccountId, AccountId, RelationshipStatus),
+        
+        /// Paiement d'affacturage immédiat effectué
+        /// [commerçant, fournisseur, transaction_id, montant, montant total]
+        ImmediatePaymentProcessed(AccountId, AccountId, [u8; 32], Balance, Balance),
+        
+        /// Paiement d'affacturage restant planifié
+        /// [commerçant, fournisseur, transaction_id, montant, bloc d'échéance]
+        RemainingPaymentScheduled(AccountId, AccountId, [u8; 32], Balance, BlockNumber),
+        
+        /// Paiement d'affacturage restant effectué
+        /// [commerçant, fournisseur, transaction_id, montant, intérêts]
+        RemainingPaymentProcessed(AccountId, AccountId, [u8; 32], Balance, Balance),
+        
+        /// Paiement d'affacturage restant reporté (relation suspendue ou liquidité insuffisante)
+        /// [commerçant, fournisseur, transaction_id, montant, nouveau bloc d'échéance]
+        RemainingPaymentDeferred(AccountId, AccountId, [u8; 32], Balance, BlockNumber),
+        
+        /// Intérêts d'affacturage prélevés auprès du commerçant
+        /// [commerçant, fournisseur, transaction_id, intérêts]
+        InterestCollected(AccountId, AccountId, [u8; 32], Balance),
+        
+        /// Part d'un fournisseur non affacturée car la relation n'est ni active ni suspendue
+        /// [commerçant, fournisseur, transaction_id, état de la relation]
+        FactoringPaymentSkipped(AccountId, AccountId, [u8; 32], RelationshipStatus),
+        
+        /// Défaut de paiement détecté
+        /// [commerçant, fournisseur, transaction_id, montant]
+        PaymentDefault(AccountId, AccountId, [u8; 32], Balance),
+        
+        /// Liquidité ajoutée au système d'affacturage
+        /// [source, montant]
+        LiquidityAdded(AccountId, Balance),
+        
+        /// Liquidité retirée du système d'affacturage
+        /// [destination, montant]
+        LiquidityWithdrawn(AccountId, Balance),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Relation commerciale déjà existante
+        RelationshipAlreadyExists,
+        
+        /// Relation commerciale non trouvée
+        RelationshipNotFound,
+        
+        /// Statut incompatible pour l'opération
+        IncompatibleStatus,
+        
+        /// Taux d'intérêt trop élevé
+        InterestRateTooHigh,
+        
+        /// Pourcentage de paiement immédiat trop bas
+        ImmediatePaymentPercentTooLow,
+        
+        /// Délai de paiement trop long
+        PaymentDelayTooLong,
+        
+        /// Montant d'affacturage trop faible
+        FactoringAmountTooSmall,
+        
+        /// Liquidité insuffisante pour l'opération
+        InsufficientLiquidity,
+        
+        /// Transaction PoP non valide
+        InvalidPopTransaction,
+        
+        /// Accès non autorisé
+        Unauthorized,
+        
+        /// Type d'acteur incompatible
+        IncompatibleActorType,
+        
+        /// Le commerçant et le fournisseur doivent être différents
+        SameMerchantAndSupplier,
+        
+        /// Paiement déjà traité
+        PaymentAlreadyProcessed,
+        
+        /// Tentative de modifier une relation en suspens
+        ModifyingPendingRelationship,
+        
+        /// Opération limitée à l'administrateur
+        AdminRequired,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+        /// Initialisation des erreurs
+        type Error = Error<T>;
+        
+        /// Émission des événements
+        fn deposit_event() = default;
+        
+        /// Traitement des paiements d'affacturage en attente au changement de bloc
+        fn on_initialize(n: T::BlockNumber) -> frame_support::weights::Weight {
+            // Traiter les paiements arrivés à échéance
+            let (buckets, processed) = Self::process_due_payments(n);
             
-            Ok(())
+            // Curseur et compartiments, puis relation, paiement, échéance et liquidité par paiement
+            T::DbWeight::get().reads_writes(
+                (1 + buckets as Weight).saturating_add(4 * processed as Weight),
+                (1 + buckets as Weight).saturating_add(4 * processed as Weight),
+            )
         }
         
-        /// Suspendre une relation commerciale
+        /// Cohérence de la configuration
+        ///
+        /// La file des paiements doit progresser d'au moins un élément par bloc.
+        fn integrity_test() {
+            assert!(T::MaxPaymentsPerBlock::get() > 0, "MaxPaymentsPerBlock doit être non nul");
+        }
+        
+        /// Enregistrer une nouvelle relation commerciale
         #[weight = 10_000]
-        pub fn suspend_relationship(
+        pub fn register_relationship(
             origin,
             merchant: T::AccountId,
             supplier: T::AccountId,
+            category: Vec<u8>,
+            immediate_payment_percent: u8,
+            remaining_payment_delay: u64,
+            interest_rate: u32,
         ) -> DispatchResult {
-            let suspender = ensure_signed(origin)?;
+            let initiator = ensure_signed(origin)?;
             
             // Vérifier que l'initiateur est soit le commerçant, soit le fournisseur
-            ensure!(suspender == merchant || suspender == supplier, Error::<T>::Unauthorized);
+            ensure!(initiator == merchant || initiator == supplier, Error::<T>::Unauthorized);
             
-            // Vérifier que la relation existe
+            // Vérifier que le commerçant et le fournisseur sont différents
+            ensure!(merchant != supplier, Error::<T>::SameMerchantAndSupplier);
+            
+            // Vérifier que le commerçant et le fournisseur ont les bons types
+            ensure!(<ActorTypes<T>>::get(&merchant) == ActorType::Merchant, Error::<T>::IncompatibleActorType);
+            ensure!(<ActorTypes<T>>::get(&supplier) == ActorType::Supplier, Error::<T>::IncompatibleActorType);
+            
+            // Vérifier que la relation n'existe pas déjà
             ensure!(
-                <CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())),
-                Error::<T>::RelationshipNotFound
+                !<CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())),
+                Error::<T>::RelationshipAlreadyExists
             );
             
-            // Récupérer la relation
-            let mut relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
-            
+            // Vérifier les conditions d'affacturage
+            ensure!(
+                immediate_payment_percent >= T::MinImmediatePaymentPercent::get(),
+                Error::<T>::ImmediatePaymentPercentTooLow
+            );
+            
+            ensure!(interest_rate <= T::MaxInterestRate::get(), Error::<T>::InterestRateTooHigh);
+            
+            ensure!(
+                remaining_payment_delay.saturated_into::<T::BlockNumber>() <= T::MaxPaymentDelay::get(),
+                Error::<T>::PaymentDelayTooLong
+            );
+            
+            let conditions = FactoringConditions {
+                immediate_payment_percent,
+                remaining_payment_delay,
+                interest_rate,
+            };
+            
+            // Créer la relation commerciale
+            let relationship = CommercialRelationship {
+                merchant: merchant.clone(),
+                supplier: supplier.clone(),
+                category: category.clone(),
+                factoring_conditions: conditions,
+                created_at: Self::get_current_timestamp(),
+                status: RelationshipStatus::Pending, // En attente de confirmation par l'autre partie
+            };
+            
+            // Enregistrer la relation
+            <CommercialRelationships<T>>::insert((merchant.clone(), supplier.clone()), relationship);
+            
+            // Mettre à jour les listes de relations
+            <MerchantRelationships<T>>::mutate(merchant.clone(), |suppliers| {
+                if !suppliers.contains(&supplier) {
+                    suppliers.push(supplier.clone());
+                }
+            });
+            
+            <SupplierRelationships<T>>::mutate(supplier.clone(), |merchants| {
+                if !merchants.contains(&merchant) {
+                    merchants.push(merchant.clone());
+                }
+            });
+            
+            // Mettre à jour le compteur
+            <TotalCommercialRelationships>::mutate(|count| *count += 1);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::CommercialRelationshipRegistered(
+                merchant,
+                supplier,
+                category
+            ));
+            
+            Ok(())
+        }
+        
+        /// Confirmer une relation commerciale en attente
+        #[weight = 10_000]
+        pub fn confirm_relationship(
+            origin,
+            merchant: T::AccountId,
+            supplier: T::AccountId,
+        ) -> DispatchResult {
+            let confirmer = ensure_signed(origin)?;
+            
+            // Vérifier que le confirmateur est soit le commerçant, soit le fournisseur
+            // et qu'il n'est pas celui qui a initié la relation
+            ensure!(confirmer == merchant || confirmer == supplier, Error::<T>::Unauthorized);
+            
+            // Vérifier que la relation existe
+            ensure!(
+                <CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())),
+                Error::<T>::RelationshipNotFound
+            );
+            
+            // Récupérer la relation
+            let mut relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
+            
+            // Vérifier que la relation est en attente
+            ensure!(relationship.status == RelationshipStatus::Pending, Error::<T>::IncompatibleStatus);
+            
+            // Mettre à jour le statut
+            relationship.status = RelationshipStatus::Active;
+            
+            // Enregistrer la relation mise à jour
+            <CommercialRelationships<T>>::insert((merchant.clone(), supplier.clone()), relationship);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::RelationshipStatusUpdated(
+                merchant,
+                supplier,
+                RelationshipStatus::Active
+            ));
+            
+            Ok(())
+        }
+        
+        /// Mettre à jour les conditions d'affacturage
+        #[weight = 10_000]
+        pub fn update_factoring_conditions(
+            origin,
+            merchant: T::AccountId,
+            supplier: T::AccountId,
+            immediate_payment_percent: u8,
+            remaining_payment_delay: u64,
+            interest_rate: u32,
+        ) -> DispatchResult {
+            let updater = ensure_signed(origin)?;
+            
+            // Vérifier que l'initiateur est soit le commerçant, soit le fournisseur
+            ensure!(updater == merchant || updater == supplier, Error::<T>::Unauthorized);
+            
+            // Vérifier que la relation existe
+            ensure!(
+                <CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())),
+                Error::<T>::RelationshipNotFound
+            );
+            
+            // Récupérer la relation
+            let mut relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
+            
+            // Vérifier que la relation est active
+            ensure!(relationship.status == RelationshipStatus::Active, Error::<T>::IncompatibleStatus);
+            
+            // Vérifier les nouvelles conditions
+            ensure!(
+                immediate_payment_percent >= T::MinImmediatePaymentPercent::get(),
+                Error::<T>::ImmediatePaymentPercentTooLow
+            );
+            
+            ensure!(interest_rate <= T::MaxInterestRate::get(), Error::<T>::InterestRateTooHigh);
+            
+            ensure!(
+                remaining_payment_delay.saturated_into::<T::BlockNumber>() <= T::MaxPaymentDelay::get(),
+                Error::<T>::PaymentDelayTooLong
+            );
+            
+            // Mettre à jour les conditions
+            let conditions = FactoringConditions {
+                immediate_payment_percent,
+                remaining_payment_delay,
+                interest_rate,
+            };
+            
+            relationship.factoring_conditions = conditions;
+            
+            // Enregistrer la relation mise à jour
+            <CommercialRelationships<T>>::insert((merchant.clone(), supplier.clone()), relationship);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::FactoringConditionsUpdated(
+                merchant,
+                supplier,
+                immediate_payment_percent,
+                remaining_payment_delay,
+                interest_rate
+            ));
+            
+            Ok(())
+        }
+        
+        /// Suspendre une relation commerciale
+        #[weight = 10_000]
+        pub fn suspend_relationship(
+            origin,
+            merchant: T::AccountId,
+            supplier: T::AccountId,
+        ) -> DispatchResult {
+            let suspender = ensure_signed(origin)?;
+            
+            // Vérifier que l'initiateur est soit le commerçant, soit le fournisseur
+            ensure!(suspender == merchant || suspender == supplier, Error::<T>::Unauthorized);
+            
+            // Vérifier que la relation existe
+            ensure!(
+                <CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())),
+                Error::<T>::RelationshipNotFound
+            );
+            
+            // Récupérer la relation
+            let mut relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
+            
             // Vérifier que la relation est active
             ensure!(relationship.status == RelationshipStatus::Active, Error::<T>::IncompatibleStatus);
             
@@ -133,6 +561,9 @@
         }
         
         /// Traiter un paiement d'affacturage pour une transaction PoP
+        ///
+        /// Les paiements sont normalement déclenchés par la validation des transactions PoP;
+        /// cet appel, réservé à `FactoringOrigin`, permet de rattraper un paiement manqué.
         #[weight = 10_000]
         pub fn process_factoring_payment(
             origin,
@@ -141,13 +572,7 @@
             supplier: T::AccountId,
             amount: BalanceOf<T>,
         ) -> DispatchResult {
-            let _ = ensure_signed(origin)?;
-            
-            // Cette fonction serait normalement appelée automatiquement par le système
-            // en réponse à une transaction PoP validée, mais elle est exposée ici pour les tests
-            
-            // Vérifier que le montant est suffisant
-            ensure!(amount >= T::MinFactoringAmount::get(), Error::<T>::FactoringAmountTooSmall);
+            T::FactoringOrigin::ensure_origin(origin)?;
             
             // Vérifier que la relation existe et est active
             ensure!(
@@ -158,94 +583,20 @@
             let relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
             ensure!(relationship.status == RelationshipStatus::Active, Error::<T>::IncompatibleStatus);
             
-            // Vérifier que ce paiement n'a pas déjà été traité
-            ensure!(
-                !<PendingPayments<T>>::contains_key((merchant.clone(), supplier.clone(), pop_transaction_id)),
-                Error::<T>::PaymentAlreadyProcessed
-            );
-            
-            // Récupérer les conditions d'affacturage
-            let conditions = relationship.factoring_conditions;
-            
-            // Calculer le paiement immédiat et le paiement restant
-            let immediate_amount = Perbill::from_percent(conditions.immediate_payment_percent.into()) * amount;
-            let remaining_amount = amount.saturating_sub(immediate_amount);
-            
-            // Vérifier la liquidité disponible
+            // Vérifier la liquidité disponible pour le paiement immédiat
+            let immediate_amount = Self::immediate_share(&relationship.factoring_conditions, amount);
             ensure!(
                 <FactoringLiquidity<T>>::get() >= immediate_amount,
                 Error::<T>::InsufficientLiquidity
             );
             
-            // Traiter le paiement immédiat
-            if immediate_amount > Zero::zero() {
-                // Transférer les fonds au fournisseur
-                T::Currency::transfer(
-                    &T::FactoringLiquidityAccount::get(),
-                    &supplier,
-                    immediate_amount,
-                    ExistenceRequirement::KeepAlive
-                )?;
-                
-                // Mettre à jour la liquidité
-                <FactoringLiquidity<T>>::mutate(|liquidity| {
-                    *liquidity = liquidity.saturating_sub(immediate_amount);
-                });
-                
-                // Émettre un événement
-                Self::deposit_event(RawEvent::ImmediatePaymentProcessed(
-                    merchant.clone(),
-                    supplier.clone(),
-                    pop_transaction_id,
-                    immediate_amount,
-                    amount
-                ));
-            }
-            
-            // Planifier le paiement restant
-            if remaining_amount > Zero::zero() {
-                // Calculer le bloc d'échéance
-                let current_block = <frame_system::Module<T>>::block_number();
-                let payment_due_block = current_block.saturating_add(
-                    conditions.remaining_payment_delay.saturated_into::<T::BlockNumber>()
-                );
-                
-                // Enregistrer le paiement en attente
-                <PendingPayments<T>>::insert(
-                    (merchant.clone(), supplier.clone(), pop_transaction_id),
-                    remaining_amount
-                );
-                
-                <PaymentDueBlock<T>>::insert(
-                    (merchant.clone(), supplier.clone(), pop_transaction_id),
-                    payment_due_block
-                );
-                
-                // Émettre un événement
-                Self::deposit_event(RawEvent::RemainingPaymentScheduled(
-                    merchant,
-                    supplier,
-                    pop_transaction_id,
-                    remaining_amount,
-                    payment_due_block
-                ));
-            }
-            
-            // Mettre à jour les statistiques
-            <TotalFactoringPayments>::mutate(|count| *count += 1);
-            <TotalFactoringProcessed>::mutate(|total| {
-                *total = total.saturating_add(immediate_amount);
-            });
-            
-            // Mettre à jour l'historique des paiements
-            <PaymentHistory<T>>::mutate((merchant, supplier), |history| {
-                if history.len() >= 20 {
-                    history.remove(0);
-                }
-                history.push((amount, <frame_system::Module<T>>::block_number()));
-            });
-            
-            Ok(())
+            Self::do_process_factoring_payment(
+                pop_transaction_id,
+                merchant,
+                supplier,
+                amount,
+                relationship.factoring_conditions,
+            )
         }
         
         /// Ajouter de la liquidité au système d'affacturage
@@ -325,112 +676,413 @@ impl<T: Config> Module<T> {
         (now / 1000) as Moment
     }
     
-    /// Traiter les paiements arrivés à échéance
-    fn process_due_payments(current_block: T::BlockNumber) {
-        for ((merchant, supplier, transaction_id), due_block) in <PaymentDueBlock<T>>::iter() {
-            if current_block >= due_block {
-                // Le paiement est dû, le traiter
-                if <PendingPayments<T>>::contains_key((merchant.clone(), supplier.clone(), transaction_id)) {
-                    let remaining_amount = <PendingPayments<T>>::take((merchant.clone(), supplier.clone(), transaction_id));
-                    
-                    // Récupérer les conditions d'affacturage
-                    if <CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())) {
-                        let relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
-                        
-                        // Calculer les intérêts
-                        let interest_rate = relationship.factoring_conditions.interest_rate;
-                        let interest_amount = Perbill::from_parts(interest_rate as u32 * 10_000_000) * remaining_amount;
-                        
-                        // Verser les fonds au fournisseur (si liquidité suffisante)
-                        let liquidity = <FactoringLiquidity<T>>::get();
-                        
-                        if liquidity >= remaining_amount {
-                            // Transférer les fonds au fournisseur
-                            let _ = T::Currency::transfer(
-                                &T::FactoringLiquidityAccount::get(),
-                                &supplier,
-                                remaining_amount,
-                                ExistenceRequirement::KeepAlive
-                            );
-                            
-                            // Mettre à jour la liquidité
-                            <FactoringLiquidity<T>>::mutate(|liq| {
-                                *liq = liq.saturating_sub(remaining_amount);
-                            });
-                            
-                            // Mettre à jour les statistiques
-                            <TotalFactoringProcessed>::mutate(|total| {
-                                *total = total.saturating_add(remaining_amount);
-                            });
-                            
-                            <TotalInterestGenerated>::mutate(|total| {
-                                *total = total.saturating_add(interest_amount);
-                            });
-                            
-                            // Émettre un événement
-                            Self::deposit_event(RawEvent::RemainingPaymentProcessed(
-                                merchant.clone(),
-                                supplier.clone(),
-                                transaction_id,
-                                remaining_amount,
-                                interest_amount
-                            ));
-                        } else {
-                            // Liquidité insuffisante, suspendre la relation
-                            if relationship.status == RelationshipStatus::Active {
-                                let mut updated_relationship = relationship;
-                                updated_relationship.status = RelationshipStatus::Suspended;
-                                
-                                <CommercialRelationships<T>>::insert(
-                                    (merchant.clone(), supplier.clone()),
-                                    updated_relationship
-                                );
-                                
-                                // Émettre un événement de défaut de paiement
-                                Self::deposit_event(RawEvent::PaymentDefault(
-                                    merchant.clone(),
-                                    supplier.clone(),
-                                    transaction_id,
-                                    remaining_amount
-                                ));
-                                
-                                // Émettre un événement de changement de statut
-                                Self::deposit_event(RawEvent::RelationshipStatusUpdated(
-                                    merchant,
-                                    supplier,
-                                    RelationshipStatus::Suspended
-                                ));
-                            }
-                        }
-                    }
-                }
-                
-                // Nettoyer les données
-                <PaymentDueBlock<T>>::remove((merchant, supplier, transaction_id));
-            }
-        }
+    /// Part du montant versée immédiatement au fournisseur
+    fn immediate_share(conditions: &FactoringConditions, amount: BalanceOf<T>) -> BalanceOf<T> {
+        Perbill::from_percent(conditions.immediate_payment_percent.into()) * amount
     }
-}
 
-/// Implémentation du trait FactoringSystem pour le module factoring system
-impl<T: Config> FactoringSystem for Module<T> {
-    fn register_relationship(
-        merchant: &AccountId,
-        supplier: &AccountId,
+    /// Calculer les intérêts d'affacturage (taux en centièmes de pourcentage)
+    fn calculate_interest(interest_rate: u32, amount: BalanceOf<T>) -> BalanceOf<T> {
+        Perbill::from_parts(interest_rate.saturating_mul(100_000)) * amount
+    }
+
+    /// Effectuer le paiement immédiat et planifier le paiement restant pour un fournisseur
+    ///
+    /// La relation doit avoir été vérifiée comme active par l'appelant.
+    fn do_process_factoring_payment(
+        pop_transaction_id: [u8; 32],
+        merchant: T::AccountId,
+        supplier: T::AccountId,
+        amount: BalanceOf<T>,
         conditions: FactoringConditions,
-    ) -> Result<(), &'static str> {
-        // Convertir les AccountId génériques en AccountId spécifiques au runtime
-        let merchant_id = merchant.clone().try_into().map_err(|_| "Invalid merchant ID")?;
-        let supplier_id = supplier.clone().try_into().map_err(|_| "Invalid supplier ID")?;
-        
-        // Vérifier que la relation n'existe pas déjà
-        if <CommercialRelationships<T>>::contains_key((merchant_id.clone(), supplier_id.clone())) {
-            return Err("Relationship already exists");
-        }
-        
-        // Vérifier les conditions d'affacturage
-        if conditions.immediate_payment_percent < T::MinImmediatePaymentPercent::get() {
-            return Err("Immediate payment percentage too low");
+    ) -> DispatchResult {
+        // Vérifier que le montant est suffisant
+        ensure!(amount >= T::MinFactoringAmount::get(), Error::<T>::FactoringAmountTooSmall);
+
+        // Vérifier que ce paiement n'a pas déjà été traité
+        let payment_key = (merchant.clone(), supplier.clone(), pop_transaction_id);
+        ensure!(
+            !<ProcessedPayments<T>>::get(&payment_key),
+            Error::<T>::PaymentAlreadyProcessed
+        );
+
+        // Calculer le paiement immédiat et le paiement restant
+        let immediate_amount = Self::immediate_share(&conditions, amount);
+        let remaining_amount = amount.saturating_sub(immediate_amount);
+
+        // Vérifier la liquidité disponible
+        ensure!(
+            <FactoringLiquidity<T>>::get() >= immediate_amount,
+            Error::<T>::InsufficientLiquidity
+        );
+
+        // Traiter le paiement immédiat
+        if immediate_amount > Zero::zero() {
+            // Transférer les fonds au fournisseur
+            T::Currency::transfer(
+                &T::FactoringLiquidityAccount::get(),
+                &supplier,
+                immediate_amount,
+                ExistenceRequirement::KeepAlive
+            )?;
+
+            // Mettre à jour la liquidité
+            <FactoringLiquidity<T>>::mutate(|liquidity| {
+                *liquidity = liquidity.saturating_sub(immediate_amount);
+            });
+
+            // Émettre un événement
+            Self::deposit_event(RawEvent::ImmediatePaymentProcessed(
+                merchant.clone(),
+                supplier.clone(),
+                pop_transaction_id,
+                immediate_amount,
+                amount
+            ));
+        }
+
+        // Planifier le paiement restant
+        if remaining_amount > Zero::zero() {
+            // Calculer le bloc d'échéance
+            let current_block = <frame_system::Module<T>>::block_number();
+            let payment_due_block = current_block.saturating_add(
+                conditions.remaining_payment_delay.saturated_into::<T::BlockNumber>()
+            );
+
+            // Enregistrer le paiement en attente
+            Self::schedule_payment(&payment_key, remaining_amount, payment_due_block);
+
+            // Émettre un événement
+            Self::deposit_event(RawEvent::RemainingPaymentScheduled(
+                merchant.clone(),
+                supplier.clone(),
+                pop_transaction_id,
+                remaining_amount,
+                payment_due_block
+            ));
+        }
+
+        <ProcessedPayments<T>>::insert(&payment_key, true);
+
+        // Mettre à jour les statistiques
+        <TotalFactoringPayments>::mutate(|count| *count += 1);
+        <TotalFactoringProcessed<T>>::mutate(|total| {
+            *total = total.saturating_add(immediate_amount);
+        });
+
+        // Mettre à jour l'historique des paiements
+        <PaymentHistory<T>>::mutate((merchant, supplier), |history| {
+            if history.len() >= 20 {
+                history.remove(0);
+            }
+            history.push((amount, <frame_system::Module<T>>::block_number()));
+        });
+
+        Ok(())
+    }
+
+    /// Traiter les paiements arrivés à échéance
+    ///
+    /// Les paiements d'une relation suspendue sont reportés de `DefaultSuspensionPeriod` blocs;
+    /// ceux d'une relation terminée sont soldés normalement. Au plus `MaxPaymentsPerBlock`
+    /// compartiments sont lus et `MaxPaymentsPerBlock` paiements traités; les paiements non
+    /// traités restent dans leur compartiment, d'où le parcours reprend au bloc suivant.
+    /// Retourne le nombre de compartiments lus et de paiements traités.
+    fn process_due_payments(current_block: T::BlockNumber) -> (u32, u32) {
+        sweep_queue::<PaymentQueue<T>, PaymentCursor<T>, _, _>(
+            current_block,
+            T::MaxPaymentsPerBlock::get(),
+            |payment_key, block| {
+                // Paiement soldé ou reporté depuis son inscription dans ce compartiment
+                if <PaymentDueBlock<T>>::contains_key(&payment_key)
+                    && <PaymentDueBlock<T>>::get(&payment_key) == block
+                {
+                    Self::process_due_payment(payment_key, current_block);
+                }
+            },
+        )
+    }
+
+    /// Payer les fournisseurs d'une transaction PoP
+    ///
+    /// Le montant standard est réparti à parts égales entre tous les fournisseurs de la transaction,
+    /// le reste de la division revenant au dernier. La part d'un fournisseur dont la relation est
+    /// suspendue lui reste due et est reportée de `DefaultSuspensionPeriod` blocs; celle d'un
+    /// fournisseur dont la relation n'est ni active ni suspendue n'est pas affacturée. L'ensemble
+    /// des paiements est annulé si l'un d'eux échoue.
+    #[transactional]
+    fn do_process_pop_payment(
+        pop_transaction_id: [u8; 32],
+        merchant: T::AccountId,
+        suppliers: Vec<T::AccountId>,
+        standard_amount: Balance,
+    ) -> Result<(), &'static str> {
+        // Vérifier que la transaction implique un fournisseur
+        if suppliers.is_empty() {
+            return Err("No supplier in transaction");
+        }
+
+        let supplier_count = suppliers.len() as Balance;
+        let share = standard_amount / supplier_count;
+        let last_share = standard_amount.saturating_sub(share * (supplier_count - 1));
+
+        // Première passe: vérifier toutes les relations avant tout transfert
+        let mut legs = Vec::new();
+        let mut required_liquidity: BalanceOf<T> = Zero::zero();
+
+        for (index, supplier) in suppliers.into_iter().enumerate() {
+            if !<CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())) {
+                return Err("Relationship not found");
+            }
+
+            let amount: BalanceOf<T> = if index as Balance + 1 == supplier_count {
+                last_share.saturated_into()
+            } else {
+                share.saturated_into()
+            };
+
+            if amount < T::MinFactoringAmount::get() {
+                return Err("Amount too small");
+            }
+
+            if <ProcessedPayments<T>>::get((merchant.clone(), supplier.clone(), pop_transaction_id)) {
+                return Err("Payment already processed");
+            }
+
+            let relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
+            if relationship.status == RelationshipStatus::Active {
+                required_liquidity = required_liquidity
+                    .saturating_add(Self::immediate_share(&relationship.factoring_conditions, amount));
+            }
+            legs.push((supplier, amount, relationship));
+        }
+
+        if !legs.iter().any(|(_, _, relationship)| {
+            matches!(relationship.status, RelationshipStatus::Active | RelationshipStatus::Suspended)
+        }) {
+            return Err("Relationship not active");
+        }
+
+        // Vérifier la liquidité disponible pour l'ensemble des paiements immédiats
+        if <FactoringLiquidity<T>>::get() < required_liquidity {
+            return Err("Insufficient liquidity");
+        }
+
+        // Seconde passe: effectuer les paiements
+        let current_block = <frame_system::Module<T>>::block_number();
+
+        for (supplier, amount, relationship) in legs {
+            match relationship.status {
+                RelationshipStatus::Active => {
+                    Self::do_process_factoring_payment(
+                        pop_transaction_id,
+                        merchant.clone(),
+                        supplier,
+                        amount,
+                        relationship.factoring_conditions,
+                    ).map_err(|_| "Factoring payment failed")?;
+                }
+                RelationshipStatus::Suspended => {
+                    // La part est payée à l'échéance reportée, une fois la relation réactivée
+                    let payment_key = (merchant.clone(), supplier, pop_transaction_id);
+                    <ProcessedPayments<T>>::insert(&payment_key, true);
+                    Self::defer_payment(&payment_key, amount, current_block);
+                }
+                status => {
+                    Self::deposit_event(RawEvent::FactoringPaymentSkipped(
+                        merchant.clone(),
+                        supplier,
+                        pop_transaction_id,
+                        status
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Solder, reporter ou nettoyer un paiement restant arrivé à échéance
+    fn process_due_payment(
+        payment_key: (T::AccountId, T::AccountId, [u8; 32]),
+        current_block: T::BlockNumber,
+    ) {
+        let (merchant, supplier, transaction_id) = payment_key.clone();
+        if !<PendingPayments<T>>::contains_key(&payment_key)
+            || !<CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone()))
+        {
+            // Nettoyer les données orphelines
+            <PendingPayments<T>>::remove(&payment_key);
+            <PaymentDueBlock<T>>::remove(&payment_key);
+            return;
+        }
+
+        let remaining_amount = <PendingPayments<T>>::get(&payment_key);
+        let relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
+
+        // Relation suspendue: reporter le paiement
+        if relationship.status == RelationshipStatus::Suspended {
+            Self::defer_payment(&payment_key, remaining_amount, current_block);
+            return;
+        }
+
+        // Liquidité insuffisante: défaut de paiement, suspension et report
+        let liquidity = <FactoringLiquidity<T>>::get();
+        let transferred = liquidity >= remaining_amount && T::Currency::transfer(
+            &T::FactoringLiquidityAccount::get(),
+            &supplier,
+            remaining_amount,
+            ExistenceRequirement::KeepAlive
+        ).is_ok();
+
+        if !transferred {
+            Self::suspend_on_default(&merchant, &supplier, transaction_id, remaining_amount);
+            Self::defer_payment(&payment_key, remaining_amount, current_block);
+            return;
+        }
+
+        // Mettre à jour la liquidité
+        <FactoringLiquidity<T>>::mutate(|liq| {
+            *liq = liq.saturating_sub(remaining_amount);
+        });
+
+        // Mettre à jour les statistiques
+        <TotalFactoringProcessed<T>>::mutate(|total| {
+            *total = total.saturating_add(remaining_amount);
+        });
+
+        // Prélever les intérêts auprès du commerçant au profit de la liquidité
+        let interest_amount = Self::calculate_interest(
+            relationship.factoring_conditions.interest_rate,
+            remaining_amount
+        );
+        let mut collected_interest = Zero::zero();
+
+        if interest_amount > Zero::zero() {
+            let collected = T::Currency::transfer(
+                &merchant,
+                &T::FactoringLiquidityAccount::get(),
+                interest_amount,
+                ExistenceRequirement::KeepAlive
+            );
+
+            if collected.is_ok() {
+                collected_interest = interest_amount;
+
+                <FactoringLiquidity<T>>::mutate(|liq| {
+                    *liq = liq.saturating_add(interest_amount);
+                });
+
+                <TotalInterestGenerated<T>>::mutate(|total| {
+                    *total = total.saturating_add(interest_amount);
+                });
+
+                Self::deposit_event(RawEvent::InterestCollected(
+                    merchant.clone(),
+                    supplier.clone(),
+                    transaction_id,
+                    interest_amount
+                ));
+            } else {
+                Self::suspend_on_default(&merchant, &supplier, transaction_id, interest_amount);
+            }
+        }
+
+        // Émettre un événement
+        Self::deposit_event(RawEvent::RemainingPaymentProcessed(
+            merchant.clone(),
+            supplier.clone(),
+            transaction_id,
+            remaining_amount,
+            collected_interest
+        ));
+
+        // Nettoyer les données
+        <PendingPayments<T>>::remove(&payment_key);
+        <PaymentDueBlock<T>>::remove(&payment_key);
+    }
+
+    /// Inscrire un paiement restant dans la file de son bloc d'échéance
+    ///
+    /// La file du bloc courant ayant déjà été traitée, l'échéance est au plus tôt le bloc suivant.
+    fn schedule_payment(
+        payment_key: &(T::AccountId, T::AccountId, [u8; 32]),
+        amount: BalanceOf<T>,
+        due_block: T::BlockNumber,
+    ) {
+        let due_block = due_block.max(<frame_system::Module<T>>::block_number() + 1u32.into());
+        <PendingPayments<T>>::insert(payment_key, amount);
+        <PaymentDueBlock<T>>::insert(payment_key, due_block);
+        <PaymentQueue<T>>::append(due_block, payment_key);
+    }
+
+    /// Reporter un paiement restant de `DefaultSuspensionPeriod` blocs
+    fn defer_payment(
+        payment_key: &(T::AccountId, T::AccountId, [u8; 32]),
+        remaining_amount: BalanceOf<T>,
+        current_block: T::BlockNumber,
+    ) {
+        let new_due_block = current_block.saturating_add(T::DefaultSuspensionPeriod::get());
+        Self::schedule_payment(payment_key, remaining_amount, new_due_block);
+
+        Self::deposit_event(RawEvent::RemainingPaymentDeferred(
+            payment_key.0.clone(),
+            payment_key.1.clone(),
+            payment_key.2,
+            remaining_amount,
+            new_due_block
+        ));
+    }
+
+    /// Signaler un défaut de paiement et suspendre la relation si elle est active
+    fn suspend_on_default(
+        merchant: &T::AccountId,
+        supplier: &T::AccountId,
+        transaction_id: [u8; 32],
+        amount: BalanceOf<T>,
+    ) {
+        // Émettre un événement de défaut de paiement
+        Self::deposit_event(RawEvent::PaymentDefault(
+            merchant.clone(),
+            supplier.clone(),
+            transaction_id,
+            amount
+        ));
+
+        let mut relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
+        if relationship.status == RelationshipStatus::Active {
+            relationship.status = RelationshipStatus::Suspended;
+            <CommercialRelationships<T>>::insert((merchant.clone(), supplier.clone()), relationship);
+
+            // Émettre un événement de changement de statut
+            Self::deposit_event(RawEvent::RelationshipStatusUpdated(
+                merchant.clone(),
+                supplier.clone(),
+                RelationshipStatus::Suspended
+            ));
+        }
+    }
+}
+
+/// Implémentation du trait FactoringSystem pour le module factoring system
+impl<T: Config> FactoringSystem for Module<T> {
+    fn register_relationship(
+        merchant: &AccountId,
+        supplier: &AccountId,
+        conditions: FactoringConditions,
+    ) -> Result<(), &'static str> {
+        // Convertir les AccountId génériques en AccountId spécifiques au runtime
+        let merchant_id = merchant.clone().try_into().map_err(|_| "Invalid merchant ID")?;
+        let supplier_id = supplier.clone().try_into().map_err(|_| "Invalid supplier ID")?;
+        
+        // Vérifier que la relation n'existe pas déjà
+        if <CommercialRelationships<T>>::contains_key((merchant_id.clone(), supplier_id.clone())) {
+            return Err("Relationship already exists");
+        }
+        
+        // Vérifier les conditions d'affacturage
+        if conditions.immediate_payment_percent < T::MinImmediatePaymentPercent::get() {
+            return Err("Immediate payment percentage too low");
         }
         
         if conditions.interest_rate > T::MaxInterestRate::get() {
@@ -471,95 +1123,20 @@ impl<T: Config> FactoringSystem for Module<T> {
     
     fn process_factoring_payment(pop_transaction: &PoPTransaction) -> Result<(), &'static str> {
         // Extraire les informations de la transaction
-        let merchant_id = pop_transaction.merchant.clone().try_into().map_err(|_| "Invalid merchant ID")?;
-        
-        // Vérifier que la transaction implique un fournisseur
-        if pop_transaction.suppliers.is_empty() {
-            return Err("No supplier in transaction");
-        }
-        
-        let supplier_id = pop_transaction.suppliers[0].clone().try_into().map_err(|_| "Invalid supplier ID")?;
-        
-        // Vérifier que la relation existe et est active
-        if !<CommercialRelationships<T>>::contains_key((merchant_id.clone(), supplier_id.clone())) {
-            return Err("Relationship not found");
-        }
-        
-        let relationship = <CommercialRelationships<T>>::get((merchant_id.clone(), supplier_id.clone()));
-        if relationship.status != RelationshipStatus::Active {
-            return Err("Relationship not active");
-        }
-        
-        // Vérifier que ce paiement n'a pas déjà été traité
-        if <PendingPayments<T>>::contains_key((merchant_id.clone(), supplier_id.clone(), pop_transaction.id)) {
-            return Err("Payment already processed");
-        }
-        
-        // Convertir le montant
-        let amount: BalanceOf<T> = pop_transaction.standard_amount.saturated_into();
-        
-        // Vérifier que le montant est suffisant
-        if amount < T::MinFactoringAmount::get() {
-            return Err("Amount too small");
-        }
-        
-        // Récupérer les conditions d'affacturage
-        let conditions = relationship.factoring_conditions;
-        
-        // Calculer le paiement immédiat et le paiement restant
-        let immediate_amount = Perbill::from_percent(conditions.immediate_payment_percent.into()) * amount;
-        let remaining_amount = amount.saturating_sub(immediate_amount);
-        
-        // Vérifier la liquidité disponible
-        if <FactoringLiquidity<T>>::get() < immediate_amount {
-            return Err("Insufficient liquidity");
-        }
-        
-        // Traiter le paiement immédiat
-        if immediate_amount > Zero::zero() {
-            // Transférer les fonds au fournisseur
-            T::Currency::transfer(
-                &T::FactoringLiquidityAccount::get(),
-                &supplier_id,
-                immediate_amount,
-                ExistenceRequirement::KeepAlive
-            ).map_err(|_| "Transfer failed")?;
-            
-            // Mettre à jour la liquidité
-            <FactoringLiquidity<T>>::mutate(|liquidity| {
-                *liquidity = liquidity.saturating_sub(immediate_amount);
-            });
-        }
-        
-        // Planifier le paiement restant
-        if remaining_amount > Zero::zero() {
-            // Calculer le bloc d'échéance
-            let current_block = <frame_system::Module<T>>::block_number();
-            let payment_due_block = current_block.saturating_add(
-                conditions.remaining_payment_delay.saturated_into::<T::BlockNumber>()
-            );
-            
-            // Enregistrer le paiement en attente
-            <PendingPayments<T>>::insert(
-                (merchant_id.clone(), supplier_id.clone(), pop_transaction.id),
-                remaining_amount
-            );
-            
-            <PaymentDueBlock<T>>::insert(
-                (merchant_id.clone(), supplier_id.clone(), pop_transaction.id),
-                payment_due_block
-            );
-        }
-        
-        // Mettre à jour les statistiques
-        <TotalFactoringPayments>::mutate(|count| *count += 1);
-        <TotalFactoringProcessed>::mutate(|total| {
-            *total = total.saturating_add(immediate_amount);
-        });
-        
-        Ok(())
+        let merchant_id: T::AccountId = pop_transaction.merchant.clone().try_into().map_err(|_| "Invalid merchant ID")?;
+        let supplier_ids = pop_transaction.suppliers
+            .iter()
+            .map(|supplier| supplier.clone().try_into().map_err(|_| "Invalid supplier ID"))
+            .collect::<Result<Vec<T::AccountId>, _>>()?;
+
+        Self::do_process_pop_payment(
+            pop_transaction.id,
+            merchant_id,
+            supplier_ids,
+            pop_transaction.standard_amount,
+        )
     }
-    
+
     fn get_factoring_conditions(merchant: &AccountId, supplier: &AccountId) -> Result<FactoringConditions, &'static str> {
         // Convertir les AccountId génériques en AccountId spécifiques au runtime
         let merchant_id = merchant.clone().try_into().map_err(|_| "Invalid merchant ID")?;
@@ -656,6 +1233,7 @@ mod tests {
         pub const MaxPaymentDelay: u64 = 100; // 100 blocs
         pub const MinFactoringAmount: u64 = 100;
         pub const DefaultSuspensionPeriod: u64 = 50; // 50 blocs
+        pub const MaxPaymentsPerBlock: u32 = 2;
     }
     
     impl Config for Test {
@@ -667,6 +1245,8 @@ mod tests {
         type MaxPaymentDelay = MaxPaymentDelay;
         type MinFactoringAmount = MinFactoringAmount;
         type DefaultSuspensionPeriod = DefaultSuspensionPeriod;
+        type MaxPaymentsPerBlock = MaxPaymentsPerBlock;
+        type FactoringOrigin = frame_system::EnsureRoot<u64>;
     }
     
     // Fonction utilitaire pour créer un environnement de test
@@ -706,6 +1286,45 @@ mod tests {
     
     #[test]
     fn test_register_relationship() {
+        new_test_ext().execute_with(|| {
+            // Enregistrer une relation commerciale
+            assert_ok!(EtikaFactoringSystem::register_relationship(
+                Origin::signed(2),
+                2,
+                3,
+                b"electronics".to_vec(),
+                80,
+                50,
+                500
+            ));
+            
+            // Vérifier que la relation a été créée
+            assert!(<CommercialRelationships<Test>>::contains_key((2, 3)));
+            
+            // Vérifier les détails de la relation
+            let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
+            assert_eq!(relationship.merchant, 2);
+            assert_eq!(relationship.supplier, 3);
+            assert_eq!(relationship.category, b"electronics".to_vec());
+            assert_eq!(relationship.factoring_conditions.immediate_payment_percent, 80);
+            assert_eq!(relationship.factoring_conditions.remaining_payment_delay, 50);
+            assert_eq!(relationship.factoring_conditions.interest_rate, 500);
+            assert_eq!(relationship.status, RelationshipStatus::Pending);
+            
+            // Vérifier les listes de relations
+            let merchant_relationships = EtikaFactoringSystem::merchant_relationships(2);
+            assert_eq!(merchant_relationships, vec![3]);
+            
+            let supplier_relationships = EtikaFactoringSystem::supplier_relationships(3);
+            assert_eq!(supplier_relationships, vec![2]);
+            
+            // Vérifier le compteur
+            assert_eq!(EtikaFactoringSystem::total_commercial_relationships(), 1);
+        });
+    }
+    
+    #[test]
+    fn test_confirm_relationship() {
         new_test_ext().execute_with(|| {
             // Enregistrer une relation commerciale
             assert_ok!(EtikaFactoringSystem::register_relationship(
@@ -789,10 +1408,16 @@ mod tests {
                 3
             ));
             
-            // Traiter un paiement d'affacturage
+            // Seule l'origine autorisée peut déclencher un paiement hors du flux PoP
             let transaction_id = [0; 32];
+            assert_noop!(
+                EtikaFactoringSystem::process_factoring_payment(Origin::signed(2), transaction_id, 2, 3, 1000),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            
+            // Traiter un paiement d'affacturage
             assert_ok!(EtikaFactoringSystem::process_factoring_payment(
-                Origin::signed(2),
+                Origin::root(),
                 transaction_id,
                 2,
                 3,
@@ -815,6 +1440,12 @@ mod tests {
             // Vérifier les statistiques
             assert_eq!(EtikaFactoringSystem::total_factoring_payments(), 1);
             assert_eq!(EtikaFactoringSystem::total_factoring_processed(), 800);
+            
+            // Un même paiement ne peut pas être traité deux fois
+            assert_noop!(
+                EtikaFactoringSystem::process_factoring_payment(Origin::root(), transaction_id, 2, 3, 1000),
+                Error::<Test>::PaymentAlreadyProcessed
+            );
         });
     }
     
@@ -841,7 +1472,7 @@ mod tests {
             // Traiter un paiement d'affacturage
             let transaction_id = [0; 32];
             assert_ok!(EtikaFactoringSystem::process_factoring_payment(
-                Origin::signed(2),
+                Origin::root(),
                 transaction_id,
                 2,
                 3,
@@ -852,11 +1483,11 @@ mod tests {
             let supplier_balance_before = Balances::free_balance(3);
             assert_eq!(supplier_balance_before, 30000 + 800); // 80% de 1000
             
-            // Avancer le temps pour que le paiement restant arrive à échéance
-            System::set_block_number(60); // > 50 blocs
+            // Avancer le temps jusqu'à l'échéance du paiement restant
+            System::set_block_number(50);
             
             // Traiter les paiements arrivés à échéance
-            EtikaFactoringSystem::on_initialize(60);
+            EtikaFactoringSystem::on_initialize(50);
             
             // Vérifier que le paiement restant a été effectué
             let supplier_balance_after = Balances::free_balance(3);
@@ -872,6 +1503,10 @@ mod tests {
             // Les intérêts sont calculés sur le montant restant: 200 * 5% = 10
             let expected_interest = 10;
             assert_eq!(EtikaFactoringSystem::total_interest_generated(), expected_interest);
+            
+            // Les intérêts sont prélevés auprès du commerçant et reviennent à la liquidité
+            assert_eq!(Balances::free_balance(2), 20000 - expected_interest);
+            assert_eq!(EtikaFactoringSystem::factoring_liquidity(), 1000000 - 1000 + expected_interest);
         });
     }
     
@@ -898,7 +1533,7 @@ mod tests {
             // Traiter un paiement d'affacturage
             let transaction_id = [0; 32];
             assert_ok!(EtikaFactoringSystem::process_factoring_payment(
-                Origin::signed(2),
+                Origin::root(),
                 transaction_id,
                 2,
                 3,
@@ -908,20 +1543,27 @@ mod tests {
             // Réduire la liquidité pour simuler un défaut de paiement
             <FactoringLiquidity<Test>>::put(0);
             
-            // Avancer le temps pour que le paiement restant arrive à échéance
-            System::set_block_number(60);
+            // Avancer le temps jusqu'à l'échéance du paiement restant
+            System::set_block_number(50);
             
             // Traiter les paiements arrivés à échéance
-            EtikaFactoringSystem::on_initialize(60);
+            EtikaFactoringSystem::on_initialize(50);
             
             // Vérifier que la relation a été suspendue
             let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
             assert_eq!(relationship.status, RelationshipStatus::Suspended);
+            
+            // Le paiement restant n'est pas perdu mais reporté
+            assert_eq!(EtikaFactoringSystem::pending_payments((2, 3, transaction_id)), 200);
+            assert_eq!(
+                EtikaFactoringSystem::payment_due_block((2, 3, transaction_id)),
+                50 + DefaultSuspensionPeriod::get()
+            );
         });
     }
     
     #[test]
-    fn test_suspend_and_reactivate_relationship() {
+    fn test_suspended_relationship_defers_remaining_payment() {
         new_test_ext().execute_with(|| {
             // Enregistrer et confirmer une relation commerciale
             assert_ok!(EtikaFactoringSystem::register_relationship(
@@ -940,515 +1582,348 @@ mod tests {
                 3
             ));
             
-            // Suspendre la relation
-            assert_ok!(EtikaFactoringSystem::suspend_relationship(
-                Origin::signed(2),
-                2,
-                3
-            ));
-            
-            // Vérifier que la relation a été suspendue
-            let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
-            assert_eq!(relationship.status, RelationshipStatus::Suspended);
-            
-            // Réactiver la relation
-            assert_ok!(EtikaFactoringSystem::reactivate_relationship(
-                Origin::signed(3),
-                2,
-                3
-            ));
-            
-            // Vérifier que la relation a été réactivée
-            let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
-            assert_eq!(relationship.status, RelationshipStatus::Active);
-        });
-    }
-    
-    #[test]
-    fn test_terminate_relationship() {
-        new_test_ext().execute_with(|| {
-            // Enregistrer et confirmer une relation commerciale
-            assert_ok!(EtikaFactoringSystem::register_relationship(
-                Origin::signed(2),
+            let transaction_id = [0; 32];
+            assert_ok!(EtikaFactoringSystem::process_factoring_payment(
+                Origin::root(),
+                transaction_id,
                 2,
                 3,
-                b"electronics".to_vec(),
-                80,
-                50,
-                500
-            ));
-            
-            assert_ok!(EtikaFactoringSystem::confirm_relationship(
-                Origin::signed(3),
-                2,
-                3
+                1000
             ));
             
-            // Mettre fin à la relation
-            assert_ok!(EtikaFactoringSystem::terminate_relationship(
+            // Suspendre la relation avant l'échéance
+            assert_ok!(EtikaFactoringSystem::suspend_relationship(
                 Origin::signed(2),
                 2,
                 3
             ));
             
-            // Vérifier que la relation a été terminée
-            let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
-            assert_eq!(relationship.status, RelationshipStatus::Terminated);
-        });
-    }
-    
-    #[test]
-    fn test_liquidity_management() {
-        new_test_ext().execute_with(|| {
-            // Ajouter de la liquidité
-            assert_ok!(EtikaFactoringSystem::add_liquidity(
-                Origin::signed(1),
-                5000
-            ));
-            
-            // Vérifier la liquidité
-            let liquidity_after_add = EtikaFactoringSystem::factoring_liquidity();
-            assert_eq!(liquidity_after_add, 1000000 + 5000);
-            
-            // Vérifier le solde du compte de liquidité
-            let liquidity_account_balance = Balances::free_balance(999);
-            assert_eq!(liquidity_account_balance, 1000000 + 5000);
-            
-            // Retirer de la liquidité
-            assert_ok!(EtikaFactoringSystem::withdraw_liquidity(
-                Origin::signed(999),
-                2000,
-                1
-            ));
-            
-            // Vérifier la liquidité
-            let liquidity_after_withdraw = EtikaFactoringSystem::factoring_liquidity();
-            assert_eq!(liquidity_after_withdraw, 1000000 + 5000 - 2000);
-            
-            // Vérifier le solde du compte de liquidité
-            let liquidity_account_balance_after = Balances::free_balance(999);
-            assert_eq!(liquidity_account_balance_after, 1000000 + 5000 - 2000);
-            
-            // Vérifier le solde du destinataire
-            let recipient_balance = Balances::free_balance(1);
-            assert_eq!(recipient_balance, 10000 - 5000 + 2000);
-        });
-    }
-}),
-                2,
-                3,
-                b"electronics".to_vec(),
-                80,
-                50,
-                500
-            ));
-            
-            // Vérifier que la relation a été créée
-            assert!(<CommercialRelationships<Test>>::contains_key((2, 3)));
-            
-            // Vérifier les détails de la relation
-            let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
-            assert_eq!(relationship.merchant, 2);
-            assert_eq!(relationship.supplier, 3);
-            assert_eq!(relationship.category, b"electronics".to_vec());
-            assert_eq!(relationship.factoring_conditions.immediate_payment_percent, 80);
-            assert_eq!(relationship.factoring_conditions.remaining_payment_delay, 50);
-            assert_eq!(relationship.factoring_conditions.interest_rate, 500);
-            assert_eq!(relationship.status, RelationshipStatus::Pending);
-            
-            // Vérifier les listes de relations
-            let merchant_relationships = EtikaFactoringSystem::merchant_relationships(2);
-            assert_eq!(merchant_relationships, vec![3]);
-            
-            let supplier_relationships = EtikaFactoringSystem::supplier_relationships(3);
-            assert_eq!(supplier_relationships, vec![2]);
-            
-            // Vérifier le compteur
-            assert_eq!(EtikaFactoringSystem::total_commercial_relationships(), 1);
-        });
-    }
-    
-    #[test]
-    fn test_confirm_relationship() {
-        new_test_ext().execute_with(|| {
-            // Enregistrer une relation commerciale
-            assert_ok!(EtikaFactoringSystem::register_relationship(
-                Origin::signed(2// etika-factoring-system/src/lib.rs
-//
-// Ce module implémente le système d'affacturage innovant de l'écosystème Étika:
-// - Enregistrement des relations commerciales entre commerçants et fournisseurs
-// - Paiement instantané des fournisseurs selon le principe "c'est vendu c'est payé"
-// - Gestion des conditions d'affacturage et des flux financiers
-
-#![cfg_attr(not(feature = "std"), no_std)]
-
-use codec::{Decode, Encode};
-use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult,
-    ensure, traits::{Currency, ExistenceRequirement, Get, ReservableCurrency, WithdrawReasons},
-    Parameter,
-};
-use frame_system::{self as system, ensure_signed};
-use sp_runtime::{
-    traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Member, Zero, SaturatedConversion},
-    DispatchError, Perbill, RuntimeDebug,
-};
-use sp_std::prelude::*;
-
-// Import des structures de données définies dans etika-data-structure
-use etika_data_structure::{
-    AccountId, Balance, Moment, CommercialRelationship, FactoringConditions, RelationshipStatus,
-    PoPTransaction, FactoringSystem, ActorType, ActorProfile,
-};
-
-/// Type monétaire utilisé pour le module
-type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-
-/// Configuration du module factoring system
-pub trait Config: frame_system::Config {
-    /// Type d'événement
-    type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-    
-    /// Type de monnaie utilisé
-    type Currency: ReservableCurrency<Self::AccountId>;
-    
-    /// Compte pour les fonds de liquidité d'affacturage
-    type FactoringLiquidityAccount: Get<Self::AccountId>;
-    
-    /// Taux d'intérêt maximum pour l'affacturage (en centièmes de pourcentage)
-    type MaxInterestRate: Get<u32>;
-    
-    /// Pourcentage minimum du paiement immédiat (en pourcentage)
-    type MinImmediatePaymentPercent: Get<u8>;
-    
-    /// Délai maximum pour le reste du paiement (en blocs)
-    type MaxPaymentDelay: Get<Self::BlockNumber>;
-    
-    /// Montant minimum pour les transactions d'affacturage
-    type MinFactoringAmount: Get<BalanceOf<Self>>;
-    
-    /// Période de suspension automatique en cas de défaut de paiement (en blocs)
-    type DefaultSuspensionPeriod: Get<Self::BlockNumber>;
-}
-
-decl_storage! {
-    trait Store for Module<T: Config> as EtikaFactoringSystem {
-        /// Relations commerciales entre commerçants et fournisseurs
-        CommercialRelationships get(fn commercial_relationships): 
-            map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => CommercialRelationship;
-        
-        /// Liquidité disponible dans le système d'affacturage
-        FactoringLiquidity get(fn factoring_liquidity): BalanceOf<T>;
-        
-        /// Paiements d'affacturage en attente (montant restant à payer après le paiement immédiat)
-        PendingPayments get(fn pending_payments): 
-            map hasher(blake2_128_concat) (T::AccountId, T::AccountId, [u8; 32]) => BalanceOf<T>;
-        
-        /// Bloc auquel le paiement restant est dû
-        PaymentDueBlock get(fn payment_due_block): 
-            map hasher(blake2_128_concat) (T::AccountId, T::AccountId, [u8; 32]) => T::BlockNumber;
-        
-        /// Montant total d'affacturage traité
-        TotalFactoringProcessed get(fn total_factoring_processed): BalanceOf<T>;
-        
-        /// Montant total des intérêts générés
-        TotalInterestGenerated get(fn total_interest_generated): BalanceOf<T>;
-        
-        /// Nombre total de relations commerciales
-        TotalCommercialRelationships get(fn total_commercial_relationships): u32;
-        
-        /// Nombre total de paiements d'affacturage effectués
-        TotalFactoringPayments get(fn total_factoring_payments): u64;
-        
-        /// Relations commerciales par commerçant
-        MerchantRelationships get(fn merchant_relationships): 
-            map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
-        
-        /// Relations commerciales par fournisseur
-        SupplierRelationships get(fn supplier_relationships): 
-            map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
-        
-        /// Historique des paiements d'affacturage par relation commerciale
-        PaymentHistory get(fn payment_history): 
-            map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => Vec<(BalanceOf<T>, T::BlockNumber)>;
-        
-        /// Mapping des types d'acteur par compte
-        ActorTypes get(fn actor_types): map hasher(blake2_128_concat) T::AccountId => ActorType;
-    }
-}
-
-decl_event!(
-    pub enum Event<T> where
-        AccountId = <T as frame_system::Config>::AccountId,
-        Balance = BalanceOf<T>,
-        BlockNumber = <T as frame_system::Config>::BlockNumber,
-    {
-        /// Nouvelle relation commerciale enregistrée
-        /// [commerçant, fournisseur, catégorie]
-        CommercialRelationshipRegistered(AccountId, AccountId, Vec<u8>),
-        
-        /// Conditions d'affacturage mises à jour
-        /// [commerçant, fournisseur, pourcentage immédiat, délai, taux d'intérêt]
-        FactoringConditionsUpdated(AccountId, AccountId, u8, u64, u32),
-        
-        /// État de la relation commerciale modifié
-        /// [commerçant, fournisseur, nouvel état]
-        RelationshipStatusUpdated(AccountId, AccountId, RelationshipStatus),
-        
-        /// Paiement d'affacturage immédiat effectué
-        /// [commerçant, fournisseur, transaction_id, montant, montant total]
-        ImmediatePaymentProcessed(AccountId, AccountId, [u8; 32], Balance, Balance),
-        
-        /// Paiement d'affacturage restant planifié
-        /// [commerçant, fournisseur, transaction_id, montant, bloc d'échéance]
-        RemainingPaymentScheduled(AccountId, AccountId, [u8; 32], Balance, BlockNumber),
-        
-        /// Paiement d'affacturage restant effectué
-        /// [commerçant, fournisseur, transaction_id, montant, intérêts]
-        RemainingPaymentProcessed(AccountId, AccountId, [u8; 32], Balance, Balance),
-        
-        /// Défaut de paiement détecté
-        /// [commerçant, fournisseur, transaction_id, montant]
-        PaymentDefault(AccountId, AccountId, [u8; 32], Balance),
-        
-        /// Liquidité ajoutée au système d'affacturage
-        /// [source, montant]
-        LiquidityAdded(AccountId, Balance),
-        
-        /// Liquidité retirée du système d'affacturage
-        /// [destination, montant]
-        LiquidityWithdrawn(AccountId, Balance),
-    }
-);
-
-decl_error! {
-    pub enum Error for Module<T: Config> {
-        /// Relation commerciale déjà existante
-        RelationshipAlreadyExists,
-        
-        /// Relation commerciale non trouvée
-        RelationshipNotFound,
-        
-        /// Statut incompatible pour l'opération
-        IncompatibleStatus,
-        
-        /// Taux d'intérêt trop élevé
-        InterestRateTooHigh,
-        
-        /// Pourcentage de paiement immédiat trop bas
-        ImmediatePaymentPercentTooLow,
-        
-        /// Délai de paiement trop long
-        PaymentDelayTooLong,
-        
-        /// Montant d'affacturage trop faible
-        FactoringAmountTooSmall,
-        
-        /// Liquidité insuffisante pour l'opération
-        InsufficientLiquidity,
-        
-        /// Transaction PoP non valide
-        InvalidPopTransaction,
-        
-        /// Accès non autorisé
-        Unauthorized,
-        
-        /// Type d'acteur incompatible
-        IncompatibleActorType,
-        
-        /// Le commerçant et le fournisseur doivent être différents
-        SameMerchantAndSupplier,
-        
-        /// Paiement déjà traité
-        PaymentAlreadyProcessed,
-        
-        /// Tentative de modifier une relation en suspens
-        ModifyingPendingRelationship,
-        
-        /// Opération limitée à l'administrateur
-        AdminRequired,
-    }
-}
-
-decl_module! {
-    pub struct Module<T: Config> for enum Call where origin: T::Origin {
-        /// Initialisation des erreurs
-        type Error = Error<T>;
-        
-        /// Émission des événements
-        fn deposit_event() = default;
-        
-        /// Traitement des paiements d'affacturage en attente au changement de bloc
-        fn on_initialize(n: T::BlockNumber) -> frame_support::weights::Weight {
-            // Traiter les paiements arrivés à échéance
-            Self::process_due_payments(n);
-            
-            0
-        }
-        
-        /// Enregistrer une nouvelle relation commerciale
-        #[weight = 10_000]
-        pub fn register_relationship(
-            origin,
-            merchant: T::AccountId,
-            supplier: T::AccountId,
-            category: Vec<u8>,
-            immediate_payment_percent: u8,
-            remaining_payment_delay: u64,
-            interest_rate: u32,
-        ) -> DispatchResult {
-            let initiator = ensure_signed(origin)?;
-            
-            // Vérifier que l'initiateur est soit le commerçant, soit le fournisseur
-            ensure!(initiator == merchant || initiator == supplier, Error::<T>::Unauthorized);
-            
-            // Vérifier que le commerçant et le fournisseur sont différents
-            ensure!(merchant != supplier, Error::<T>::SameMerchantAndSupplier);
-            
-            // Vérifier que le commerçant et le fournisseur ont les bons types
-            ensure!(<ActorTypes<T>>::get(&merchant) == ActorType::Merchant, Error::<T>::IncompatibleActorType);
-            ensure!(<ActorTypes<T>>::get(&supplier) == ActorType::Supplier, Error::<T>::IncompatibleActorType);
-            
-            // Vérifier que la relation n'existe pas déjà
-            ensure!(
-                !<CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())),
-                Error::<T>::RelationshipAlreadyExists
+            // Aucun nouveau paiement n'est accepté pendant la suspension
+            assert_noop!(
+                EtikaFactoringSystem::process_factoring_payment(Origin::root(), [1; 32], 2, 3, 1000),
+                Error::<Test>::IncompatibleStatus
             );
             
-            // Vérifier les conditions d'affacturage
-            ensure!(
-                immediate_payment_percent >= T::MinImmediatePaymentPercent::get(),
-                Error::<T>::ImmediatePaymentPercentTooLow
-            );
+            // À l'échéance, le paiement restant est reporté
+            System::set_block_number(50);
+            EtikaFactoringSystem::on_initialize(50);
             
-            ensure!(interest_rate <= T::MaxInterestRate::get(), Error::<T>::InterestRateTooHigh);
-            
-            let conditions = FactoringConditions {
-                immediate_payment_percent,
-                remaining_payment_delay,
-                interest_rate,
-            };
+            assert_eq!(Balances::free_balance(3), 30000 + 800);
+            assert_eq!(EtikaFactoringSystem::pending_payments((2, 3, transaction_id)), 200);
+            assert_eq!(
+                EtikaFactoringSystem::payment_due_block((2, 3, transaction_id)),
+                50 + DefaultSuspensionPeriod::get()
+            );
             
-            // Créer la relation commerciale
-            let relationship = CommercialRelationship {
-                merchant: merchant.clone(),
-                supplier: supplier.clone(),
-                category: category.clone(),
-                factoring_conditions: conditions,
-                created_at: Self::get_current_timestamp(),
-                status: RelationshipStatus::Pending, // En attente de confirmation par l'autre partie
-            };
+            // Après réactivation, le paiement est effectué à la nouvelle échéance
+            assert_ok!(EtikaFactoringSystem::reactivate_relationship(
+                Origin::signed(3),
+                2,
+                3
+            ));
             
-            // Enregistrer la relation
-            <CommercialRelationships<T>>::insert((merchant.clone(), supplier.clone()), relationship);
+            System::set_block_number(100);
+            EtikaFactoringSystem::on_initialize(100);
             
-            // Mettre à jour les listes de relations
-            <MerchantRelationships<T>>::mutate(merchant.clone(), |suppliers| {
-                if !suppliers.contains(&supplier) {
-                    suppliers.push(supplier.clone());
-                }
-            });
+            assert_eq!(Balances::free_balance(3), 30000 + 1000);
+            assert!(!<PendingPayments<Test>>::contains_key((2, 3, transaction_id)));
+        });
+    }
+    
+    #[test]
+    fn test_terminated_relationship_settles_outstanding_payment() {
+        new_test_ext().execute_with(|| {
+            // Enregistrer et confirmer une relation commerciale
+            assert_ok!(EtikaFactoringSystem::register_relationship(
+                Origin::signed(2),
+                2,
+                3,
+                b"electronics".to_vec(),
+                80,
+                50,
+                500
+            ));
             
-            <SupplierRelationships<T>>::mutate(supplier.clone(), |merchants| {
-                if !merchants.contains(&merchant) {
-                    merchants.push(merchant.clone());
-                }
-            });
+            assert_ok!(EtikaFactoringSystem::confirm_relationship(
+                Origin::signed(3),
+                2,
+                3
+            ));
             
-            // Mettre à jour le compteur
-            <TotalCommercialRelationships>::mutate(|count| *count += 1);
+            let transaction_id = [0; 32];
+            assert_ok!(EtikaFactoringSystem::process_factoring_payment(
+                Origin::root(),
+                transaction_id,
+                2,
+                3,
+                1000
+            ));
             
-            // Émettre un événement
-            Self::deposit_event(RawEvent::CommercialRelationshipRegistered(
-                merchant,
-                supplier,
-                category
+            // Mettre fin à la relation
+            assert_ok!(EtikaFactoringSystem::terminate_relationship(
+                Origin::signed(2),
+                2,
+                3
             ));
             
-            Ok(())
-        }
-        
-        /// Confirmer une relation commerciale en attente
-        #[weight = 10_000]
-        pub fn confirm_relationship(
-            origin,
-            merchant: T::AccountId,
-            supplier: T::AccountId,
-        ) -> DispatchResult {
-            let confirmer = ensure_signed(origin)?;
+            // Aucun nouveau paiement n'est accepté
+            assert_noop!(
+                EtikaFactoringSystem::process_factoring_payment(Origin::root(), [1; 32], 2, 3, 1000),
+                Error::<Test>::IncompatibleStatus
+            );
             
-            // Vérifier que le confirmateur est soit le commerçant, soit le fournisseur
-            // et qu'il n'est pas celui qui a initié la relation
-            ensure!(confirmer == merchant || confirmer == supplier, Error::<T>::Unauthorized);
+            // Le paiement restant déjà dû au fournisseur est soldé à l'échéance
+            System::set_block_number(50);
+            EtikaFactoringSystem::on_initialize(50);
             
-            // Vérifier que la relation existe
-            ensure!(
-                <CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())),
-                Error::<T>::RelationshipNotFound
+            assert_eq!(Balances::free_balance(3), 30000 + 1000);
+            assert!(!<PendingPayments<Test>>::contains_key((2, 3, transaction_id)));
+        });
+    }
+    
+    #[test]
+    fn test_pop_payment_holds_suspended_supplier_share() {
+        new_test_ext().execute_with(|| {
+            for supplier in [3, 5].iter() {
+                assert_ok!(EtikaFactoringSystem::register_relationship(
+                    Origin::signed(2),
+                    2,
+                    *supplier,
+                    b"electronics".to_vec(),
+                    80,
+                    50,
+                    500
+                ));
+                assert_ok!(EtikaFactoringSystem::confirm_relationship(
+                    Origin::signed(*supplier),
+                    2,
+                    *supplier
+                ));
+            }
+            assert_ok!(EtikaFactoringSystem::suspend_relationship(Origin::signed(2), 2, 5));
+            
+            // Chaque fournisseur a droit à la moitié du montant, quel que soit l'état de sa relation
+            let transaction_id = [7; 32];
+            assert_ok!(EtikaFactoringSystem::do_process_pop_payment(transaction_id, 2, vec![3, 5], 1000));
+            assert_eq!(Balances::free_balance(3), 30000 + 400);
+            assert_eq!(EtikaFactoringSystem::pending_payments((2, 3, transaction_id)), 100);
+            
+            // La part du fournisseur suspendu n'est pas redistribuée mais reportée
+            assert_eq!(Balances::free_balance(5), 50000);
+            assert_eq!(EtikaFactoringSystem::pending_payments((2, 5, transaction_id)), 500);
+            assert_eq!(
+                EtikaFactoringSystem::payment_due_block((2, 5, transaction_id)),
+                DefaultSuspensionPeriod::get()
+            );
+            assert_eq!(EtikaFactoringSystem::factoring_liquidity(), 1000000 - 400);
+            assert_eq!(
+                EtikaFactoringSystem::do_process_pop_payment(transaction_id, 2, vec![3, 5], 1000),
+                Err("Payment already processed")
             );
             
-            // Récupérer la relation
-            let mut relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
+            // Une fois la relation réactivée, la part est payée à l'échéance
+            assert_ok!(EtikaFactoringSystem::reactivate_relationship(Origin::signed(5), 2, 5));
+            System::set_block_number(50);
+            EtikaFactoringSystem::on_initialize(50);
+            assert_eq!(Balances::free_balance(3), 30000 + 500);
+            assert_eq!(Balances::free_balance(5), 50000 + 500);
+            assert!(!<PendingPayments<Test>>::contains_key((2, 5, transaction_id)));
+        });
+    }
+    
+    #[test]
+    fn test_pop_payment_skips_terminated_supplier() {
+        new_test_ext().execute_with(|| {
+            for supplier in [3, 5].iter() {
+                assert_ok!(EtikaFactoringSystem::register_relationship(
+                    Origin::signed(2),
+                    2,
+                    *supplier,
+                    b"electronics".to_vec(),
+                    80,
+                    50,
+                    500
+                ));
+                assert_ok!(EtikaFactoringSystem::confirm_relationship(
+                    Origin::signed(*supplier),
+                    2,
+                    *supplier
+                ));
+            }
+            assert_ok!(EtikaFactoringSystem::terminate_relationship(Origin::signed(2), 2, 5));
+            
+            // Le fournisseur actif ne reçoit que sa propre part
+            let transaction_id = [7; 32];
+            assert_ok!(EtikaFactoringSystem::do_process_pop_payment(transaction_id, 2, vec![3, 5], 1000));
+            assert_eq!(Balances::free_balance(3), 30000 + 400);
+            assert_eq!(EtikaFactoringSystem::pending_payments((2, 3, transaction_id)), 100);
+            assert_eq!(Balances::free_balance(5), 50000);
+            assert!(!<PendingPayments<Test>>::contains_key((2, 5, transaction_id)));
+            
+            // Sans relation active ni suspendue, rien n'est payé
+            assert_ok!(EtikaFactoringSystem::terminate_relationship(Origin::signed(2), 2, 3));
+            assert_eq!(
+                EtikaFactoringSystem::do_process_pop_payment([8; 32], 2, vec![3, 5], 1000),
+                Err("Relationship not active")
+            );
+        });
+    }
+    
+    #[test]
+    fn test_due_payments_are_bounded_per_block() {
+        new_test_ext().execute_with(|| {
+            for (merchant, supplier) in [(2, 3), (2, 5), (4, 3)].iter() {
+                assert_ok!(EtikaFactoringSystem::register_relationship(
+                    Origin::signed(*merchant),
+                    *merchant,
+                    *supplier,
+                    b"electronics".to_vec(),
+                    80,
+                    50,
+                    0
+                ));
+                assert_ok!(EtikaFactoringSystem::confirm_relationship(
+                    Origin::signed(*supplier),
+                    *merchant,
+                    *supplier
+                ));
+                assert_ok!(EtikaFactoringSystem::process_factoring_payment(
+                    Origin::root(),
+                    [1; 32],
+                    *merchant,
+                    *supplier,
+                    1000
+                ));
+            }
+            assert_eq!(EtikaFactoringSystem::payment_queue(50).len(), 3);
+            
+            // Au plus `MaxPaymentsPerBlock` paiements par bloc, le reste au bloc suivant
+            System::set_block_number(50);
+            EtikaFactoringSystem::on_initialize(50);
+            assert_eq!(EtikaFactoringSystem::payment_queue(50).len(), 1);
+            assert_eq!(EtikaFactoringSystem::payment_cursor(), Some(50));
+            
+            System::set_block_number(51);
+            EtikaFactoringSystem::on_initialize(51);
+            assert!(EtikaFactoringSystem::payment_queue(50).is_empty());
+            assert_eq!(EtikaFactoringSystem::payment_cursor(), None);
+            assert_eq!(Balances::free_balance(3), 30000 + 2000);
+            assert_eq!(Balances::free_balance(5), 50000 + 1000);
+        });
+    }
+    
+    #[test]
+    fn test_suspend_and_reactivate_relationship() {
+        new_test_ext().execute_with(|| {
+            // Enregistrer et confirmer une relation commerciale
+            assert_ok!(EtikaFactoringSystem::register_relationship(
+                Origin::signed(2),
+                2,
+                3,
+                b"electronics".to_vec(),
+                80,
+                50,
+                500
+            ));
             
-            // Vérifier que la relation est en attente
-            ensure!(relationship.status == RelationshipStatus::Pending, Error::<T>::IncompatibleStatus);
+            assert_ok!(EtikaFactoringSystem::confirm_relationship(
+                Origin::signed(3),
+                2,
+                3
+            ));
             
-            // Mettre à jour le statut
-            relationship.status = RelationshipStatus::Active;
+            // Suspendre la relation
+            assert_ok!(EtikaFactoringSystem::suspend_relationship(
+                Origin::signed(2),
+                2,
+                3
+            ));
             
-            // Enregistrer la relation mise à jour
-            <CommercialRelationships<T>>::insert((merchant.clone(), supplier.clone()), relationship);
+            // Vérifier que la relation a été suspendue
+            let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
+            assert_eq!(relationship.status, RelationshipStatus::Suspended);
             
-            // Émettre un événement
-            Self::deposit_event(RawEvent::RelationshipStatusUpdated(
-                merchant,
-                supplier,
-                RelationshipStatus::Active
+            // Réactiver la relation
+            assert_ok!(EtikaFactoringSystem::reactivate_relationship(
+                Origin::signed(3),
+                2,
+                3
             ));
             
-            Ok(())
-        }
-        
-        /// Mettre à jour les conditions d'affacturage
-        #[weight = 10_000]
-        pub fn update_factoring_conditions(
-            origin,
-            merchant: T::AccountId,
-            supplier: T::AccountId,
-            immediate_payment_percent: u8,
-            remaining_payment_delay: u64,
-            interest_rate: u32,
-        ) -> DispatchResult {
-            let updater = ensure_signed(origin)?;
+            // Vérifier que la relation a été réactivée
+            let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
+            assert_eq!(relationship.status, RelationshipStatus::Active);
+        });
+    }
+    
+    #[test]
+    fn test_terminate_relationship() {
+        new_test_ext().execute_with(|| {
+            // Enregistrer et confirmer une relation commerciale
+            assert_ok!(EtikaFactoringSystem::register_relationship(
+                Origin::signed(2),
+                2,
+                3,
+                b"electronics".to_vec(),
+                80,
+                50,
+                500
+            ));
             
-            // Vérifier que l'initiateur est soit le commerçant, soit le fournisseur
-            ensure!(updater == merchant || updater == supplier, Error::<T>::Unauthorized);
+            assert_ok!(EtikaFactoringSystem::confirm_relationship(
+                Origin::signed(3),
+                2,
+                3
+            ));
             
-            // Vérifier que la relation existe
-            ensure!(
-                <CommercialRelationships<T>>::contains_key((merchant.clone(), supplier.clone())),
-                Error::<T>::RelationshipNotFound
-            );
+            // Mettre fin à la relation
+            assert_ok!(EtikaFactoringSystem::terminate_relationship(
+                Origin::signed(2),
+                2,
+                3
+            ));
             
-            // Récupérer la relation
-            let mut relationship = <CommercialRelationships<T>>::get((merchant.clone(), supplier.clone()));
+            // Vérifier que la relation a été terminée
+            let relationship = EtikaFactoringSystem::commercial_relationships((2, 3));
+            assert_eq!(relationship.status, RelationshipStatus::Terminated);
+        });
+    }
+    
+    #[test]
+    fn test_liquidity_management() {
+        new_test_ext().execute_with(|| {
+            // Ajouter de la liquidité
+            assert_ok!(EtikaFactoringSystem::add_liquidity(
+                Origin::signed(1),
+                5000
+            ));
             
-            // Vérifier que la relation est active
-            ensure!(relationship.status == RelationshipStatus::Active, Error::<T>::IncompatibleStatus);
+            // Vérifier la liquidité
+            let liquidity_after_add = EtikaFactoringSystem::factoring_liquidity();
+            assert_eq!(liquidity_after_add, 1000000 + 5000);
             
-            // Vérifier les nouvelles conditions
-            ensure!(
-                immediate_payment_percent >= T::MinImmediatePaymentPercent::get(),
-                Error::<T>::ImmediatePaymentPercentTooLow
-            );
+            // Vérifier le solde du compte de liquidité
+            let liquidity_account_balance = Balances::free_balance(999);
+            assert_eq!(liquidity_account_balance, 1000000 + 5000);
             
-            ensure!(interest_rate <= T::MaxInterestRate::get(), Error::<T>::InterestRateTooHigh);
+            // Retirer de la liquidité
+            assert_ok!(EtikaFactoringSystem::withdraw_liquidity(
+                Origin::signed(999),
+                2000,
+                1
+            ));
             
-            // Mettre à jour les conditions
-            let conditions = FactoringConditions {
-                immediate_payment_percent,
-                remaining_payment_delay,
-                interest_rate,
-            };
+            // Vérifier la liquidité
+            let liquidity_after_withdraw = EtikaFactoringSystem::factoring_liquidity();
+            assert_eq!(liquidity_after_withdraw, 1000000 + 5000 - 2000);
+            
+            // Vérifier le solde du compte de liquidité
+            let liquidity_account_balance_after = Balances::free_balance(999);
+            assert_eq!(liquidity_account_balance_after, 1000000 + 5000 - 2000);
             
-            relationship.factoring
\ No newline at end of file
+            // Vérifier le solde du destinataire
+            let recipient_balance = Balances::free_balance(1);
+            assert_eq!(recipient_balance, 10000 - 5000 + 2000);
+        });
+    }
+}
@@ -1,1558 +1,1640 @@
-            // Vérifier que l'ordre est actif et compatible
-            if buy_order.status == OrderStatus::Active && 
-               buy_order.price >= sell_order.price &&
-               buy_order.creator != sell_order.creator { // Pas de self-trading
-                
-                let match_quantity = remaining_quantity.min(buy_order.quantity);
-                
-                if match_quantity > Zero::zero() {
-                    matched_buy_orders.push((buy_order_id, buy_order, match_quantity));
-                    remaining_quantity = remaining_quantity.saturating_sub(match_quantity);
-                    
-                    // Si quantité restante = 0, on peut s'arrêter
-                    if remaining_quantity.is_zero() {
-                        break;
-                    }
-                }
-            }
-        }
+// etika-marketplace/src/lib.rs
+//
+// Ce module implémente la place de marché de l'écosystème Étika:
+// - Échange de tokens entre participants
+// - Gestion des ordres d'achat et de vente
+// - Produits financiers basés sur l'épargne des consommateurs
+// - Mécanismes de liquidité et de détermination des prix
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult,
+    ensure, traits::{Currency, ExistenceRequirement, Get, ReservableCurrency, WithdrawReasons},
+    transactional, weights::Weight, Parameter,
+};
+use frame_system::{self as system, ensure_signed};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Member, Zero, SaturatedConversion},
+    DispatchError, Perbill, RuntimeDebug,
+};
+use sp_std::{convert::TryInto, prelude::*};
+
+// Import des structures de données définies dans etika-data-structure
+use etika_data_structure::{
+    AccountId, Balance, Moment, ActorType, MarketOrder, OrderType, OrderStatus,
+    FinancialProduct, ProductType, ProductStatus, Marketplace, TokenSystem, TradeMonitor,
+};
+
+/// Poids d'une opération sur le carnet d'ordres (expiration ou exécution)
+const ORDER_OPERATION_WEIGHT: Weight = 10_000;
+
+/// Type monétaire utilisé pour le module
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Entrée d'un carnet d'ordres: prix, identifiant de l'ordre et créateur
+type BookEntry<T> = (BalanceOf<T>, [u8; 32], <T as frame_system::Config>::AccountId);
+
+/// Configuration du module marketplace
+pub trait Config: frame_system::Config {
+    /// Type d'événement
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+    
+    /// Type de monnaie utilisé
+    type Currency: ReservableCurrency<Self::AccountId>;
+    
+    /// Système de tokens
+    type TokenSystem: TokenSystem;
+    
+    /// Frais de transaction sur la place de marché (en pourcentage)
+    type MarketplaceFee: Get<Perbill>;
+    
+    /// Compte destinataire des frais de transaction
+    type FeeAccount: Get<Self::AccountId>;
+    
+    /// Montant minimum pour un ordre sur la place de marché
+    type MinOrderAmount: Get<BalanceOf<Self>>;
+    
+    /// Durée maximale d'un ordre (en blocs)
+    type MaxOrderDuration: Get<Self::BlockNumber>;
+    
+    /// Nombre maximum d'ordres par compte
+    type MaxOrdersPerAccount: Get<u32>;
+    
+    /// Nombre maximum de produits financiers actifs
+    type MaxActiveProducts: Get<u32>;
+    
+    /// Frais de création d'un produit financier
+    type ProductCreationFee: Get<BalanceOf<Self>>;
+    
+    /// Surveillance des échanges (circuit-breakers)
+    type TradeMonitor: TradeMonitor;
+    
+    /// Nombre maximum d'exécutions par bloc
+    type MaxMatchesPerBlock: Get<u32>;
+    
+    /// Nombre maximum d'ordres ouverts dans chacun des carnets d'achat et de vente
+    type MaxBookSize: Get<u32>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Config> as EtikaMarketplace {
+        /// Ordres d'achat actifs
+        BuyOrders get(fn buy_orders): map hasher(blake2_128_concat) [u8; 32] => MarketOrder;
         
-        // Exécuter les correspondances
-        for (buy_order_id, mut buy_order, match_quantity) in matched_buy_orders {
-            // Calculer le prix de la transaction (généralement le prix de l'ordre placé en premier)
-            let trade_price = buy_order.price;
-            
-            // Calculer le montant total
-            let total_amount = match_quantity.saturating_mul(trade_price);
-            
-            // Calculer les frais de marché
-            let fee_amount = T::MarketplaceFee::get() * total_amount;
-            let net_amount = total_amount.saturating_sub(fee_amount);
-            
-            // Effectuer les transferts
-            
-            // 1. Libérer les fonds réservés de l'acheteur
-            T::Currency::unreserve(&buy_order.creator, total_amount);
-            
-            // 2. Transférer les frais au compte des frais
-            T::Currency::transfer(
-                &buy_order.creator,
-                &T::FeeAccount::get(),
-                fee_amount,
-                ExistenceRequirement::KeepAlive
-            )?;
-            
-            // 3. Transférer le montant net au vendeur
-            T::Currency::transfer(
-                &buy_order.creator,
-                &sell_order.creator,
-                net_amount,
-                ExistenceRequirement::KeepAlive
-            )?;
-            
-            // 4. Transférer les tokens du vendeur à l'acheteur
-            // Pour une implémentation réelle, il faudrait adapter ce code au vrai système de tokens
-            let _ = T::TokenSystem::transfer_tokens(
-                &sell_order.creator.clone().try_into().map_err(|_| Error::<T>::IncompatibleActorType)?,
-                &buy_order.creator.clone().try_into().map_err(|_| Error::<T>::IncompatibleActorType)?,
-                match_quantity.saturated_into::<Balance>()
-            ).map_err(|_| Error::<T>::InsufficientFunds)?;
-            
-            // Mettre à jour les ordres
-            
-            // Ordre de vente
-            sell_order.quantity = sell_order.quantity.saturating_sub(match_quantity);
-            if sell_order.quantity.is_zero() {
-                sell_order.status = OrderStatus::Filled;
-                <SellOrders<T>>::insert(sell_order_id, sell_order.clone());
-                <TotalSellOrders>::mutate(|count| *count = count.saturating_sub(1));
-            } else {
-                sell_order.status = OrderStatus::PartiallyFilled;
-                <SellOrders<T>>::insert(sell_order_id, sell_order.clone());
-            }
-            
-            // Ordre d'achat
-            buy_order.quantity = buy_order.quantity.saturating_sub(match_quantity);
-            if buy_order.quantity.is_zero() {
-                buy_order.status = OrderStatus::Filled;
-                <BuyOrders<T>>::insert(buy_order_id, buy_order.clone());
-                <TotalBuyOrders>::mutate(|count| *count = count.saturating_sub(1));
-            } else {
-                buy_order.status = OrderStatus::PartiallyFilled;
-                <BuyOrders<T>>::insert(buy_order_id, buy_order.clone());
-            }
-            
-            // Mettre à jour les statistiques
-            <TotalTradingVolume<T>>::mutate(|volume| {
-                *volume = volume.saturating_add(total_amount);
-            });
-            
-            <TotalFeesCollected<T>>::mutate(|fees| {
-                *fees = fees.saturating_add(fee_amount);
-            });
-            
-            // Mettre à jour l'historique des prix
-            Self::update_price_history(trade_price);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::TradeExecuted(
-                buy_order_id,
-                sell_order_id,
-                match_quantity,
-                trade_price
-            ));
-        }
+        /// Ordres de vente actifs
+        SellOrders get(fn sell_orders): map hasher(blake2_128_concat) [u8; 32] => MarketOrder;
         
-        Ok(())
-    }
-    
-    /// Faire correspondre les ordres d'achat et de vente existants
-    fn match_orders() -> DispatchResult {
-        // Cette fonction est appelée périodiquement pour tenter de faire correspondre les ordres existants
-        
-        // Récupérer tous les ordres d'achat actifs
-        let buy_orders: Vec<_> = <BuyOrders<T>>::iter()
-            .filter(|(_, order)| order.status == OrderStatus::Active)
-            .collect();
-        
-        // Pour chaque ordre d'achat, tenter de le faire correspondre
-        for (buy_order_id, _) in buy_orders {
-            let _ = Self::try_match_buy_order(buy_order_id);
-        }
+        /// Ordres par compte
+        AccountOrders get(fn account_orders): map hasher(blake2_128_concat) T::AccountId => Vec<[u8; 32]>;
         
-        Ok(())
-    }
-    
-    /// Mettre à jour l'historique des prix et la moyenne des prix
-    fn update_price_history(price: BalanceOf<T>) {
-        // Mettre à jour l'historique des prix
-        let mut history = <PriceHistory<T>>::get();
+        /// Produits financiers actifs
+        FinancialProducts get(fn financial_products): map hasher(blake2_128_concat) [u8; 32] => FinancialProduct;
         
-        // Ajouter la nouvelle entrée
-        let current_block = <frame_system::Module<T>>::block_number();
-        history.push((price, current_block));
+        /// Produits financiers par type
+        ProductsByType get(fn products_by_type): map hasher(blake2_128_concat) ProductType => Vec<[u8; 32]>;
         
-        // Limiter la taille de l'historique aux 100 dernières entrées
-        if history.len() > 100 {
-            history.remove(0);
-        }
+        /// Investissements par compte et produit
+        Investments get(fn investments): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) [u8; 32] => BalanceOf<T>;
         
-        <PriceHistory<T>>::put(history.clone());
+        /// Nombre total d'ordres d'achat actifs
+        TotalBuyOrders get(fn total_buy_orders): u32;
         
-        // Calculer la moyenne des prix
-        if !history.is_empty() {
-            let total_price: BalanceOf<T> = history.iter()
-                .map(|(p, _)| *p)
-                .fold(Zero::zero(), |acc, p| acc.saturating_add(p));
-                
-            let average_price = total_price / history.len().saturated_into::<BalanceOf<T>>();
-            <AverageTokenPrice<T>>::put(average_price);
-        }
+        /// Nombre total d'ordres de vente actifs
+        TotalSellOrders get(fn total_sell_orders): u32;
+        
+        /// Nombre total de produits financiers actifs
+        TotalActiveProducts get(fn total_active_products): u32;
+        
+        /// Volume total des transactions sur la place de marché
+        TotalTradingVolume get(fn total_trading_volume): BalanceOf<T>;
+        
+        /// Frais totaux collectés
+        TotalFeesCollected get(fn total_fees_collected): BalanceOf<T>;
+        
+        /// Prix moyen du token (moyenne mobile sur les 100 dernières transactions)
+        AverageTokenPrice get(fn average_token_price): BalanceOf<T>;
+        
+        /// Historique des prix (limité aux 100 dernières transactions)
+        PriceHistory get(fn price_history): Vec<(BalanceOf<T>, T::BlockNumber)>;
+        
+        /// Meilleur prix d'achat actuel
+        BestBuyPrice get(fn best_buy_price): BalanceOf<T>;
+        
+        /// Meilleur prix de vente actuel
+        BestSellPrice get(fn best_sell_price): BalanceOf<T>;
+        
+        /// Mapping des types d'acteur par compte
+        ActorTypes get(fn actor_types): map hasher(blake2_128_concat) T::AccountId => ActorType;
+        
+        /// Carnet d'achat: ordres ouverts triés par prix décroissant puis par ancienneté (au plus `MaxBookSize`)
+        BuyBook get(fn buy_book): Vec<BookEntry<T>>;
+        
+        /// Carnet de vente: ordres ouverts triés par prix croissant puis par ancienneté (au plus `MaxBookSize`)
+        SellBook get(fn sell_book): Vec<BookEntry<T>>;
+        
+        /// Ordres à clôturer par numéro de bloc
+        OrderExpiries get(fn order_expiries): map hasher(blake2_128_concat) T::BlockNumber => Vec<([u8; 32], OrderType)>;
+        
+        /// Nombre d'ordres ouverts par compte
+        OpenOrderCount get(fn open_order_count): map hasher(blake2_128_concat) T::AccountId => u32;
     }
 }
 
-/// Implémentation du trait Marketplace pour le module marketplace
-impl<T: Config> Marketplace for Module<T> {
-    fn create_order(
-        creator: &AccountId,
-        order_type: OrderType,
-        quantity: Balance,
-        price: Balance,
-        expiration: Moment,
-    ) -> Result<[u8; 32], &'static str> {
-        // Convertir les types génériques en types spécifiques au runtime
-        let creator_id = creator.clone().try_into().map_err(|_| "Invalid creator ID")?;
-        let quantity_balance = quantity.saturated_into::<BalanceOf<T>>();
-        let price_balance = price.saturated_into::<BalanceOf<T>>();
+decl_event!(
+    pub enum Event<T> where
+        AccountId = <T as frame_system::Config>::AccountId,
+        Balance = BalanceOf<T>,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
+    {
+        /// Nouvel ordre d'achat créé
+        /// [order_id, créateur, quantité, prix]
+        BuyOrderCreated([u8; 32], AccountId, Balance, Balance),
         
-        // Vérifier les paramètres basiques
-        if quantity == 0 {
-            return Err("Quantity must be positive");
-        }
+        /// Nouvel ordre de vente créé
+        /// [order_id, créateur, quantité, prix]
+        SellOrderCreated([u8; 32], AccountId, Balance, Balance),
         
-        if price == 0 {
-            return Err("Price must be positive");
-        }
+        /// Ordre annulé
+        /// [order_id, type d'ordre]
+        OrderCancelled([u8; 32], OrderType),
         
-        // Générer l'ID de l'ordre
-        let order_id = Self::generate_order_id(&creator_id, order_type, quantity_balance, price_balance);
+        /// Ordre expiré
+        /// [order_id, type d'ordre]
+        OrderExpired([u8; 32], OrderType),
         
-        // Dans une implémentation réelle, on créerait l'ordre ici
-        // Pour cette interface simple, nous ne faisons que retourner l'ID
+        /// Transaction exécutée
+        /// [ordre achat, ordre vente, quantité, prix]
+        TradeExecuted([u8; 32], [u8; 32], Balance, Balance),
         
-        Ok(order_id)
-    }
-    
-    fn cancel_order(order_id: [u8; 32], caller: &AccountId) -> Result<(), &'static str> {
-        // Convertir les types génériques en types spécifiques au runtime
-        let caller_id = caller.clone().try_into().map_err(|_| "Invalid caller ID")?;
+        /// Nouveau produit financier créé
+        /// [product_id, créateur, nom, type, rendement attendu]
+        FinancialProductCreated([u8; 32], AccountId, Vec<u8>, ProductType, u32),
         
-        // Vérifier si l'ordre existe comme ordre d'achat
-        if <BuyOrders<T>>::contains_key(order_id) {
-            let order = <BuyOrders<T>>::get(order_id);
-            
-            // Vérifier que l'appelant est le créateur
-            if order.creator != caller_id {
-                return Err("Unauthorized");
-            }
-            
-            if order.status != OrderStatus::Active {
-                return Err("Order is not active");
-            }
-            
-            // Dans une implémentation réelle, on annulerait l'ordre ici
-            
-            Ok(())
-        } 
-        // Vérifier si l'ordre existe comme ordre de vente
-        else if <SellOrders<T>>::contains_key(order_id) {
-            let order = <SellOrders<T>>::get(order_id);
-            
-            // Vérifier que l'appelant est le créateur
-            if order.creator != caller_id {
-                return Err("Unauthorized");
-            }
-            
-            if order.status != OrderStatus::Active {
-                return Err("Order is not active");
-            }
-            
-            // Dans une implémentation réelle, on annulerait l'ordre ici
-            
-            Ok(())
-        }
-        else {
-            Err("Order not found")
-        }
-    }
-    
-    fn match_orders() -> Result<(), &'static str> {
-        match Self::match_orders() {
-            Ok(_) => Ok(()),
-            Err(_) => Err("Failed to match orders"),
-        }
+        /// Investissement dans un produit financier
+        /// [investisseur, product_id, montant]
+        InvestmentMade(AccountId, [u8; 32], Balance),
+        
+        /// Retrait d'un investissement
+        /// [investisseur, product_id, montant]
+        InvestmentWithdrawn(AccountId, [u8; 32], Balance),
+        
+        /// Statut d'un produit financier modifié
+        /// [product_id, nouveau statut]
+        ProductStatusUpdated([u8; 32], ProductStatus),
+        
+        /// Rendement distribué aux investisseurs
+        /// [product_id, rendement total]
+        YieldDistributed([u8; 32], Balance),
+        
+        /// Correspondance suspendue par les circuit-breakers
+        /// [prix rejeté]
+        MatchingSuspended(Balance),
     }
-}
+);
 
-/// Tests unitaires pour le module marketplace
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use frame_support::{assert_ok, assert_noop, parameter_types};
-    use sp_core::H256;
-    use sp_runtime::{
-        testing::Header,
-        traits::{BlakeTwo256, IdentityLookup},
-        Perbill, ModuleId,
-    };
-    
-    type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
-    type Block = frame_system::mocking::MockBlock<Test>;
-    
-    frame_support::construct_runtime!(
-        pub enum Test where
-            Block = Block,
-            NodeBlock = Block,
-            UncheckedExtrinsic = UncheckedExtrinsic,
-        {
-            System: frame_system::{Module, Call, Config, Storage, Event<T>},
-            Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
-            EtikaMarketplace: Module<Test>,
-        }
-    );
-    
-    parameter_types! {
-        pub const BlockHashCount: u64 = 250;
-        pub const MaximumBlockWeight: u32 = 1024;
-        pub const MaximumBlockLength: u32 = 2 * 1024;
-        pub const AvailableBlockRatio: Perbill = Perbill::one();
-        pub const ExistentialDeposit: u64 = 1;
-    }
-    
-    impl frame_system::Config for Test {
-        type BaseCallFilter = ();
-        type BlockWeights = ();
-        type BlockLength = ();
-        type DbWeight = ();
-        type Origin = Origin;
-        type Index = u64;
-        type BlockNumber = u64;
-        type Call = Call;
-        type Hash = H256;
-        type Hashing = BlakeTwo256;
-        type AccountId = u64;
-        type Lookup = IdentityLookup<Self::AccountId>;
-        type Header = Header;
-        type Event = Event;
-        type BlockHashCount = BlockHashCount;
-        type Version = ();
-        type PalletInfo = PalletInfo;
-        type AccountData = pallet_balances::AccountData<u64>;
-        type OnNewAccount = ();
-        type OnKilledAccount = ();
-        type SystemWeightInfo = ();
-        type SS58Prefix = ();
-    }
-    
-    parameter_types! {
-        pub const MinimumPeriod: u64 = 1000;
-        pub const MaxLocks: u32 = 50;
-    }
-    
-    impl pallet_balances::Config for Test {
-        type MaxLocks = MaxLocks;
-        type Balance = u64;
-        type Event = Event;
-        type DustRemoval = ();
-        type ExistentialDeposit = ExistentialDeposit;
-        type AccountStore = System;
-        type WeightInfo = ();
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Ordre non trouvé
+        OrderNotFound,
+        
+        /// Produit financier non trouvé
+        ProductNotFound,
+        
+        /// Montant trop faible
+        AmountTooSmall,
+        
+        /// Trop d'ordres pour ce compte
+        TooManyOrders,
+        
+        /// Trop de produits financiers actifs
+        TooManyActiveProducts,
+        
+        /// Fonds insuffisants
+        InsufficientFunds,
+        
+        /// Ordre déjà exécuté ou annulé
+        OrderNotActive,
+        
+        /// Non autorisé à effectuer cette opération
+        Unauthorized,
+        
+        /// Produit non disponible pour investissement
+        ProductNotOpen,
+        
+        /// Période de verrouillage non écoulée
+        LockPeriodNotExpired,
+        
+        /// Prix invalide
+        InvalidPrice,
+        
+        /// Quantité invalide
+        InvalidQuantity,
+        
+        /// Le créateur de l'ordre ne peut pas être l'exécuteur
+        SelfTrading,
+        
+        /// Type d'acteur incompatible
+        IncompatibleActorType,
+        
+        /// Type de produit financier non reconnu
+        InvalidProductType,
+        
+        /// Rendement attendu invalide
+        InvalidExpectedYield,
+        
+        /// Durée d'investissement invalide
+        InvalidInvestmentDuration,
+        
+        /// Le carnet d'ordres a atteint sa taille maximale
+        OrderBookFull,
+        
+        /// Le vendeur ne peut pas livrer les tokens de son ordre
+        SellerSettlementFailed,
+        
+        /// L'acheteur ne peut pas régler le montant de son ordre
+        BuyerSettlementFailed,
+        
+        /// L'échange a été refusé par les circuit-breakers
+        TradeRejected,
     }
-    
-    // Mock implementation for TokenSystem
-    pub struct MockTokenSystem;
-    
-    impl TokenSystem for MockTokenSystem {
-        fn distribute_tokens(_to: &AccountId, _amount: Balance) -> Result<(), &'static str> {
-            Ok(())
-        }
-    
-        fn activate_tokens(_from: &AccountId, _amount: Balance) -> Result<(), &'static str> {
-            Ok(())
-        }
-    
-        fn burn_tokens(_from: &AccountId, _amount: Balance) -> Result<(), &'static str> {
-            Ok(())
+}
+
+decl_module! {
+    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+        /// Initialisation des erreurs
+        type Error = Error<T>;
+        
+        /// Émission des événements
+        fn deposit_event() = default;
+        
+        /// Traitement des ordres expirés et correspondance des ordres au changement de bloc
+        ///
+        /// Le nombre d'exécutions est borné par `MaxMatchesPerBlock` et la taille des carnets par `MaxBookSize`
+        /// afin que le poids du bloc reste prévisible.
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            // Clôturer les ordres arrivés à expiration
+            let expired = Self::expire_orders(n);
+
+            // Faire correspondre les carnets d'ordres
+            let matched = Self::match_books(None, T::MaxMatchesPerBlock::get());
+
+            (expired.saturating_add(matched) as Weight)
+                .saturating_mul(ORDER_OPERATION_WEIGHT)
         }
-    
-        fn transfer_tokens(_from: &AccountId, _to: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+
+        /// Créer un nouvel ordre d'achat
+        #[weight = 10_000]
+        pub fn create_buy_order(
+            origin,
+            quantity: BalanceOf<T>,
+            price: BalanceOf<T>,
+            expiration: T::BlockNumber,
+        ) -> DispatchResult {
+            let creator = ensure_signed(origin)?;
+
+            let order_id = Self::do_create_order(creator, OrderType::Buy, quantity, price, expiration)?;
+
+            // Tenter de faire correspondre cet ordre avec le carnet de vente
+            Self::match_books(Some(order_id), T::MaxMatchesPerBlock::get());
+
             Ok(())
         }
 
-        fn active_balance(_account: &AccountId) -> Balance {
-            10000
-        }
+        /// Créer un nouvel ordre de vente
+        #[weight = 10_000]
+        pub fn create_sell_order(
+            origin,
+            quantity: BalanceOf<T>,
+            price: BalanceOf<T>,
+            expiration: T::BlockNumber,
+        ) -> DispatchResult {
+            let creator = ensure_signed(origin)?;
+
+            let order_id = Self::do_create_order(creator, OrderType::Sell, quantity, price, expiration)?;
+
+            // Tenter de faire correspondre cet ordre avec le carnet d'achat
+            Self::match_books(Some(order_id), T::MaxMatchesPerBlock::get());
 
-        fn lock_tokens(_account: &AccountId, _amount: Balance) -> Result<(), &'static str> {
             Ok(())
         }
 
-        fn unlock_tokens(_account: &AccountId) -> Result<(), &'static str> {
-            Ok(())
+        /// Annuler un ordre existant
+        #[weight = 10_000]
+        pub fn cancel_order(
+            origin,
+            order_id: [u8; 32],
+            order_type: OrderType,
+        ) -> DispatchResult {
+            let canceller = ensure_signed(origin)?;
+
+            Self::do_cancel_order(canceller, order_id, order_type)
         }
-    }
-    
-    parameter_types! {
-        pub const MarketplaceFee: Perbill = Perbill::from_percent(1); // 1%
-        pub const FeeAccountId: u64 = 999;
-        pub const MinOrderAmount: u64 = 100;
-        pub const MaxOrderDuration: u64 = 1000; // 1000 blocs
-        pub const MaxOrdersPerAccount: u32 = 10;
-        pub const MaxActiveProducts: u32 = 100;
-        pub const ProductCreationFee: u64 = 1000;
-    }
-    
-    impl Config for Test {
-        type Event = Event;
-        type Currency = Balances;
-        type TokenSystem = MockTokenSystem;
-        type MarketplaceFee = MarketplaceFee;
-        type FeeAccount = FeeAccountId;
-        type MinOrderAmount = MinOrderAmount;
-        type MaxOrderDuration = MaxOrderDuration;
-        type MaxOrdersPerAccount = MaxOrdersPerAccount;
-        type MaxActiveProducts = MaxActiveProducts;
-        type ProductCreationFee = ProductCreationFee;
-    }
-    
-    // Fonction utilitaire pour créer un environnement de test
-    fn new_test_ext() -> sp_io::TestExternalities {
-        let mut t = frame_system::GenesisConfig::default()
-            .build_storage::<Test>()
-            .unwrap();
+
+        /// Créer un nouveau produit financier
+        #[weight = 10_000]
+        pub fn create_financial_product(
+            origin,
+            name: Vec<u8>,
+            description: Vec<u8>,
+            product_type: ProductType,
+            expected_yield: u32,
+            min_investment_duration: u64,
+            min_investment_amount: BalanceOf<T>,
+            risk_level: u8,
+        ) -> DispatchResult {
+            let creator = ensure_signed(origin)?;
             
-        pallet_balances::GenesisConfig::<Test> {
-            balances: vec![
-                (1, 100000),
-                (2, 200000),
-                (3, 300000),
-                (4, 400000),
-                (5, 500000),
-                (999, 1000000), // Compte des frais
-            ],
-        }
-        .assimilate_storage(&mut t)
-        .unwrap();
-        
-        // Initialiser la moyenne des prix
-        let mut ext = t.into();
-        ext.execute_with(|| {
-            <AverageTokenPrice<Test>>::put(1000);
+            // Vérifier que l'acteur est autorisé (devrait être le fonds des consommateurs ou un administrateur)
+            // Dans une implémentation réelle, il faudrait un système d'autorisation plus sophistiqué
             
-            // Enregistrer les types d'acteurs
-            <ActorTypes<Test>>::insert(1, ActorType::Consumer);
-            <ActorTypes<Test>>::insert(2, ActorType::Merchant);
-            <ActorTypes<Test>>::insert(3, ActorType::Supplier);
-            <ActorTypes<Test>>::insert(4, ActorType::NGO);
-            <ActorTypes<Test>>::insert(5, ActorType::Investor);
-        });
-        
-        ext
-    }
-    
-    #[test]
-    fn test_create_buy_order() {
-        new_test_ext().execute_with(|| {
-            // Créer un ordre d'achat
-            assert_ok!(EtikaMarketplace::create_buy_order(
-                Origin::signed(1),
-                100,    // quantité
-                1500,   // prix
-                100     // expiration
-            ));
-            
-            // Vérifier le nombre d'ordres
-            assert_eq!(EtikaMarketplace::total_buy_orders(), 1);
+            // Vérifier que les paramètres du produit sont valides
+            ensure!(expected_yield > 0, Error::<T>::InvalidExpectedYield);
+            ensure!(min_investment_duration > 0, Error::<T>::InvalidInvestmentDuration);
+            ensure!(min_investment_amount > Zero::zero(), Error::<T>::AmountTooSmall);
+            ensure!(risk_level >= 1 && risk_level <= 5, Error::<T>::InvalidProductType);
             
-            // Vérifier les ordres du compte
-            let account_orders = EtikaMarketplace::account_orders(1);
-            assert_eq!(account_orders.len(), 1);
+            // Vérifier que nous n'avons pas trop de produits actifs
+            let active_products = <TotalActiveProducts>::get();
+            ensure!(
+                active_products < T::MaxActiveProducts::get(),
+                Error::<T>::TooManyActiveProducts
+            );
             
-            // Vérifier le meilleur prix d'achat
-            assert_eq!(EtikaMarketplace::best_buy_price(), 1500);
-        });
-    }
-    
-    #[test]
-    fn test_create_sell_order() {
-        new_test_ext().execute_with(|| {
-            // Créer un ordre de vente
-            assert_ok!(EtikaMarketplace::create_sell_order(
-                Origin::signed(1),
-                100,    // quantité
-                1200,   // prix
-                100     // expiration
-            ));
+            // Prélever les frais de création
+            T::Currency::transfer(
+                &creator,
+                &T::FeeAccount::get(),
+                T::ProductCreationFee::get(),
+                ExistenceRequirement::KeepAlive
+            )?;
             
-            // Vérifier le nombre d'ordres
-            assert_eq!(EtikaMarketplace::total_sell_orders(), 1);
+            <TotalFeesCollected<T>>::mutate(|total| {
+                *total = total.saturating_add(T::ProductCreationFee::get());
+            });
             
-            // Vérifier les ordres du compte
-            let account_orders = EtikaMarketplace::account_orders(1);
-            assert_eq!(account_orders.len(), 1);
+            // Créer l'ID du produit
+            let product_id = Self::generate_product_id(&creator, &name, product_type);
             
-            // Vérifier le meilleur prix de vente
-            assert_eq!(EtikaMarketplace::best_sell_price(), 1200);
-        });
-    }
-    
-    #[test]
-    fn test_cancel_order() {
-        new_test_ext().execute_with(|| {
-            // Créer un ordre d'achat
-            assert_ok!(EtikaMarketplace::create_buy_order(
-                Origin::signed(1),
-                100,
-                1500,
-                100
-            ));
+            // Créer le produit financier
+            let product = FinancialProduct {
+                id: product_id,
+                name: name.clone(),
+                description,
+                product_type,
+                expected_yield,
+                min_investment_duration,
+                min_investment_amount: min_investment_amount.saturated_into::<Balance>(),
+                total_invested: 0,
+                risk_level,
+                created_at: Self::get_current_timestamp(),
+                status: ProductStatus::Open,
+            };
             
-            // Récupérer l'ID de l'ordre
-            let account_orders = EtikaMarketplace::account_orders(1);
-            let order_id = account_orders[0];
+            // Enregistrer le produit
+            <FinancialProducts<T>>::insert(product_id, product);
             
-            // Annuler l'ordre
-            assert_ok!(EtikaMarketplace::cancel_order(
-                Origin::signed(1),
-                order_id,
-                OrderType::Buy
-            ));
+            // Mettre à jour la liste des produits par type
+            <ProductsByType<T>>::mutate(product_type, |products| {
+                products.push(product_id);
+            });
             
-            // Vérifier que l'ordre est annulé
-            let order = EtikaMarketplace::buy_orders(order_id);
-            assert_eq!(order.status, OrderStatus::Cancelled);
+            // Mettre à jour le compteur de produits
+            <TotalActiveProducts>::mutate(|count| *count += 1);
             
-            // Vérifier le nombre d'ordres actifs
-            assert_eq!(EtikaMarketplace::total_buy_orders(), 0);
-        });
-    }
-    
-    #[test]
-    fn test_order_matching() {
-        new_test_ext().execute_with(|| {
-            // Créer un ordre de vente
-            assert_ok!(EtikaMarketplace::create_sell_order(
-                Origin::signed(2),
-                100,
-                1000,
-                100
+            // Émettre un événement
+            Self::deposit_event(RawEvent::FinancialProductCreated(
+                product_id,
+                creator,
+                name,
+                product_type,
+                expected_yield
             ));
             
-            // Créer un ordre d'achat compatible
-            assert_ok!(EtikaMarketplace::create_buy_order(
-                Origin::signed(3),
-                50,
-                1200,  // Prix supérieur au prix de vente
-                100
-            ));
+            Ok(())
+        }
+        
+        /// Investir dans un produit financier
+        #[weight = 10_000]
+        pub fn invest_in_product(
+            origin,
+            product_id: [u8; 32],
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let investor = ensure_signed(origin)?;
             
-            // Vérifier que l'ordre d'achat a été partiellement exécuté
-            let account_orders = EtikaMarketplace::account_orders(3);
-            let buy_order_id = account_orders[0];
-            let buy_order = EtikaMarketplace::buy_orders(buy_order_id);
+            // Vérifier que le produit existe et est ouvert
+            ensure!(<FinancialProducts<T>>::contains_key(product_id), Error::<T>::ProductNotFound);
+            let mut product = <FinancialProducts<T>>::get(product_id);
+            ensure!(product.status == ProductStatus::Open, Error::<T>::ProductNotOpen);
             
-            // L'ordre d'achat devrait être entièrement exécuté
-            assert_eq!(buy_order.status, OrderStatus::Filled);
+            // Vérifier que le montant est suffisant
+            ensure!(
+                amount >= product.min_investment_amount.saturated_into::<BalanceOf<T>>(),
+                Error::<T>::AmountTooSmall
+            );
             
-            // Vérifier que l'ordre de vente a été partiellement exécuté
-            let account_orders = EtikaMarketplace::account_orders(2);
-            let sell_order_id = account_orders[0];
-            let sell_order = EtikaMarketplace::sell_orders(sell_order_id);
+            // Vérifier que l'investisseur a suffisamment de fonds
+            ensure!(
+                T::Currency::free_balance(&investor) >= amount,
+                Error::<T>::InsufficientFunds
+            );
             
-            // L'ordre de vente devrait être partiellement exécuté
-            assert_eq!(sell_order.status, OrderStatus::PartiallyFilled);
-            assert_eq!(sell_order.quantity, 50); // 100 - 50
+            // Transférer les fonds vers le compte du produit (à implémenter)
+            // Dans une implémentation réelle, chaque produit aurait son propre compte multisig
+            // Pour l'exemple, nous utilisons le compte des frais comme destination
+            T::Currency::transfer(
+                &investor,
+                &T::FeeAccount::get(),
+                amount,
+                ExistenceRequirement::KeepAlive
+            )?;
             
-            // Vérifier le volume de trading
-            assert_eq!(EtikaMarketplace::total_trading_volume(), 50 * 1000); // quantité * prix
+            // Mettre à jour l'investissement
+            <Investments<T>>::mutate(investor.clone(), product_id, |invested| {
+                *invested = invested.saturating_add(amount);
+            });
             
-            // Vérifier les frais collectés
-            let expected_fee = Perbill::from_percent(1) * (50 * 1000);
-            assert_eq!(EtikaMarketplace::total_fees_collected(), expected_fee);
-        });
-    }
-    
-    #[test]
-    fn test_create_financial_product() {
-        new_test_ext().execute_with(|| {
-            // Créer un produit financier
-            assert_ok!(EtikaMarketplace::create_financial_product(
-                Origin::signed(999), // Compte administrateur
-                b"Savings Bond".to_vec(),
-                b"A fixed income investment with guaranteed returns".to_vec(),
-                ProductType::GuaranteedSavings,
-                500, // 5.00% de rendement attendu
-                180 * 24 * 60 * 60, // 180 jours en secondes
-                10000, // Montant minimum d'investissement
-                2 // Niveau de risque (1-5)
+            // Mettre à jour le total investi dans le produit
+            product.total_invested = product.total_invested.saturating_add(amount.saturated_into::<Balance>());
+            <FinancialProducts<T>>::insert(product_id, product);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::InvestmentMade(
+                investor,
+                product_id,
+                amount
             ));
             
-            // Vérifier le nombre de produits
-            assert_eq!(EtikaMarketplace::total_active_products(), 1);
+            Ok(())
+        }
+        
+        /// Fermer un produit financier aux nouveaux investissements
+        #[weight = 10_000]
+        pub fn close_product(
+            origin,
+            product_id: [u8; 32],
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
             
-            // Vérifier la liste des produits par type
-            let products = EtikaMarketplace::products_by_type(ProductType::GuaranteedSavings);
-            assert_eq!(products.len(), 1);
+            // Vérifier que le produit existe
+            ensure!(<FinancialProducts<T>>::contains_key(product_id), Error::<T>::ProductNotFound);
+            let mut product = <FinancialProducts<T>>::get(product_id);
             
-            // Vérifier les détails du produit
-            let product_id = products[0];
-            let product = EtikaMarketplace::financial_products(product_id);
+            // Vérifier que le produit est ouvert
+            ensure!(product.status == ProductStatus::Open, Error::<T>::ProductNotOpen);
             
-            assert_eq!(product.name, b"Savings Bond".to_vec());
-            assert_eq!(product.expected_yield, 500);
-            assert_eq!(product.risk_level, 2);
-            assert_eq!(product.status, ProductStatus::Open);
-        });
-    }
-    
-    #[test]
-    fn test_invest_in_product() {
-        new_test_ext().execute_with(|| {
-            // Créer un produit financier
-            assert_ok!(EtikaMarketplace::create_financial_product(
-                Origin::signed(999),
-                b"Savings Bond".to_vec(),
-                b"A fixed income investment with guaranteed returns".to_vec(),
-                ProductType::GuaranteedSavings,
-                500,
-                180 * 24 * 60 * 60,
-                10000,
-                2
-            ));
+            // Vérifier que l'appelant est autorisé (créateur du produit ou administrateur)
+            // Dans une implémentation réelle, il faudrait un système d'autorisation plus sophistiqué
             
-            // Récupérer l'ID du produit
-            let products = EtikaMarketplace::products_by_type(ProductType::GuaranteedSavings);
-            let product_id = products[0];
+            // Mettre à jour le statut du produit
+            product.status = ProductStatus::Closed;
+            <FinancialProducts<T>>::insert(product_id, product);
             
-            // Investir dans le produit
-            assert_ok!(EtikaMarketplace::invest_in_product(
-                Origin::signed(1),
+            // Émettre un événement
+            Self::deposit_event(RawEvent::ProductStatusUpdated(
                 product_id,
-                20000 // Montant de l'investissement
-            ));
-            
-            // Vérifier l'investissement
-            let investment = EtikaMarketplace::investments(1, product_id);
-            assert_eq!(investment, 20000);
-            
-            // Vérifier le total investi dans le produit
-            let product = EtikaMarketplace::financial_products(product_id);
-            assert_eq!(product.total_invested, 20000);
-        });
-    }
-    
-    #[test]
-    fn test_close_product() {
-        new_test_ext().execute_with(|| {
-            // Créer un produit financier
-            assert_ok!(EtikaMarketplace::create_financial_product(
-                Origin::signed(999),
-                b"Savings Bond".to_vec(),
-                b"A fixed income investment with guaranteed returns".to_vec(),
-                ProductType::GuaranteedSavings,
-                500,
-                180 * 24 * 60 * 60,
-                10000,
-                2
-            ));
-            
-            // Récupérer l'ID du produit
-            let products = EtikaMarketplace::products_by_type(ProductType::GuaranteedSavings);
-            let product_id = products[0];
-            
-            // Fermer le produit aux nouveaux investissements
-            assert_ok!(EtikaMarketplace::close_product(
-                Origin::signed(999),
-                product_id
+                ProductStatus::Closed
             ));
             
-            // Vérifier le statut du produit
-            let product = EtikaMarketplace::financial_products(product_id);
-            assert_eq!(product.status, ProductStatus::Closed);
-        });
-    }
-}        /// Initialisation des erreurs
-        type Error = Error<T>;
-        
-        /// Émission des événements
-        fn deposit_event() = default;
-        
-        /// Traitement des ordres expirés et correspondance des ordres au changement de bloc
-        fn on_initialize(n: T::BlockNumber) -> frame_support::weights::Weight {
-            // Nettoyer les ordres expirés
-            Self::clean_expired_orders(n);
-            
-            // Tenter de faire correspondre des ordres d'achat et de vente
-            Self::match_orders();
-            
-            0
+            Ok(())
         }
         
-        /// Créer un nouvel ordre d'achat
+        /// Distribuer un rendement aux investisseurs d'un produit
         #[weight = 10_000]
-        pub fn create_buy_order(
+        pub fn distribute_yield(
             origin,
-            quantity: BalanceOf<T>,
-            price: BalanceOf<T>,
-            expiration: T::BlockNumber,
+            product_id: [u8; 32],
+            total_yield: BalanceOf<T>,
         ) -> DispatchResult {
-            let creator = ensure_signed(origin)?;
-            
-            // Vérifier que le montant est suffisant
-            ensure!(
-                quantity.saturating_mul(price) >= T::MinOrderAmount::get(),
-                Error::<T>::AmountTooSmall
-            );
+            let distributor = ensure_signed(origin)?;
             
-            // Vérifier que les prix sont valides
-            ensure!(price > Zero::zero(), Error::<T>::InvalidPrice);
-            ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantity);
+            // Vérifier que le produit existe
+            ensure!(<FinancialProducts<T>>::contains_key(product_id), Error::<T>::ProductNotFound);
+            let product = <FinancialProducts<T>>::get(product_id);
             
-            // Vérifier que le créateur n'a pas trop d'ordres
-            let mut account_orders = <AccountOrders<T>>::get(&creator);
+            // Vérifier que le produit est fermé ou mature
             ensure!(
-                account_orders.len() < T::MaxOrdersPerAccount::get() as usize,
-                Error::<T>::TooManyOrders
+                product.status == ProductStatus::Closed || product.status == ProductStatus::Matured,
+                Error::<T>::ProductNotOpen
             );
             
-            // Vérifier la période d'expiration
-            let current_block = <frame_system::Module<T>>::block_number();
-            let max_expiration = current_block.saturating_add(T::MaxOrderDuration::get());
-            
-            let actual_expiration = if expiration > max_expiration {
-                max_expiration
-            } else if expiration <= current_block {
-                current_block.saturating_add(T::MaxOrderDuration::get())
-            } else {
-                expiration
-            };
-            
-            // Calculer le montant total nécessaire
-            let total_amount = quantity.saturating_mul(price);
-            
-            // Vérifier que le créateur a suffisamment de fonds
+            // Vérifier que le distributeur a suffisamment de fonds
             ensure!(
-                T::Currency::free_balance(&creator) >= total_amount,
+                T::Currency::free_balance(&distributor) >= total_yield,
                 Error::<T>::InsufficientFunds
             );
             
-            // Réserver les fonds
-            T::Currency::reserve(&creator, total_amount)?;
-            
-            // Créer l'ordre
-            let order_id = Self::generate_order_id(&creator, OrderType::Buy, quantity, price);
-            
-            let order = MarketOrder {
-                id: order_id,
-                creator: creator.clone(),
-                order_type: OrderType::Buy,
-                quantity,
-                price,
-                created_at: Self::get_current_timestamp(),
-                status: OrderStatus::Active,
-                expiration: actual_expiration.saturated_into::<Moment>(),
-            };
-            
-            // Enregistrer l'ordre
-            <BuyOrders<T>>::insert(order_id, order);
-            
-            // Mettre à jour la liste des ordres du compte
-            account_orders.push(order_id);
-            <AccountOrders<T>>::insert(&creator, account_orders);
-            
-            // Mettre à jour le compteur d'ordres
-            <TotalBuyOrders>::mutate(|count| *count += 1);
-            
-            // Mettre à jour le meilleur prix d'achat si nécessaire
-            let best_buy = <BestBuyPrice<T>>::get();
-            if price > best_buy {
-                <BestBuyPrice<T>>::put(price);
-            }
+            // Dans une implémentation réelle, ici nous distribuerions le rendement
+            // à tous les investisseurs proportionnellement à leur investissement
+            // Pour l'exemple, nous émettons simplement un événement
             
             // Émettre un événement
-            Self::deposit_event(RawEvent::BuyOrderCreated(
-                order_id,
-                creator,
-                quantity,
-                price
+            Self::deposit_event(RawEvent::YieldDistributed(
+                product_id,
+                total_yield
             ));
             
-            // Tenter de faire correspondre cet ordre avec des ordres de vente existants
-            Self::try_match_buy_order(order_id)?;
-            
             Ok(())
         }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Générer un ID unique pour un ordre
+    fn generate_order_id(creator: &T::AccountId, order_type: OrderType, quantity: BalanceOf<T>, price: BalanceOf<T>, nonce: u32) -> [u8; 32] {
+        let timestamp = Self::get_current_timestamp();
+        let mut data = Vec::new();
         
-        /// Créer un nouvel ordre de vente
-        #[weight = 10_000]
-        pub fn create_sell_order(
-            origin,
-            quantity: BalanceOf<T>,
-            price: BalanceOf<T>,
-            expiration: T::BlockNumber,
-        ) -> DispatchResult {
-            let creator = ensure_signed(origin)?;
-            
-            // Vérifier que le montant est suffisant
-            ensure!(
-                quantity.saturating_mul(price) >= T::MinOrderAmount::get(),
-                Error::<T>::AmountTooSmall
-            );
-            
-            // Vérifier que les prix sont valides
-            ensure!(price > Zero::zero(), Error::<T>::InvalidPrice);
-            ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantity);
-            
-            // Vérifier que le créateur n'a pas trop d'ordres
-            let mut account_orders = <AccountOrders<T>>::get(&creator);
-            ensure!(
-                account_orders.len() < T::MaxOrdersPerAccount::get() as usize,
-                Error::<T>::TooManyOrders
-            );
-            
-            // Vérifier la période d'expiration
-            let current_block = <frame_system::Module<T>>::block_number();
-            let max_expiration = current_block.saturating_add(T::MaxOrderDuration::get());
-            
-            let actual_expiration = if expiration > max_expiration {
-                max_expiration
-            } else if expiration <= current_block {
-                current_block.saturating_add(T::MaxOrderDuration::get())
-            } else {
-                expiration
-            };
-            
-            // Vérifier que le créateur a suffisamment de tokens actifs
-            let active_balance = T::TokenSystem::active_balance(&creator.clone().try_into().map_err(|_| Error::<T>::IncompatibleActorType)?);
-            ensure!(
-                active_balance >= quantity.saturated_into::<Balance>(),
-                Error::<T>::InsufficientFunds
-            );
-            
-            // Réserver les tokens (en utilisant le système de tokens)
-            let _ = T::TokenSystem::lock_tokens(
-                &creator.clone().try_into().map_err(|_| Error::<T>::IncompatibleActorType)?,
-                quantity.saturated_into::<Balance>()
-            ).map_err(|_| Error::<T>::InsufficientFunds)?;
-            
-            // Créer l'ordre
-            let order_id = Self::generate_order_id(&creator, OrderType::Sell, quantity, price);
-            
-            let order = MarketOrder {
-                id: order_id,
-                creator: creator.clone(),
-                order_type: OrderType::Sell,
-                quantity,
-                price,
-                created_at: Self::get_current_timestamp(),
-                status: OrderStatus::Active,
-                expiration: actual_expiration.saturated_into::<Moment>(),
+        data.extend_from_slice(&creator.encode());
+        data.extend_from_slice(&(order_type as u8).to_be_bytes());
+        data.extend_from_slice(&quantity.saturated_into::<u128>().to_be_bytes());
+        data.extend_from_slice(&price.saturated_into::<u128>().to_be_bytes());
+        data.extend_from_slice(&timestamp.to_be_bytes());
+        data.extend_from_slice(&nonce.to_be_bytes());
+        
+        let hash = sp_io::hashing::blake2_256(&data);
+        hash
+    }
+    
+    /// Générer un ID unique pour un produit financier
+    fn generate_product_id(creator: &T::AccountId, name: &[u8], product_type: ProductType) -> [u8; 32] {
+        let timestamp = Self::get_current_timestamp();
+        let mut data = Vec::new();
+        
+        data.extend_from_slice(&creator.encode());
+        data.extend_from_slice(name);
+        data.extend_from_slice(&(product_type as u8).to_be_bytes());
+        data.extend_from_slice(&timestamp.to_be_bytes());
+        
+        let hash = sp_io::hashing::blake2_256(&data);
+        hash
+    }
+    
+    /// Obtenir le timestamp actuel en secondes
+    fn get_current_timestamp() -> Moment {
+        let now = sp_io::offchain::timestamp()
+            .unwrap_or_default()
+            .unix_millis();
+        (now / 1000) as Moment
+    }
+    
+    /// Convertir un compte du runtime en compte du système de tokens
+    fn token_account(who: &T::AccountId) -> Result<AccountId, DispatchError> {
+        who.clone().try_into().map_err(|_| Error::<T>::IncompatibleActorType.into())
+    }
+
+    /// Un ordre partiellement exécuté reste dans le carnet
+    fn is_open(status: &OrderStatus) -> bool {
+        *status == OrderStatus::Active || *status == OrderStatus::PartiallyFilled
+    }
+
+    /// Créer un ordre, réserver les fonds ou les tokens et l'insérer dans le carnet
+    fn do_create_order(
+        creator: T::AccountId,
+        order_type: OrderType,
+        quantity: BalanceOf<T>,
+        price: BalanceOf<T>,
+        expiration: T::BlockNumber,
+    ) -> Result<[u8; 32], DispatchError> {
+        // Vérifier que les prix sont valides
+        ensure!(price > Zero::zero(), Error::<T>::InvalidPrice);
+        ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantity);
+
+        // Vérifier que le montant est suffisant
+        let total_amount = quantity.saturating_mul(price);
+        ensure!(total_amount >= T::MinOrderAmount::get(), Error::<T>::AmountTooSmall);
+
+        // Vérifier que le créateur n'a pas trop d'ordres ouverts
+        ensure!(
+            <OpenOrderCount<T>>::get(&creator) < T::MaxOrdersPerAccount::get(),
+            Error::<T>::TooManyOrders
+        );
+
+        // Vérifier que le carnet n'est pas plein
+        let book_size = match order_type {
+            OrderType::Buy => <TotalBuyOrders>::get(),
+            OrderType::Sell => <TotalSellOrders>::get(),
+        };
+        ensure!(book_size < T::MaxBookSize::get(), Error::<T>::OrderBookFull);
+
+        // Interdire qu'un ordre croise un ordre opposé du même créateur
+        ensure!(!Self::crosses_own_order(&creator, &order_type, price), Error::<T>::SelfTrading);
+
+        // Vérifier la période d'expiration
+        let current_block = <frame_system::Module<T>>::block_number();
+        let max_expiration = current_block.saturating_add(T::MaxOrderDuration::get());
+
+        let actual_expiration = if expiration > max_expiration {
+            max_expiration
+        } else if expiration <= current_block {
+            current_block.saturating_add(T::MaxOrderDuration::get())
+        } else {
+            expiration
+        };
+
+        // Réserver les fonds de l'acheteur ou les tokens du vendeur
+        match order_type {
+            OrderType::Buy => {
+                T::Currency::reserve(&creator, total_amount)
+                    .map_err(|_| Error::<T>::InsufficientFunds)?;
+            },
+            OrderType::Sell => {
+                T::TokenSystem::reserve_tokens(
+                    &Self::token_account(&creator)?,
+                    quantity.saturated_into::<Balance>()
+                ).map_err(|_| Error::<T>::InsufficientFunds)?;
+            },
+        }
+
+        // Créer l'ordre
+        let mut account_orders = <AccountOrders<T>>::get(&creator);
+        let order_id = Self::generate_order_id(&creator, order_type.clone(), quantity, price, account_orders.len() as u32);
+
+        let order = MarketOrder {
+            id: order_id,
+            creator: creator.clone(),
+            order_type: order_type.clone(),
+            quantity,
+            price,
+            created_at: Self::get_current_timestamp(),
+            status: OrderStatus::Active,
+            expiration: actual_expiration.saturated_into::<Moment>(),
+        };
+
+        // Enregistrer l'ordre et l'insérer dans le carnet
+        match order_type {
+            OrderType::Buy => {
+                <BuyOrders<T>>::insert(order_id, order);
+                <TotalBuyOrders>::mutate(|count| *count += 1);
+            },
+            OrderType::Sell => {
+                <SellOrders<T>>::insert(order_id, order);
+                <TotalSellOrders>::mutate(|count| *count += 1);
+            },
+        }
+        Self::insert_into_book(&order_type, price, order_id, creator.clone());
+
+        // Programmer l'expiration (l'ordre expire après son bloc d'expiration)
+        <OrderExpiries<T>>::append(actual_expiration.saturating_add(1u32.into()), (order_id, order_type.clone()));
+
+        // Mettre à jour la liste des ordres du compte
+        account_orders.push(order_id);
+        <AccountOrders<T>>::insert(&creator, account_orders);
+        <OpenOrderCount<T>>::mutate(&creator, |count| *count += 1);
+
+        // Émettre un événement
+        match order_type {
+            OrderType::Buy => Self::deposit_event(RawEvent::BuyOrderCreated(order_id, creator, quantity, price)),
+            OrderType::Sell => Self::deposit_event(RawEvent::SellOrderCreated(order_id, creator, quantity, price)),
+        }
+
+        Ok(order_id)
+    }
+
+    /// Annuler un ordre ouvert à la demande de son créateur
+    fn do_cancel_order(
+        canceller: T::AccountId,
+        order_id: [u8; 32],
+        order_type: OrderType,
+    ) -> DispatchResult {
+        // Vérifier que l'ordre existe et est ouvert
+        let order = match order_type {
+            OrderType::Buy => {
+                ensure!(<BuyOrders<T>>::contains_key(order_id), Error::<T>::OrderNotFound);
+                <BuyOrders<T>>::get(order_id)
+            },
+            OrderType::Sell => {
+                ensure!(<SellOrders<T>>::contains_key(order_id), Error::<T>::OrderNotFound);
+                <SellOrders<T>>::get(order_id)
+            },
+        };
+
+        // Vérifier que le canceller est le créateur de l'ordre
+        ensure!(order.creator == canceller, Error::<T>::Unauthorized);
+        ensure!(Self::is_open(&order.status), Error::<T>::OrderNotActive);
+
+        Self::close_order(order, OrderStatus::Cancelled)?;
+
+        // Émettre un événement
+        Self::deposit_event(RawEvent::OrderCancelled(order_id, order_type));
+
+        Ok(())
+    }
+
+    /// Retirer un ordre ouvert du carnet et restituer la partie non exécutée
+    fn close_order(mut order: MarketOrder, status: OrderStatus) -> DispatchResult {
+        match order.order_type {
+            OrderType::Buy => {
+                // Libérer les fonds réservés restants
+                let reserved_amount = order.quantity.saturating_mul(order.price);
+                T::Currency::unreserve(&order.creator, reserved_amount);
+                <TotalBuyOrders>::mutate(|count| *count = count.saturating_sub(1));
+            },
+            OrderType::Sell => {
+                // Libérer les tokens réservés restants
+                T::TokenSystem::unreserve_tokens(
+                    &Self::token_account(&order.creator)?,
+                    order.quantity.saturated_into::<Balance>()
+                ).map_err(|_| Error::<T>::InsufficientFunds)?;
+                <TotalSellOrders>::mutate(|count| *count = count.saturating_sub(1));
+            },
+        }
+
+        Self::remove_from_book(&order.order_type, order.id);
+        <OpenOrderCount<T>>::mutate(&order.creator, |count| *count = count.saturating_sub(1));
+
+        // Mettre à jour le statut de l'ordre
+        let order_id = order.id;
+        order.status = status;
+        match order.order_type.clone() {
+            OrderType::Buy => <BuyOrders<T>>::insert(order_id, order),
+            OrderType::Sell => <SellOrders<T>>::insert(order_id, order),
+        }
+
+        Ok(())
+    }
+
+    /// Clôturer les ordres dont l'expiration est programmée à ce bloc
+    fn expire_orders(current_block: T::BlockNumber) -> u32 {
+        let mut expired = 0u32;
+
+        for (order_id, order_type) in <OrderExpiries<T>>::take(current_block) {
+            let order = match order_type {
+                OrderType::Buy if <BuyOrders<T>>::contains_key(order_id) => <BuyOrders<T>>::get(order_id),
+                OrderType::Sell if <SellOrders<T>>::contains_key(order_id) => <SellOrders<T>>::get(order_id),
+                _ => continue,
             };
-            
-            // Enregistrer l'ordre
-            <SellOrders<T>>::insert(order_id, order);
-            
-            // Mettre à jour la liste des ordres du compte
-            account_orders.push(order_id);
-            <AccountOrders<T>>::insert(&creator, account_orders);
-            
-            // Mettre à jour le compteur d'ordres
-            <TotalSellOrders>::mutate(|count| *count += 1);
-            
-            // Mettre à jour le meilleur prix de vente si nécessaire
-            let best_sell = <BestSellPrice<T>>::get();
-            if best_sell.is_zero() || price < best_sell {
-                <BestSellPrice<T>>::put(price);
+
+            // Les ordres exécutés ou annulés entre-temps sont ignorés
+            if !Self::is_open(&order.status) {
+                continue;
+            }
+
+            if Self::close_order(order, OrderStatus::Expired).is_ok() {
+                expired += 1;
+
+                // Émettre un événement
+                Self::deposit_event(RawEvent::OrderExpired(order_id, order_type));
             }
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::SellOrderCreated(
-                order_id,
-                creator,
-                quantity,
-                price
-            ));
-            
-            // Tenter de faire correspondre cet ordre avec des ordres d'achat existants
-            Self::try_match_sell_order(order_id)?;
-            
-            Ok(())
         }
-        
-        /// Annuler un ordre existant
-        #[weight = 10_000]
-        pub fn cancel_order(
-            origin,
-            order_id: [u8; 32],
-            order_type: OrderType,
-        ) -> DispatchResult {
-            let canceller = ensure_signed(origin)?;
-            
-            // Vérifier que l'ordre existe et est actif
-            match order_type {
-                OrderType::Buy => {
-                    ensure!(<BuyOrders<T>>::contains_key(order_id), Error::<T>::OrderNotFound);
-                    let order = <BuyOrders<T>>::get(order_id);
-                    
-                    // Vérifier que le canceller est le créateur de l'ordre
-                    ensure!(order.creator == canceller, Error::<T>::Unauthorized);
-                    ensure!(order.status == OrderStatus::Active, Error::<T>::OrderNotActive);
-                    
-                    // Libérer les fonds réservés
-                    let reserved_amount = order.quantity.saturating_mul(order.price);
-                    T::Currency::unreserve(&order.creator, reserved_amount);
-                    
-                    // Mettre à jour le statut de l'ordre
-                    let mut updated_order = order;
-                    updated_order.status = OrderStatus::Cancelled;
-                    <BuyOrders<T>>::insert(order_id, updated_order);
-                    
-                    // Mettre à jour le compteur d'ordres
-                    <TotalBuyOrders>::mutate(|count| *count = count.saturating_sub(1));
-                },
-                OrderType::Sell => {
-                    ensure!(<SellOrders<T>>::contains_key(order_id), Error::<T>::OrderNotFound);
-                    let order = <SellOrders<T>>::get(order_id);
-                    
-                    // Vérifier que le canceller est le créateur de l'ordre
-                    ensure!(order.creator == canceller, Error::<T>::Unauthorized);
-                    ensure!(order.status == OrderStatus::Active, Error::<T>::OrderNotActive);
-                    
-                    // Libérer les tokens réservés
-                    let _ = T::TokenSystem::unlock_tokens(
-                        &order.creator.clone().try_into().map_err(|_| Error::<T>::IncompatibleActorType)?,
-                    ).map_err(|_| Error::<T>::InsufficientFunds)?;
-                    
-                    // Mettre à jour le statut de l'ordre
-                    let mut updated_order = order;
-                    updated_order.status = OrderStatus::Cancelled;
-                    <SellOrders<T>>::insert(order_id, updated_order);
-                    
-                    // Mettre à jour le compteur d'ordres
-                    <TotalSellOrders>::mutate(|count| *count = count.saturating_sub(1));
+
+        expired
+    }
+
+    /// Insérer un ordre dans le carnet trié (priorité prix puis ancienneté)
+    fn insert_into_book(order_type: &OrderType, price: BalanceOf<T>, order_id: [u8; 32], creator: T::AccountId) {
+        match order_type {
+            OrderType::Buy => <BuyBook<T>>::mutate(|book| {
+                let position = book.iter().position(|(p, _, _)| *p < price).unwrap_or(book.len());
+                book.insert(position, (price, order_id, creator));
+                <BestBuyPrice<T>>::put(Self::head_price(book));
+            }),
+            OrderType::Sell => <SellBook<T>>::mutate(|book| {
+                let position = book.iter().position(|(p, _, _)| *p > price).unwrap_or(book.len());
+                book.insert(position, (price, order_id, creator));
+                <BestSellPrice<T>>::put(Self::head_price(book));
+            }),
+        }
+    }
+
+    /// Retirer un ordre du carnet
+    fn remove_from_book(order_type: &OrderType, order_id: [u8; 32]) {
+        match order_type {
+            OrderType::Buy => <BuyBook<T>>::mutate(|book| {
+                book.retain(|(_, id, _)| *id != order_id);
+                <BestBuyPrice<T>>::put(Self::head_price(book));
+            }),
+            OrderType::Sell => <SellBook<T>>::mutate(|book| {
+                book.retain(|(_, id, _)| *id != order_id);
+                <BestSellPrice<T>>::put(Self::head_price(book));
+            }),
+        }
+    }
+
+    /// Prix en tête d'un carnet (zéro si le carnet est vide)
+    fn head_price(book: &[BookEntry<T>]) -> BalanceOf<T> {
+        book.first().map(|(p, _, _)| *p).unwrap_or_else(Zero::zero)
+    }
+
+    /// Vérifier si un nouvel ordre croiserait un ordre opposé ouvert du même créateur
+    ///
+    /// Le créateur est conservé dans le carnet: la vérification ne lit aucun ordre.
+    fn crosses_own_order(creator: &T::AccountId, order_type: &OrderType, price: BalanceOf<T>) -> bool {
+        match order_type {
+            OrderType::Buy => <SellBook<T>>::get().iter()
+                .take_while(|(p, _, _)| *p <= price)
+                .any(|(_, _, owner)| owner == creator),
+            OrderType::Sell => <BuyBook<T>>::get().iter()
+                .take_while(|(p, _, _)| *p >= price)
+                .any(|(_, _, owner)| owner == creator),
+        }
+    }
+
+    /// Faire correspondre les têtes des carnets d'achat et de vente
+    ///
+    /// Exécute au plus `limit` échanges. Le prix d'exécution est celui de l'ordre déjà présent
+    /// dans le carnet (celui qui n'est pas `taker`); sans `taker`, le prix de vente est retenu.
+    /// Un échange dont le règlement échoue est annulé dans son ensemble et l'ordre défaillant est
+    /// retiré du carnet, afin de ne pas bloquer la correspondance des ordres suivants.
+    /// Retourne le nombre d'opérations effectuées (échanges exécutés ou ordres défaillants retirés).
+    fn match_books(taker: Option<[u8; 32]>, limit: u32) -> u32 {
+        let mut matched = 0u32;
+
+        while matched < limit {
+            let (buy_price, buy_order_id) = match <BuyBook<T>>::get().first() {
+                Some((price, order_id, _)) => (*price, *order_id),
+                None => break,
+            };
+            let (sell_price, sell_order_id) = match <SellBook<T>>::get().first() {
+                Some((price, order_id, _)) => (*price, *order_id),
+                None => break,
+            };
+
+            // Les carnets ne se croisent plus
+            if buy_price < sell_price {
+                break;
+            }
+
+            let buy_order = <BuyOrders<T>>::get(buy_order_id);
+            let sell_order = <SellOrders<T>>::get(sell_order_id);
+
+            let trade_price = if taker == Some(sell_order_id) { buy_price } else { sell_price };
+            let match_quantity = buy_order.quantity.min(sell_order.quantity);
+
+            match Self::execute_trade(buy_order.clone(), sell_order.clone(), match_quantity, trade_price) {
+                Ok(()) => {}
+                Err(error) if error == Error::<T>::TradeRejected.into() => {
+                    // Le refus a été annulé avec l'échange: le contrôle est rejoué hors transaction
+                    // pour conserver le déclenchement du circuit-breaker, sans enregistrer l'échange
+                    let _ = T::TradeMonitor::check_trade(
+                        match_quantity.saturating_mul(trade_price).saturated_into::<Balance>(),
+                        trade_price.saturated_into::<Balance>()
+                    );
+                    Self::deposit_event(RawEvent::MatchingSuspended(trade_price));
+                    break;
+                }
+                Err(error) => {
+                    // Retirer l'ordre dont le règlement a échoué et restituer ses réserves
+                    let failed_order = if error == Error::<T>::BuyerSettlementFailed.into() { buy_order } else { sell_order };
+                    let (failed_id, failed_type) = (failed_order.id, failed_order.order_type.clone());
+                    if Self::close_order(failed_order, OrderStatus::Cancelled).is_err() {
+                        Self::remove_from_book(&failed_type, failed_id);
+                    }
+                    Self::deposit_event(RawEvent::OrderCancelled(failed_id, failed_type));
                 }
             }
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::OrderCancelled(order_id, order_type));
-            
-            Ok(())
+
+            matched += 1;
         }
-        
-        /// Créer un nouveau produit financier
-        #[weight = 10_000]
-        pub fn create_financial_product(
-            origin,
-            name: Vec<u8>,
-            description: Vec<u8>,
-            product_type: ProductType,
-            expected_yield: u32,
-            min_investment_duration: u64,
-            min_investment_amount: BalanceOf<T>,
-            risk_level: u8,
-        ) -> DispatchResult {
-            let creator = ensure_signed(origin)?;
-            
-            // Vérifier que l'acteur est autorisé (devrait être le fonds des consommateurs ou un administrateur)
-            // Dans une implémentation réelle, il faudrait un système d'autorisation plus sophistiqué
-            
-            // Vérifier que les paramètres du produit sont valides
-            ensure!(expected_yield > 0, Error::<T>::InvalidExpectedYield);
-            ensure!(min_investment_duration > 0, Error::<T>::InvalidInvestmentDuration);
-            ensure!(min_investment_amount > Zero::zero(), Error::<T>::AmountTooSmall);
-            ensure!(risk_level >= 1 && risk_level <= 5, Error::<T>::InvalidProductType);
-            
-            // Vérifier que nous n'avons pas trop de produits actifs
-            let active_products = <TotalActiveProducts>::get();
-            ensure!(
-                active_products < T::MaxActiveProducts::get(),
-                Error::<T>::TooManyActiveProducts
-            );
-            
-            // Prélever les frais de création
-            T::Currency::transfer(
-                &creator,
-                &T::FeeAccount::get(),
-                T::ProductCreationFee::get(),
-                ExistenceRequirement::KeepAlive
-            )?;
-            
-            <TotalFeesCollected<T>>::mutate(|total| {
-                *total = total.saturating_add(T::ProductCreationFee::get());
-            });
-            
-            // Créer l'ID du produit
-            let product_id = Self::generate_product_id(&creator, &name, product_type);
-            
-            // Créer le produit financier
-            let product = FinancialProduct {
-                id: product_id,
-                name: name.clone(),
-                description,
-                product_type,
-                expected_yield,
-                min_investment_duration,
-                min_investment_amount: min_investment_amount.saturated_into::<Balance>(),
-                total_invested: 0,
-                risk_level,
-                created_at: Self::get_current_timestamp(),
-                status: ProductStatus::Open,
-            };
-            
-            // Enregistrer le produit
-            <FinancialProducts<T>>::insert(product_id, product);
-            
-            // Mettre à jour la liste des produits par type
-            <ProductsByType<T>>::mutate(product_type, |products| {
-                products.push(product_id);
-            });
-            
-            // Mettre à jour le compteur de produits
-            <TotalActiveProducts>::mutate(|count| *count += 1);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::FinancialProductCreated(
-                product_id,
-                creator,
-                name,
-                product_type,
-                expected_yield
-            ));
-            
-            Ok(())
+
+        matched
+    }
+
+    /// Exécuter un échange entre deux ordres ouverts
+    ///
+    /// Le règlement est atomique: en cas d'échec, aucun transfert ni aucune réserve n'est modifié.
+    /// L'échange n'est soumis aux circuit-breakers qu'une fois les transferts effectués, afin
+    /// qu'ils n'enregistrent que des échanges réellement réglés; un refus annule le règlement.
+    #[transactional]
+    fn execute_trade(
+        mut buy_order: MarketOrder,
+        mut sell_order: MarketOrder,
+        match_quantity: BalanceOf<T>,
+        trade_price: BalanceOf<T>,
+    ) -> DispatchResult {
+        // Calculer le montant total
+        let total_amount = match_quantity.saturating_mul(trade_price);
+
+        // Calculer les frais de marché
+        let fee_amount = T::MarketplaceFee::get() * total_amount;
+        let net_amount = total_amount.saturating_sub(fee_amount);
+
+        // 1. Transférer les tokens réservés du vendeur à l'acheteur
+        let seller = Self::token_account(&sell_order.creator)?;
+        let buyer = Self::token_account(&buy_order.creator)?;
+        let token_amount = match_quantity.saturated_into::<Balance>();
+
+        T::TokenSystem::unreserve_tokens(&seller, token_amount)
+            .map_err(|_| Error::<T>::SellerSettlementFailed)?;
+        T::TokenSystem::transfer_tokens(&seller, &buyer, token_amount)
+            .map_err(|_| Error::<T>::SellerSettlementFailed)?;
+
+        // 2. Libérer les fonds réservés de l'acheteur au prix de son ordre
+        T::Currency::unreserve(&buy_order.creator, match_quantity.saturating_mul(buy_order.price));
+
+        // 3. Transférer les frais au compte des frais
+        T::Currency::transfer(
+            &buy_order.creator,
+            &T::FeeAccount::get(),
+            fee_amount,
+            ExistenceRequirement::KeepAlive
+        ).map_err(|_| Error::<T>::BuyerSettlementFailed)?;
+
+        // 4. Transférer le montant net au vendeur
+        T::Currency::transfer(
+            &buy_order.creator,
+            &sell_order.creator,
+            net_amount,
+            ExistenceRequirement::KeepAlive
+        ).map_err(|_| Error::<T>::BuyerSettlementFailed)?;
+
+        // 5. Soumettre l'échange réglé aux circuit-breakers (enregistre aussi le dernier prix)
+        T::TradeMonitor::check_trade(
+            total_amount.saturated_into::<Balance>(),
+            trade_price.saturated_into::<Balance>()
+        ).map_err(|_| Error::<T>::TradeRejected)?;
+
+        // Mettre à jour les ordres
+        Self::apply_fill(&mut buy_order, match_quantity);
+        Self::apply_fill(&mut sell_order, match_quantity);
+
+        let buy_order_id = buy_order.id;
+        let sell_order_id = sell_order.id;
+        <BuyOrders<T>>::insert(buy_order_id, buy_order);
+        <SellOrders<T>>::insert(sell_order_id, sell_order);
+
+        // Mettre à jour les statistiques
+        <TotalTradingVolume<T>>::mutate(|volume| {
+            *volume = volume.saturating_add(total_amount);
+        });
+
+        <TotalFeesCollected<T>>::mutate(|fees| {
+            *fees = fees.saturating_add(fee_amount);
+        });
+
+        // Mettre à jour l'historique des prix
+        Self::update_price_history(trade_price);
+
+        // Émettre un événement
+        Self::deposit_event(RawEvent::TradeExecuted(
+            buy_order_id,
+            sell_order_id,
+            match_quantity,
+            trade_price
+        ));
+
+        Ok(())
+    }
+
+    /// Déduire la quantité exécutée d'un ordre et le retirer du carnet s'il est entièrement exécuté
+    fn apply_fill(order: &mut MarketOrder, match_quantity: BalanceOf<T>) {
+        order.quantity = order.quantity.saturating_sub(match_quantity);
+
+        if !order.quantity.is_zero() {
+            order.status = OrderStatus::PartiallyFilled;
+            return;
         }
-        
-        /// Investir dans un produit financier
-        #[weight = 10_000]
-        pub fn invest_in_product(
-            origin,
-            product_id: [u8; 32],
-            amount: BalanceOf<T>,
-        ) -> DispatchResult {
-            let investor = ensure_signed(origin)?;
-            
-            // Vérifier que le produit existe et est ouvert
-            ensure!(<FinancialProducts<T>>::contains_key(product_id), Error::<T>::ProductNotFound);
-            let mut product = <FinancialProducts<T>>::get(product_id);
-            ensure!(product.status == ProductStatus::Open, Error::<T>::ProductNotOpen);
-            
-            // Vérifier que le montant est suffisant
-            ensure!(
-                amount >= product.min_investment_amount.saturated_into::<BalanceOf<T>>(),
-                Error::<T>::AmountTooSmall
-            );
-            
-            // Vérifier que l'investisseur a suffisamment de fonds
-            ensure!(
-                T::Currency::free_balance(&investor) >= amount,
-                Error::<T>::InsufficientFunds
-            );
-            
-            // Transférer les fonds vers le compte du produit (à implémenter)
-            // Dans une implémentation réelle, chaque produit aurait son propre compte multisig
-            // Pour l'exemple, nous utilisons le compte des frais comme destination
-            T::Currency::transfer(
-                &investor,
-                &T::FeeAccount::get(),
-                amount,
-                ExistenceRequirement::KeepAlive
-            )?;
-            
-            // Mettre à jour l'investissement
-            <Investments<T>>::mutate(investor.clone(), product_id, |invested| {
-                *invested = invested.saturating_add(amount);
-            });
-            
-            // Mettre à jour le total investi dans le produit
-            product.total_invested = product.total_invested.saturating_add(amount.saturated_into::<Balance>());
-            <FinancialProducts<T>>::insert(product_id, product);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::InvestmentMade(
-                investor,
-                product_id,
-                amount
-            ));
-            
-            Ok(())
+
+        order.status = OrderStatus::Filled;
+        Self::remove_from_book(&order.order_type, order.id);
+        <OpenOrderCount<T>>::mutate(&order.creator, |count| *count = count.saturating_sub(1));
+
+        match order.order_type {
+            OrderType::Buy => <TotalBuyOrders>::mutate(|count| *count = count.saturating_sub(1)),
+            OrderType::Sell => <TotalSellOrders>::mutate(|count| *count = count.saturating_sub(1)),
         }
+    }
+
+    /// Mettre à jour l'historique des prix et la moyenne des prix
+    fn update_price_history(price: BalanceOf<T>) {
+        // Mettre à jour l'historique des prix
+        let mut history = <PriceHistory<T>>::get();
         
-        /// Fermer un produit financier aux nouveaux investissements
-        #[weight = 10_000]
-        pub fn close_product(
-            origin,
-            product_id: [u8; 32],
-        ) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
-            
-            // Vérifier que le produit existe
-            ensure!(<FinancialProducts<T>>::contains_key(product_id), Error::<T>::ProductNotFound);
-            let mut product = <FinancialProducts<T>>::get(product_id);
-            
-            // Vérifier que le produit est ouvert
-            ensure!(product.status == ProductStatus::Open, Error::<T>::ProductNotOpen);
-            
-            // Vérifier que l'appelant est autorisé (créateur du produit ou administrateur)
-            // Dans une implémentation réelle, il faudrait un système d'autorisation plus sophistiqué
-            
-            // Mettre à jour le statut du produit
-            product.status = ProductStatus::Closed;
-            <FinancialProducts<T>>::insert(product_id, product);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::ProductStatusUpdated(
-                product_id,
-                ProductStatus::Closed
-            ));
-            
-            Ok(())
+        // Ajouter la nouvelle entrée
+        let current_block = <frame_system::Module<T>>::block_number();
+        history.push((price, current_block));
+        
+        // Limiter la taille de l'historique aux 100 dernières entrées
+        if history.len() > 100 {
+            history.remove(0);
         }
         
-        /// Distribuer un rendement aux investisseurs d'un produit
-        #[weight = 10_000]
-        pub fn distribute_yield(
-            origin,
-            product_id: [u8; 32],
-            total_yield: BalanceOf<T>,
-        ) -> DispatchResult {
-            let distributor = ensure_signed(origin)?;
-            
-            // Vérifier que le produit existe
-            ensure!(<FinancialProducts<T>>::contains_key(product_id), Error::<T>::ProductNotFound);
-            let product = <FinancialProducts<T>>::get(product_id);
-            
-            // Vérifier que le produit est fermé ou mature
-            ensure!(
-                product.status == ProductStatus::Closed || product.status == ProductStatus::Matured,
-                Error::<T>::ProductNotOpen
-            );
-            
-            // Vérifier que le distributeur a suffisamment de fonds
-            ensure!(
-                T::Currency::free_balance(&distributor) >= total_yield,
-                Error::<T>::InsufficientFunds
-            );
-            
-            // Dans une implémentation réelle, ici nous distribuerions le rendement
-            // à tous les investisseurs proportionnellement à leur investissement
-            // Pour l'exemple, nous émettons simplement un événement
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::YieldDistributed(
-                product_id,
-                total_yield
-            ));
-            
-            Ok(())
+        <PriceHistory<T>>::put(history.clone());
+        
+        // Calculer la moyenne des prix
+        if !history.is_empty() {
+            let total_price: BalanceOf<T> = history.iter()
+                .map(|(p, _)| *p)
+                .fold(Zero::zero(), |acc, p| acc.saturating_add(p));
+                
+            let average_price = total_price / history.len().saturated_into::<BalanceOf<T>>();
+            <AverageTokenPrice<T>>::put(average_price);
         }
     }
 }
 
-impl<T: Config> Module<T> {
-    /// Générer un ID unique pour un ordre
-    fn generate_order_id(creator: &T::AccountId, order_type: OrderType, quantity: BalanceOf<T>, price: BalanceOf<T>) -> [u8; 32] {
-        let timestamp = Self::get_current_timestamp();
-        let mut data = Vec::new();
-        
-        data.extend_from_slice(&creator.encode());
-        data.extend_from_slice(&(order_type as u8).to_be_bytes());
-        data.extend_from_slice(&quantity.saturated_into::<u128>().to_be_bytes());
-        data.extend_from_slice(&price.saturated_into::<u128>().to_be_bytes());
-        data.extend_from_slice(&timestamp.to_be_bytes());
-        
-        let hash = sp_io::hashing::blake2_256(&data);
-        hash
+/// Implémentation du trait Marketplace pour le module marketplace
+impl<T: Config> Marketplace for Module<T> {
+    fn create_order(
+        creator: &AccountId,
+        order_type: OrderType,
+        quantity: Balance,
+        price: Balance,
+        expiration: Moment,
+    ) -> Result<[u8; 32], &'static str> {
+        // Convertir les types génériques en types spécifiques au runtime
+        let creator_id = creator.clone().try_into().map_err(|_| "Invalid creator ID")?;
+
+        // L'expiration est exprimée en numéro de bloc
+        let order_id = Self::do_create_order(
+            creator_id,
+            order_type,
+            quantity.saturated_into::<BalanceOf<T>>(),
+            price.saturated_into::<BalanceOf<T>>(),
+            expiration.saturated_into::<T::BlockNumber>(),
+        ).map_err(|_| "Order creation failed")?;
+
+        Self::match_books(Some(order_id), T::MaxMatchesPerBlock::get());
+
+        Ok(order_id)
+    }
+
+    fn cancel_order(order_id: [u8; 32], caller: &AccountId) -> Result<(), &'static str> {
+        // Convertir les types génériques en types spécifiques au runtime
+        let caller_id = caller.clone().try_into().map_err(|_| "Invalid caller ID")?;
+
+        let order_type = if <BuyOrders<T>>::contains_key(order_id) {
+            OrderType::Buy
+        } else if <SellOrders<T>>::contains_key(order_id) {
+            OrderType::Sell
+        } else {
+            return Err("Order not found");
+        };
+
+        Self::do_cancel_order(caller_id, order_id, order_type).map_err(|_| "Order cancellation failed")
+    }
+
+    fn match_orders() -> Result<(), &'static str> {
+        Self::match_books(None, T::MaxMatchesPerBlock::get());
+        Ok(())
     }
+}
+
+/// Tests unitaires pour le module marketplace
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_support::{assert_ok, assert_noop, parameter_types, traits::OnInitialize};
+    use sp_core::H256;
+    use sp_runtime::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill, ModuleId,
+    };
     
-    /// Générer un ID unique pour un produit financier
-    fn generate_product_id(creator: &T::AccountId, name: &[u8], product_type: ProductType) -> [u8; 32] {
-        let timestamp = Self::get_current_timestamp();
-        let mut data = Vec::new();
-        
-        data.extend_from_slice(&creator.encode());
-        data.extend_from_slice(name);
-        data.extend_from_slice(&(product_type as u8).to_be_bytes());
-        data.extend_from_slice(&timestamp.to_be_bytes());
-        
-        let hash = sp_io::hashing::blake2_256(&data);
-        hash
+    type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+    type Block = frame_system::mocking::MockBlock<Test>;
+    
+    frame_support::construct_runtime!(
+        pub enum Test where
+            Block = Block,
+            NodeBlock = Block,
+            UncheckedExtrinsic = UncheckedExtrinsic,
+        {
+            System: frame_system::{Module, Call, Config, Storage, Event<T>},
+            Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+            EtikaMarketplace: Module<Test>,
+        }
+    );
+    
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+        pub const ExistentialDeposit: u64 = 1;
     }
     
-    /// Obtenir le timestamp actuel en secondes
-    fn get_current_timestamp() -> Moment {
-        let now = sp_io::offchain::timestamp()
-            .unwrap_or_default()
-            .unix_millis();
-        (now / 1000) as Moment
+    impl frame_system::Config for Test {
+        type BaseCallFilter = ();
+        type BlockWeights = ();
+        type BlockLength = ();
+        type DbWeight = ();
+        type Origin = Origin;
+        type Index = u64;
+        type BlockNumber = u64;
+        type Call = Call;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = Event;
+        type BlockHashCount = BlockHashCount;
+        type Version = ();
+        type PalletInfo = PalletInfo;
+        type AccountData = pallet_balances::AccountData<u64>;
+        type OnNewAccount = ();
+        type OnKilledAccount = ();
+        type SystemWeightInfo = ();
+        type SS58Prefix = ();
     }
     
-    /// Nettoyer les ordres expirés
-    fn clean_expired_orders(current_block: T::BlockNumber) {
-        // Nettoyer les ordres d'achat expirés
-        for (order_id, order) in <BuyOrders<T>>::iter() {
-            if order.status == OrderStatus::Active && 
-               current_block.saturated_into::<Moment>() > order.expiration {
-                // Libérer les fonds réservés
-                let reserved_amount = order.quantity.saturating_mul(order.price);
-                T::Currency::unreserve(&order.creator, reserved_amount);
-                
-                // Mettre à jour le statut de l'ordre
-                let mut updated_order = order;
-                updated_order.status = OrderStatus::Expired;
-                <BuyOrders<T>>::insert(order_id, updated_order);
-                
-                // Mettre à jour le compteur d'ordres
-                <TotalBuyOrders>::mutate(|count| *count = count.saturating_sub(1));
-                
-                // Émettre un événement
-                Self::deposit_event(RawEvent::OrderExpired(order_id, OrderType::Buy));
-            }
-        }
-        
-        // Nettoyer les ordres de vente expirés
-        for (order_id, order) in <SellOrders<T>>::iter() {
-            if order.status == OrderStatus::Active && 
-               current_block.saturated_into::<Moment>() > order.expiration {
-                // Libérer les tokens réservés
-                let _ = T::TokenSystem::unlock_tokens(
-                    &order.creator.clone().try_into().map_err(|_| "Conversion error").unwrap_or_default(),
-                );
-                
-                // Mettre à jour le statut de l'ordre
-                let mut updated_order = order;
-                updated_order.status = OrderStatus::Expired;
-                <SellOrders<T>>::insert(order_id, updated_order);
-                
-                // Mettre à jour le compteur d'ordres
-                <TotalSellOrders>::mutate(|count| *count = count.saturating_sub(1));
-                
-                // Émettre un événement
-                Self::deposit_event(RawEvent::OrderExpired(order_id, OrderType::Sell));
-            }
-        }
+    parameter_types! {
+        pub const MinimumPeriod: u64 = 1000;
+        pub const MaxLocks: u32 = 50;
+    }
+    
+    impl pallet_balances::Config for Test {
+        type MaxLocks = MaxLocks;
+        type Balance = u64;
+        type Event = Event;
+        type DustRemoval = ();
+        type ExistentialDeposit = ExistentialDeposit;
+        type AccountStore = System;
+        type WeightInfo = ();
     }
     
-    /// Tenter de faire correspondre un nouvel ordre d'achat avec des ordres de vente existants
-    fn try_match_buy_order(buy_order_id: [u8; 32]) -> DispatchResult {
-        // Vérifier que l'ordre existe toujours
-        if !<BuyOrders<T>>::contains_key(buy_order_id) {
-            return Ok(());
+    // Mock implementation for TokenSystem
+    pub struct MockTokenSystem;
+    
+    impl TokenSystem for MockTokenSystem {
+        fn distribute_tokens(_to: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
         }
-        
-        let mut buy_order = <BuyOrders<T>>::get(buy_order_id);
-        
-        // Si l'ordre n'est plus actif, ne rien faire
-        if buy_order.status != OrderStatus::Active {
-            return Ok(());
+    
+        fn activate_tokens(_from: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
         }
-        
-        // Rechercher des ordres de vente compatibles (prix <= prix d'achat, par ordre de prix croissant)
-        let mut matched_sell_orders = Vec::new();
-        let mut remaining_quantity = buy_order.quantity;
-        
-        for (sell_order_id, sell_order) in <SellOrders<T>>::iter() {
-            // Vérifier que l'ordre est actif et compatible
-            if sell_order.status == OrderStatus::Active && 
-               sell_order.price <= buy_order.price &&
-               sell_order.creator != buy_order.creator { // Pas de self-trading
-                
-                let match_quantity = remaining_quantity.min(sell_order.quantity);
-                
-                if match_quantity > Zero::zero() {
-                    matched_sell_orders.push((sell_order_id, sell_order, match_quantity));
-                    remaining_quantity = remaining_quantity.saturating_sub(match_quantity);
-                    
-                    // Si quantité restante = 0, on peut s'arrêter
-                    if remaining_quantity.is_zero() {
-                        break;
-                    }
-                }
-            }
+    
+        fn deactivate_tokens(_from: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
         }
-        
-        // Exécuter les correspondances
-        for (sell_order_id, mut sell_order, match_quantity) in matched_sell_orders {
-            // Calculer le prix de la transaction (généralement le prix de l'ordre placé en premier)
-            let trade_price = sell_order.price;
-            
-            // Calculer le montant total
-            let total_amount = match_quantity.saturating_mul(trade_price);
-            
-            // Calculer les frais de marché
-            let fee_amount = T::MarketplaceFee::get() * total_amount;
-            let net_amount = total_amount.saturating_sub(fee_amount);
-            
-            // Effectuer les transferts
-            
-            // 1. Libérer les fonds réservés de l'acheteur
-            T::Currency::unreserve(&buy_order.creator, total_amount);
-            
-            // 2. Transférer les frais au compte des frais
-            T::Currency::transfer(
-                &buy_order.creator,
-                &T::FeeAccount::get(),
-                fee_amount,
-                ExistenceRequirement::KeepAlive
-            )?;
-            
-            // 3. Transférer le montant net au vendeur
-            T::Currency::transfer(
-                &buy_order.creator,
-                &sell_order.creator,
-                net_amount,
-                ExistenceRequirement::KeepAlive
-            )?;
-            
-            // 4. Transférer les tokens du vendeur à l'acheteur
-            // Pour une implémentation réelle, il faudrait adapter ce code au vrai système de tokens
-            let _ = T::TokenSystem::transfer_tokens(
-                &sell_order.creator.clone().try_into().map_err(|_| Error::<T>::IncompatibleActorType)?,
-                &buy_order.creator.clone().try_into().map_err(|_| Error::<T>::IncompatibleActorType)?,
-                match_quantity.saturated_into::<Balance>()
-            ).map_err(|_| Error::<T>::InsufficientFunds)?;
-            
-            // Mettre à jour les ordres
-            
-            // Ordre d'achat
-            buy_order.quantity = buy_order.quantity.saturating_sub(match_quantity);
-            if buy_order.quantity.is_zero() {
-                buy_order.status = OrderStatus::Filled;
-                <BuyOrders<T>>::insert(buy_order_id, buy_order.clone());
-                <TotalBuyOrders>::mutate(|count| *count = count.saturating_sub(1));
-            } else {
-                buy_order.status = OrderStatus::PartiallyFilled;
-                <BuyOrders<T>>::insert(buy_order_id, buy_order.clone());
-            }
-            
-            // Ordre de vente
-            sell_order.quantity = sell_order.quantity.saturating_sub(match_quantity);
-            if sell_order.quantity.is_zero() {
-                sell_order.status = OrderStatus::Filled;
-                <SellOrders<T>>::insert(sell_order_id, sell_order.clone());
-                <TotalSellOrders>::mutate(|count| *count = count.saturating_sub(1));
-            } else {
-                sell_order.status = OrderStatus::PartiallyFilled;
-                <SellOrders<T>>::insert(sell_order_id, sell_order.clone());
-            }
-            
-            // Mettre à jour les statistiques
-            <TotalTradingVolume<T>>::mutate(|volume| {
-                *volume = volume.saturating_add(total_amount);
-            });
-            
-            <TotalFeesCollected<T>>::mutate(|fees| {
-                *fees = fees.saturating_add(fee_amount);
-            });
-            
-            // Mettre à jour l'historique des prix
-            Self::update_price_history(trade_price);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::TradeExecuted(
-                buy_order_id,
-                sell_order_id,
-                match_quantity,
-                trade_price
-            ));
+    
+        fn burn_tokens(_from: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
         }
-        
-        Ok(())
-    }
     
-    /// Tenter de faire correspondre un nouvel ordre de vente avec des ordres d'achat existants
-    fn try_match_sell_order(sell_order_id: [u8; 32]) -> DispatchResult {
-        // Vérifier que l'ordre existe toujours
-        if !<SellOrders<T>>::contains_key(sell_order_id) {
-            return Ok(());
+        fn transfer_tokens(_from: &AccountId, _to: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
         }
-        
-        let mut sell_order = <SellOrders<T>>::get(sell_order_id);
-        
-        // Si l'ordre n'est plus actif, ne rien faire
-        if sell_order.status != OrderStatus::Active {
-            return Ok(());
+
+        fn active_balance(_account: &AccountId) -> Balance {
+            10000
         }
-        
-        // Rechercher des ordres d'achat compatibles (prix >= prix de vente, par ordre de prix décroissant)
-        let mut matched_buy_orders = Vec::new();
-        let mut remaining_quantity = sell_order.quantity;
-        
-        // Trier les ordres d'achat par prix décroissant
-        let mut buy_orders: Vec<(_, _)> = <BuyOrders<T>>::iter().collect();
-        buy_orders.sort_by(|(_, a), (_, b)| b.price.cmp(&a.price));
-        
-        for (buy_order_id, buy_order) in buy_orders {
-            // Vérifier que l'ordre est actif et compatible
-            if buy_order.status ==// etika-marketplace/src/lib.rs
-//
-// Ce module implémente la place de marché de l'écosystème Étika:
-// - Échange de tokens entre participants
-// - Gestion des ordres d'achat et de vente
-// - Produits financiers basés sur l'épargne des consommateurs
-// - Mécanismes de liquidité et de détermination des prix
 
-#![cfg_attr(not(feature = "std"), no_std)]
+        fn reserve_tokens(_account: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
 
-use codec::{Decode, Encode};
-use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult,
-    ensure, traits::{Currency, ExistenceRequirement, Get, ReservableCurrency, WithdrawReasons},
-    Parameter,
-};
-use frame_system::{self as system, ensure_signed};
-use sp_runtime::{
-    traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Member, Zero, SaturatedConversion},
-    DispatchError, Perbill, RuntimeDebug,
-};
-use sp_std::prelude::*;
-
-// Import des structures de données définies dans etika-data-structure
-use etika_data_structure::{
-    AccountId, Balance, Moment, ActorType, MarketOrder, OrderType, OrderStatus,
-    FinancialProduct, ProductType, ProductStatus, Marketplace, TokenSystem,
-};
-
-/// Type monétaire utilisé pour le module
-type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-
-/// Configuration du module marketplace
-pub trait Config: frame_system::Config {
-    /// Type d'événement
-    type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-    
-    /// Type de monnaie utilisé
-    type Currency: ReservableCurrency<Self::AccountId>;
-    
-    /// Système de tokens
-    type TokenSystem: TokenSystem;
-    
-    /// Frais de transaction sur la place de marché (en pourcentage)
-    type MarketplaceFee: Get<Perbill>;
+        fn unreserve_tokens(_account: &AccountId, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
+    }
     
-    /// Compte destinataire des frais de transaction
-    type FeeAccount: Get<Self::AccountId>;
+    thread_local! {
+        static LAST_TRADE_PRICE: std::cell::RefCell<Balance> = std::cell::RefCell::new(0);
+        static TRADING_HALTED: std::cell::RefCell<bool> = std::cell::RefCell::new(false);
+    }
     
-    /// Montant minimum pour un ordre sur la place de marché
-    type MinOrderAmount: Get<BalanceOf<Self>>;
+    // Mock implementation for TradeMonitor
+    pub struct MockTradeMonitor;
     
-    /// Durée maximale d'un ordre (en blocs)
-    type MaxOrderDuration: Get<Self::BlockNumber>;
+    impl TradeMonitor for MockTradeMonitor {
+        fn check_trade(_volume: Balance, price: Balance) -> Result<(), &'static str> {
+            if TRADING_HALTED.with(|halted| *halted.borrow()) {
+                return Err("Trade rejected by circuit breaker");
+            }
+            LAST_TRADE_PRICE.with(|last| *last.borrow_mut() = price);
+            Ok(())
+        }
+    }
     
-    /// Nombre maximum d'ordres par compte
-    type MaxOrdersPerAccount: Get<u32>;
+    parameter_types! {
+        pub const MarketplaceFee: Perbill = Perbill::from_percent(1); // 1%
+        pub const FeeAccountId: u64 = 999;
+        pub const MinOrderAmount: u64 = 100;
+        pub const MaxOrderDuration: u64 = 1000; // 1000 blocs
+        pub const MaxOrdersPerAccount: u32 = 10;
+        pub const MaxActiveProducts: u32 = 100;
+        pub const ProductCreationFee: u64 = 1000;
+        pub const MaxMatchesPerBlock: u32 = 2;
+        pub const MaxBookSize: u32 = 5;
+    }
     
-    /// Nombre maximum de produits financiers actifs
-    type MaxActiveProducts: Get<u32>;
+    impl Config for Test {
+        type Event = Event;
+        type Currency = Balances;
+        type TokenSystem = MockTokenSystem;
+        type MarketplaceFee = MarketplaceFee;
+        type FeeAccount = FeeAccountId;
+        type MinOrderAmount = MinOrderAmount;
+        type MaxOrderDuration = MaxOrderDuration;
+        type MaxOrdersPerAccount = MaxOrdersPerAccount;
+        type MaxActiveProducts = MaxActiveProducts;
+        type ProductCreationFee = ProductCreationFee;
+        type TradeMonitor = MockTradeMonitor;
+        type MaxMatchesPerBlock = MaxMatchesPerBlock;
+        type MaxBookSize = MaxBookSize;
+    }
     
-    /// Frais de création d'un produit financier
-    type ProductCreationFee: Get<BalanceOf<Self>>;
-}
-
-decl_storage! {
-    trait Store for Module<T: Config> as EtikaMarketplace {
-        /// Ordres d'achat actifs
-        BuyOrders get(fn buy_orders): map hasher(blake2_128_concat) [u8; 32] => MarketOrder;
-        
-        /// Ordres de vente actifs
-        SellOrders get(fn sell_orders): map hasher(blake2_128_concat) [u8; 32] => MarketOrder;
-        
-        /// Ordres par compte
-        AccountOrders get(fn account_orders): map hasher(blake2_128_concat) T::AccountId => Vec<[u8; 32]>;
-        
-        /// Produits financiers actifs
-        FinancialProducts get(fn financial_products): map hasher(blake2_128_concat) [u8; 32] => FinancialProduct;
-        
-        /// Produits financiers par type
-        ProductsByType get(fn products_by_type): map hasher(blake2_128_concat) ProductType => Vec<[u8; 32]>;
-        
-        /// Investissements par compte et produit
-        Investments get(fn investments): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) [u8; 32] => BalanceOf<T>;
-        
-        /// Nombre total d'ordres d'achat actifs
-        TotalBuyOrders get(fn total_buy_orders): u32;
-        
-        /// Nombre total d'ordres de vente actifs
-        TotalSellOrders get(fn total_sell_orders): u32;
-        
-        /// Nombre total de produits financiers actifs
-        TotalActiveProducts get(fn total_active_products): u32;
-        
-        /// Volume total des transactions sur la place de marché
-        TotalTradingVolume get(fn total_trading_volume): BalanceOf<T>;
-        
-        /// Frais totaux collectés
-        TotalFeesCollected get(fn total_fees_collected): BalanceOf<T>;
-        
-        /// Prix moyen du token (moyenne mobile sur les 100 dernières transactions)
-        AverageTokenPrice get(fn average_token_price): BalanceOf<T>;
-        
-        /// Historique des prix (limité aux 100 dernières transactions)
-        PriceHistory get(fn price_history): Vec<(BalanceOf<T>, T::BlockNumber)>;
-        
-        /// Meilleur prix d'achat actuel
-        BestBuyPrice get(fn best_buy_price): BalanceOf<T>;
+    // Fonction utilitaire pour créer un environnement de test
+    fn new_test_ext() -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+            
+        pallet_balances::GenesisConfig::<Test> {
+            balances: vec![
+                (1, 1000000),
+                (2, 200000),
+                (3, 300000),
+                (4, 400000),
+                (5, 500000),
+                (999, 1000000), // Compte des frais
+            ],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
         
-        /// Meilleur prix de vente actuel
-        BestSellPrice get(fn best_sell_price): BalanceOf<T>;
+        // Initialiser la moyenne des prix
+        let mut ext = t.into();
+        ext.execute_with(|| {
+            <AverageTokenPrice<Test>>::put(1000);
+            
+            // Enregistrer les types d'acteurs
+            <ActorTypes<Test>>::insert(1, ActorType::Consumer);
+            <ActorTypes<Test>>::insert(2, ActorType::Merchant);
+            <ActorTypes<Test>>::insert(3, ActorType::Supplier);
+            <ActorTypes<Test>>::insert(4, ActorType::NGO);
+            <ActorTypes<Test>>::insert(5, ActorType::Investor);
+        });
         
-        /// Mapping des types d'acteur par compte
-        ActorTypes get(fn actor_types): map hasher(blake2_128_concat) T::AccountId => ActorType;
+        ext
     }
-}
-
-decl_event!(
-    pub enum Event<T> where
-        AccountId = <T as frame_system::Config>::AccountId,
-        Balance = BalanceOf<T>,
-        BlockNumber = <T as frame_system::Config>::BlockNumber,
-    {
-        /// Nouvel ordre d'achat créé
-        /// [order_id, créateur, quantité, prix]
-        BuyOrderCreated([u8; 32], AccountId, Balance, Balance),
-        
-        /// Nouvel ordre de vente créé
-        /// [order_id, créateur, quantité, prix]
-        SellOrderCreated([u8; 32], AccountId, Balance, Balance),
-        
-        /// Ordre annulé
-        /// [order_id, type d'ordre]
-        OrderCancelled([u8; 32], OrderType),
-        
-        /// Ordre expiré
-        /// [order_id, type d'ordre]
-        OrderExpired([u8; 32], OrderType),
-        
-        /// Transaction exécutée
-        /// [ordre achat, ordre vente, quantité, prix]
-        TradeExecuted([u8; 32], [u8; 32], Balance, Balance),
-        
-        /// Nouveau produit financier créé
-        /// [product_id, créateur, nom, type, rendement attendu]
-        FinancialProductCreated([u8; 32], AccountId, Vec<u8>, ProductType, u32),
-        
-        /// Investissement dans un produit financier
-        /// [investisseur, product_id, montant]
-        InvestmentMade(AccountId, [u8; 32], Balance),
-        
-        /// Retrait d'un investissement
-        /// [investisseur, product_id, montant]
-        InvestmentWithdrawn(AccountId, [u8; 32], Balance),
-        
-        /// Statut d'un produit financier modifié
-        /// [product_id, nouveau statut]
-        ProductStatusUpdated([u8; 32], ProductStatus),
-        
-        /// Rendement distribué aux investisseurs
-        /// [product_id, rendement total]
-        YieldDistributed([u8; 32], Balance),
+    
+    #[test]
+    fn test_create_buy_order() {
+        new_test_ext().execute_with(|| {
+            // Créer un ordre d'achat
+            assert_ok!(EtikaMarketplace::create_buy_order(
+                Origin::signed(1),
+                100,    // quantité
+                1500,   // prix
+                100     // expiration
+            ));
+            
+            // Vérifier le nombre d'ordres
+            assert_eq!(EtikaMarketplace::total_buy_orders(), 1);
+            
+            // Vérifier les ordres du compte
+            let account_orders = EtikaMarketplace::account_orders(1);
+            assert_eq!(account_orders.len(), 1);
+            
+            // Vérifier le meilleur prix d'achat
+            assert_eq!(EtikaMarketplace::best_buy_price(), 1500);
+        });
     }
-);
-
-decl_error! {
-    pub enum Error for Module<T: Config> {
-        /// Ordre non trouvé
-        OrderNotFound,
-        
-        /// Produit financier non trouvé
-        ProductNotFound,
-        
-        /// Montant trop faible
-        AmountTooSmall,
-        
-        /// Trop d'ordres pour ce compte
-        TooManyOrders,
-        
-        /// Trop de produits financiers actifs
-        TooManyActiveProducts,
-        
-        /// Fonds insuffisants
-        InsufficientFunds,
-        
-        /// Ordre déjà exécuté ou annulé
-        OrderNotActive,
-        
-        /// Non autorisé à effectuer cette opération
-        Unauthorized,
-        
-        /// Produit non disponible pour investissement
-        ProductNotOpen,
-        
-        /// Période de verrouillage non écoulée
-        LockPeriodNotExpired,
-        
-        /// Prix invalide
-        InvalidPrice,
-        
-        /// Quantité invalide
-        InvalidQuantity,
-        
-        /// Le créateur de l'ordre ne peut pas être l'exécuteur
-        SelfTrading,
-        
-        /// Type d'acteur incompatible
-        IncompatibleActorType,
-        
-        /// Type de produit financier non reconnu
-        InvalidProductType,
-        
-        /// Rendement attendu invalide
-        InvalidExpectedYield,
-        
-        /// Durée d'investissement invalide
-        InvalidInvestmentDuration,
+    
+    #[test]
+    fn test_create_sell_order() {
+        new_test_ext().execute_with(|| {
+            // Créer un ordre de vente
+            assert_ok!(EtikaMarketplace::create_sell_order(
+                Origin::signed(1),
+                100,    // quantité
+                1200,   // prix
+                100     // expiration
+            ));
+            
+            // Vérifier le nombre d'ordres
+            assert_eq!(EtikaMarketplace::total_sell_orders(), 1);
+            
+            // Vérifier les ordres du compte
+            let account_orders = EtikaMarketplace::account_orders(1);
+            assert_eq!(account_orders.len(), 1);
+            
+            // Vérifier le meilleur prix de vente
+            assert_eq!(EtikaMarketplace::best_sell_price(), 1200);
+        });
+    }
+    
+    #[test]
+    fn test_cancel_order() {
+        new_test_ext().execute_with(|| {
+            // Créer un ordre d'achat
+            assert_ok!(EtikaMarketplace::create_buy_order(
+                Origin::signed(1),
+                100,
+                1500,
+                100
+            ));
+            
+            // Récupérer l'ID de l'ordre
+            let account_orders = EtikaMarketplace::account_orders(1);
+            let order_id = account_orders[0];
+            
+            // Annuler l'ordre
+            assert_ok!(EtikaMarketplace::cancel_order(
+                Origin::signed(1),
+                order_id,
+                OrderType::Buy
+            ));
+            
+            // Vérifier que l'ordre est annulé
+            let order = EtikaMarketplace::buy_orders(order_id);
+            assert_eq!(order.status, OrderStatus::Cancelled);
+            
+            // Vérifier le nombre d'ordres actifs
+            assert_eq!(EtikaMarketplace::total_buy_orders(), 0);
+        });
+    }
+    
+    #[test]
+    fn test_order_matching() {
+        new_test_ext().execute_with(|| {
+            // Créer un ordre de vente
+            assert_ok!(EtikaMarketplace::create_sell_order(
+                Origin::signed(2),
+                100,
+                1000,
+                100
+            ));
+            
+            // Créer un ordre d'achat compatible
+            assert_ok!(EtikaMarketplace::create_buy_order(
+                Origin::signed(3),
+                50,
+                1200,  // Prix supérieur au prix de vente
+                100
+            ));
+            
+            // Vérifier que l'ordre d'achat a été partiellement exécuté
+            let account_orders = EtikaMarketplace::account_orders(3);
+            let buy_order_id = account_orders[0];
+            let buy_order = EtikaMarketplace::buy_orders(buy_order_id);
+            
+            // L'ordre d'achat devrait être entièrement exécuté
+            assert_eq!(buy_order.status, OrderStatus::Filled);
+            
+            // Vérifier que l'ordre de vente a été partiellement exécuté
+            let account_orders = EtikaMarketplace::account_orders(2);
+            let sell_order_id = account_orders[0];
+            let sell_order = EtikaMarketplace::sell_orders(sell_order_id);
+            
+            // L'ordre de vente devrait être partiellement exécuté
+            assert_eq!(sell_order.status, OrderStatus::PartiallyFilled);
+            assert_eq!(sell_order.quantity, 50); // 100 - 50
+            
+            // Vérifier le volume de trading
+            assert_eq!(EtikaMarketplace::total_trading_volume(), 50 * 1000); // quantité * prix
+            
+            // Vérifier les frais collectés
+            let expected_fee = Perbill::from_percent(1) * (50 * 1000);
+            assert_eq!(EtikaMarketplace::total_fees_collected(), expected_fee);
+        });
+    }
+    
+    #[test]
+    fn test_price_priority_and_partial_fill() {
+        new_test_ext().execute_with(|| {
+            // Deux ordres de vente à des prix différents
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(2), 30, 1100, 100));
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(4), 30, 1000, 100));
+            assert_eq!(EtikaMarketplace::best_sell_price(), 1000);
+            
+            // L'ordre d'achat consomme d'abord le vendeur le moins cher
+            assert_ok!(EtikaMarketplace::create_buy_order(Origin::signed(3), 40, 1200, 100));
+            
+            let cheap_order = EtikaMarketplace::sell_orders(EtikaMarketplace::account_orders(4)[0]);
+            assert_eq!(cheap_order.status, OrderStatus::Filled);
+            
+            let other_order = EtikaMarketplace::sell_orders(EtikaMarketplace::account_orders(2)[0]);
+            assert_eq!(other_order.status, OrderStatus::PartiallyFilled);
+            assert_eq!(other_order.quantity, 20);
+            
+            // Les échanges sont exécutés au prix des ordres de vente
+            assert_eq!(EtikaMarketplace::total_trading_volume(), 30 * 1000 + 10 * 1100);
+            
+            // L'acheteur n'a plus de fonds réservés
+            assert_eq!(Balances::reserved_balance(3), 0);
+            assert_eq!(EtikaMarketplace::buy_book().len(), 0);
+            assert_eq!(EtikaMarketplace::best_sell_price(), 1100);
+        });
+    }
+    
+    #[test]
+    fn test_matching_is_bounded_per_block() {
+        new_test_ext().execute_with(|| {
+            // Trois ordres de vente
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(2), 10, 1000, 100));
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(4), 10, 1000, 100));
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(5), 10, 1000, 100));
+            
+            // Un ordre d'achat couvrant les trois ne peut en exécuter que deux
+            assert_ok!(EtikaMarketplace::create_buy_order(Origin::signed(3), 30, 1000, 100));
+            let buy_order_id = EtikaMarketplace::account_orders(3)[0];
+            let buy_order = EtikaMarketplace::buy_orders(buy_order_id);
+            assert_eq!(buy_order.status, OrderStatus::PartiallyFilled);
+            assert_eq!(buy_order.quantity, 10);
+            
+            // Le reste est exécuté au bloc suivant
+            System::set_block_number(1);
+            EtikaMarketplace::on_initialize(1);
+            assert_eq!(EtikaMarketplace::buy_orders(buy_order_id).status, OrderStatus::Filled);
+            assert_eq!(EtikaMarketplace::sell_book().len(), 0);
+            assert_eq!(EtikaMarketplace::total_sell_orders(), 0);
+        });
+    }
+    
+    #[test]
+    fn test_expired_order_is_refunded() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(EtikaMarketplace::create_buy_order(Origin::signed(3), 10, 1000, 5));
+            let order_id = EtikaMarketplace::account_orders(3)[0];
+            assert_eq!(Balances::reserved_balance(3), 10 * 1000);
+            
+            // L'ordre reste ouvert jusqu'à son bloc d'expiration inclus
+            System::set_block_number(5);
+            EtikaMarketplace::on_initialize(5);
+            assert_eq!(EtikaMarketplace::buy_orders(order_id).status, OrderStatus::Active);
+            
+            System::set_block_number(6);
+            EtikaMarketplace::on_initialize(6);
+            assert_eq!(EtikaMarketplace::buy_orders(order_id).status, OrderStatus::Expired);
+            assert_eq!(Balances::reserved_balance(3), 0);
+            assert_eq!(EtikaMarketplace::total_buy_orders(), 0);
+            assert_eq!(EtikaMarketplace::open_order_count(3), 0);
+        });
+    }
+    
+    #[test]
+    fn test_trade_price_is_reported_to_monitor() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(EtikaMarketplace::create_buy_order(Origin::signed(3), 10, 1200, 100));
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(2), 10, 1000, 100));
+            
+            // Le vendeur arrive en second: l'échange se fait au prix de l'acheteur
+            assert_eq!(LAST_TRADE_PRICE.with(|last| *last.borrow()), 1200);
+            
+            // Un circuit-breaker déclenché suspend la correspondance
+            TRADING_HALTED.with(|halted| *halted.borrow_mut() = true);
+            assert_ok!(EtikaMarketplace::create_buy_order(Origin::signed(3), 10, 1200, 100));
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(4), 10, 1000, 100));
+            assert_eq!(EtikaMarketplace::buy_book().len(), 1);
+            assert_eq!(EtikaMarketplace::sell_book().len(), 1);
+            
+            // L'échange refusé est annulé dans son ensemble
+            assert_eq!(EtikaMarketplace::total_trading_volume(), 10 * 1200);
+        });
+    }
+    
+    #[test]
+    fn test_failed_settlement_is_reverted_and_skipped() {
+        new_test_ext().execute_with(|| {
+            // L'acheteur réserve tout son solde: il ne pourra pas régler en gardant son compte actif
+            assert_ok!(EtikaMarketplace::create_buy_order(Origin::signed(2), 200, 1000, 100));
+            let failing_order_id = EtikaMarketplace::account_orders(2)[0];
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(3), 200, 1000, 100));
+            
+            // Aucun transfert n'a eu lieu, l'ordre défaillant est annulé et ses fonds libérés;
+            // l'échange avorté n'est pas signalé aux circuit-breakers
+            assert_eq!(LAST_TRADE_PRICE.with(|last| *last.borrow()), 0);
+            assert_eq!(EtikaMarketplace::buy_orders(failing_order_id).status, OrderStatus::Cancelled);
+            assert_eq!(Balances::free_balance(2), 200000);
+            assert_eq!(Balances::reserved_balance(2), 0);
+            assert_eq!(Balances::free_balance(999), 1000000);
+            assert_eq!(EtikaMarketplace::total_trading_volume(), 0);
+            
+            // L'ordre de vente reste dans le carnet et peut être exécuté par un autre acheteur
+            let sell_order_id = EtikaMarketplace::account_orders(3)[0];
+            assert_eq!(EtikaMarketplace::sell_orders(sell_order_id).status, OrderStatus::Active);
+            assert_ok!(EtikaMarketplace::create_buy_order(Origin::signed(5), 200, 1000, 100));
+            assert_eq!(EtikaMarketplace::sell_orders(sell_order_id).status, OrderStatus::Filled);
+        });
+    }
+    
+    #[test]
+    fn test_order_book_is_bounded() {
+        new_test_ext().execute_with(|| {
+            for account in 1..=5 {
+                assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(account), 10, 1000, 100));
+            }
+            
+            assert_noop!(
+                EtikaMarketplace::create_sell_order(Origin::signed(1), 10, 1000, 100),
+                Error::<Test>::OrderBookFull
+            );
+            
+            // Une place se libère à l'annulation d'un ordre
+            let order_id = EtikaMarketplace::account_orders(1)[0];
+            assert_ok!(EtikaMarketplace::cancel_order(Origin::signed(1), order_id, OrderType::Sell));
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(1), 10, 1000, 100));
+            assert_eq!(EtikaMarketplace::sell_book().len(), 5);
+        });
+    }
+    
+    #[test]
+    fn test_self_trading_rejected() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(EtikaMarketplace::create_sell_order(Origin::signed(3), 10, 1000, 100));
+            
+            assert_noop!(
+                EtikaMarketplace::create_buy_order(Origin::signed(3), 10, 1000, 100),
+                Error::<Test>::SelfTrading
+            );
+        });
+    }
+    
+    #[test]
+    fn test_create_financial_product() {
+        new_test_ext().execute_with(|| {
+            // Créer un produit financier
+            assert_ok!(EtikaMarketplace::create_financial_product(
+                Origin::signed(999), // Compte administrateur
+                b"Savings Bond".to_vec(),
+                b"A fixed income investment with guaranteed returns".to_vec(),
+                ProductType::GuaranteedSavings,
+                500, // 5.00% de rendement attendu
+                180 * 24 * 60 * 60, // 180 jours en secondes
+                10000, // Montant minimum d'investissement
+                2 // Niveau de risque (1-5)
+            ));
+            
+            // Vérifier le nombre de produits
+            assert_eq!(EtikaMarketplace::total_active_products(), 1);
+            
+            // Vérifier la liste des produits par type
+            let products = EtikaMarketplace::products_by_type(ProductType::GuaranteedSavings);
+            assert_eq!(products.len(), 1);
+            
+            // Vérifier les détails du produit
+            let product_id = products[0];
+            let product = EtikaMarketplace::financial_products(product_id);
+            
+            assert_eq!(product.name, b"Savings Bond".to_vec());
+            assert_eq!(product.expected_yield, 500);
+            assert_eq!(product.risk_level, 2);
+            assert_eq!(product.status, ProductStatus::Open);
+        });
+    }
+    
+    #[test]
+    fn test_invest_in_product() {
+        new_test_ext().execute_with(|| {
+            // Créer un produit financier
+            assert_ok!(EtikaMarketplace::create_financial_product(
+                Origin::signed(999),
+                b"Savings Bond".to_vec(),
+                b"A fixed income investment with guaranteed returns".to_vec(),
+                ProductType::GuaranteedSavings,
+                500,
+                180 * 24 * 60 * 60,
+                10000,
+                2
+            ));
+            
+            // Récupérer l'ID du produit
+            let products = EtikaMarketplace::products_by_type(ProductType::GuaranteedSavings);
+            let product_id = products[0];
+            
+            // Investir dans le produit
+            assert_ok!(EtikaMarketplace::invest_in_product(
+                Origin::signed(1),
+                product_id,
+                20000 // Montant de l'investissement
+            ));
+            
+            // Vérifier l'investissement
+            let investment = EtikaMarketplace::investments(1, product_id);
+            assert_eq!(investment, 20000);
+            
+            // Vérifier le total investi dans le produit
+            let product = EtikaMarketplace::financial_products(product_id);
+            assert_eq!(product.total_invested, 20000);
+        });
+    }
+    
+    #[test]
+    fn test_close_product() {
+        new_test_ext().execute_with(|| {
+            // Créer un produit financier
+            assert_ok!(EtikaMarketplace::create_financial_product(
+                Origin::signed(999),
+                b"Savings Bond".to_vec(),
+                b"A fixed income investment with guaranteed returns".to_vec(),
+                ProductType::GuaranteedSavings,
+                500,
+                180 * 24 * 60 * 60,
+                10000,
+                2
+            ));
+            
+            // Récupérer l'ID du produit
+            let products = EtikaMarketplace::products_by_type(ProductType::GuaranteedSavings);
+            let product_id = products[0];
+            
+            // Fermer le produit aux nouveaux investissements
+            assert_ok!(EtikaMarketplace::close_product(
+                Origin::signed(999),
+                product_id
+            ));
+            
+            // Vérifier le statut du produit
+            let product = EtikaMarketplace::financial_products(product_id);
+            assert_eq!(product.status, ProductStatus::Closed);
+        });
     }
 }
-
-decl_module! {
-    pub struct Module<T: Config> for enum Call where origin: T::Origin {
-        /// Initialisation
\ No newline at end of file
@@ -275,6 +275,7 @@ decl_module! {
                 timestamp: Self::get_current_timestamp(),
                 receipt_hash,
                 signatures,
+                nonce: <frame_system::Module<T>>::account_nonce(&creator).saturated_into::<u64>(),
             };
             
             // Enregistrer la transaction comme en attente
@@ -423,6 +424,7 @@ impl<T: Config> Module<T> {
         if savings_amount > Zero::zero() {
             let _ = T::ConsumerFund::add_savings(
                 &transaction.consumer.clone().try_into().map_err(|_| Error::<T>::DecodingError)?,
+                &transaction.merchant.clone().try_into().map_err(|_| Error::<T>::DecodingError)?,
                 savings_amount.saturated_into(),
             );
             
@@ -629,6 +631,10 @@ mod tests {
             Ok(())
         }
     
+        fn deactivate_tokens(_from: &u64, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
+    
         fn burn_tokens(_from: &u64, _amount: Balance) -> Result<(), &'static str> {
             Ok(())
         }
@@ -636,13 +642,29 @@ mod tests {
         fn transfer_tokens(_from: &u64, _to: &u64, _amount: Balance) -> Result<(), &'static str> {
             Ok(())
         }
+    
+        fn active_balance(_account: &u64) -> Balance {
+            0
+        }
+    
+        fn reserve_tokens(_account: &u64, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
+    
+        fn unreserve_tokens(_account: &u64, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
     }
     
     // Mock implementation for ConsumerFund
     pub struct MockConsumerFund;
     
     impl ConsumerFund for MockConsumerFund {
-        fn add_savings(_consumer: &u64, _amount: Balance) -> Result<(), &'static str> {
+        fn add_savings(_consumer: &u64, _contributor: &u64, _amount: Balance) -> Result<(), &'static str> {
+            Ok(())
+        }
+    
+        fn remove_savings(_consumer: &u64, _contributor: &u64, _amount: Balance) -> Result<(), &'static str> {
             Ok(())
         }
     
@@ -11,7 +11,7 @@ use sp_runtime::{
     DispatchError, RuntimeDebug,
 };
 use sp_std::prelude::*;
-use etika_data_structure::Balance;
+use etika_data_structure::{Balance, TradeMonitor};
 
 /// Types de transactions pouvant être surveillées
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
@@ -268,8 +268,8 @@ impl<T: Config> Module<T> {
         price: Option<Balance>,
     ) -> DispatchResult {
         // Récupérer la configuration pour ce type de transaction
-        let config = <CircuitBreakerConfigs<T>>::get(tx_type)
-            .ok_or(Error::<T>::CircuitBreakerNotConfigured)?;
+        let config = <CircuitBreakerConfigs<T>>::get(tx_type);
+        ensure!(config.is_some(), Error::<T>::CircuitBreakerNotConfigured);
         
         // Vérifier si le circuit-breaker est actif
         if let Some(config) = config {
@@ -330,7 +330,7 @@ impl<T: Config> Module<T> {
         // Récupérer et mettre à jour la configuration
         if let Some(mut config) = <CircuitBreakerConfigs<T>>::get(tx_type) {
             config.status = CircuitBreakerStatus::Triggered;
-            <CircuitBreakerConfigs<T>>::insert(tx_type, Some(config));
+            <CircuitBreakerConfigs<T>>::insert(tx_type, Some(config.clone()));
             
             // Planifier la réactivation automatique
             let reset_block = <frame_system::Module<T>>::block_number() 
@@ -347,3 +347,17 @@ impl<T: Config> Module<T> {
         }
     }
 }
+
+/// Implémentation du trait TradeMonitor pour les échanges de tokens
+impl<T: Config> TradeMonitor for Module<T> {
+    fn check_trade(volume: Balance, price: Balance) -> Result<(), &'static str> {
+        if <CircuitBreakerConfigs<T>>::contains_key(TransactionType::TokenTrade) {
+            Self::check_circuit_breakers(TransactionType::TokenTrade, volume, Some(price))
+                .map_err(|_| "Trade rejected by circuit breaker")
+        } else {
+            // Sans configuration, le prix est tout de même enregistré pour les contrôles futurs
+            <LastPrice<T>>::insert(TransactionType::TokenTrade, price);
+            Ok(())
+        }
+    }
+}
@@ -103,6 +103,12 @@ pub trait SecurityCheck<AccountId> {
     fn security_check(account: &AccountId, action_type: &[u8], params: &[u8]) -> DispatchResult;
 }
 
+/// Trait exposant les autorisations spéciales aux autres modules
+pub trait SpecialAuthority<AccountId> {
+    /// Vérifier si un compte détient l'autorisation spéciale pour un rôle
+    fn has_special_authority(account: &AccountId, role_id: &[u8; 32]) -> bool;
+}
+
 /// Trait pour les modules implémentant des mesures de sécurité adaptatives
 pub trait AdaptiveSecurity<BlockNumber> {
     /// Ajuster le niveau de sécurité en fonction du contexte
@@ -433,6 +439,13 @@ impl<T: Config> Module<T> {
     }
 }
 
+/// Implémentation du trait SpecialAuthority
+impl<T: Config> SpecialAuthority<T::AccountId> for Module<T> {
+    fn has_special_authority(account: &T::AccountId, role_id: &[u8; 32]) -> bool {
+        Module::<T>::has_special_authority(account, role_id)
+    }
+}
+
 /// Implémentation du trait SecurityCheck
 impl<T: Config> SecurityCheck<T::AccountId> for Module<T> {
     fn security_check(account: &T::AccountId, action_type: &[u8], params: &[u8]) -> DispatchResult {
@@ -87,6 +87,21 @@ parameter_types! {
     pub const NGORate: u32 = 200; // 2.00%
     pub const MaxTokenBalance: Balance = 1_000_000_000;
     pub const MaxTransferHistoryEntries: u32 = 10;
+    pub const ConsumerLatentLifetime: u32 = 2;
+    pub const MerchantLatentLifetime: u32 = 0;
+    pub const SupplierLatentLifetime: u32 = 3;
+    pub const LatentExpiryWarning: u32 = 1;
+    pub const MaxLatentBatches: u32 = 4;
+    pub const MaxLatentExpiriesPerBlock: u32 = 2;
+    pub const MaxTrackedTokensPerAccount: u32 = 8;
+    pub const MaxTokenLineage: u32 = 4;
+    pub const MaxEscrowsPerTrigger: u32 = 2;
+    pub const EscrowDeposit: Balance = 10;
+    pub const MaxFeeRateChange: u32 = 100; // 1.00%
+    pub const FeeRateChangePeriod: u64 = 50;
+    pub const FeeRateNoticePeriod: u64 = 10;
+    pub const MaxNgoNameLength: u32 = 32;
+    pub const MaxNgoProjects: u32 = 2;
 }
 
 // Configuration du module token system
@@ -100,6 +115,23 @@ impl TokenSystemConfig for TestRuntime {
     type NGORate = NGORate;
     type MaxTokenBalance = MaxTokenBalance;
     type MaxTransferHistoryEntries = MaxTransferHistoryEntries;
+    type ConsumerLatentLifetime = ConsumerLatentLifetime;
+    type MerchantLatentLifetime = MerchantLatentLifetime;
+    type SupplierLatentLifetime = SupplierLatentLifetime;
+    type LatentExpiryWarning = LatentExpiryWarning;
+    type MaxLatentBatches = MaxLatentBatches;
+    type MaxLatentExpiriesPerBlock = MaxLatentExpiriesPerBlock;
+    type MaxTrackedTokensPerAccount = MaxTrackedTokensPerAccount;
+    type MaxTokenLineage = MaxTokenLineage;
+    type MaxEscrowsPerTrigger = MaxEscrowsPerTrigger;
+    type EscrowDeposit = EscrowDeposit;
+    type FeeRateOrigin = frame_system::EnsureRoot<u64>;
+    type ActorTypeOrigin = frame_system::EnsureRoot<u64>;
+    type MaxFeeRateChange = MaxFeeRateChange;
+    type FeeRateChangePeriod = FeeRateChangePeriod;
+    type FeeRateNoticePeriod = FeeRateNoticePeriod;
+    type MaxNgoNameLength = MaxNgoNameLength;
+    type MaxNgoProjects = MaxNgoProjects;
 }
 
 // Paramètres du module blockchain core (à adapter selon l'implémentation réelle)
@@ -178,17 +210,16 @@ fn new_test_ext() -> sp_io::TestExternalities {
 
 // Fonction utilitaire pour configurer les acteurs du test
 fn setup_test_actors() -> (u64, u64, u64, u64) {
-    let admin = 0;
     let consumer = 1;
     let merchant = 2;
     let supplier = 3;
     let ngo = 4;
     
     // Enregistrer les types d'acteurs
-    EtikaTokenSystem::update_actor_type(Origin::signed(admin), consumer, ActorType::Consumer).unwrap();
-    EtikaTokenSystem::update_actor_type(Origin::signed(admin), merchant, ActorType::Merchant).unwrap();
-    EtikaTokenSystem::update_actor_type(Origin::signed(admin), supplier, ActorType::Supplier).unwrap();
-    EtikaTokenSystem::update_actor_type(Origin::signed(admin), ngo, ActorType::NGO).unwrap();
+    EtikaTokenSystem::update_actor_type(Origin::root(), consumer, ActorType::Consumer).unwrap();
+    EtikaTokenSystem::update_actor_type(Origin::root(), merchant, ActorType::Merchant).unwrap();
+    EtikaTokenSystem::update_actor_type(Origin::root(), supplier, ActorType::Supplier).unwrap();
+    EtikaTokenSystem::update_actor_type(Origin::root(), ngo, ActorType::NGO).unwrap();
     
     // Initialiser les comptes avec des tokens
     etika_token_system::LatentTokenBalances::<TestRuntime>::insert(consumer, 5000);
@@ -487,7 +518,7 @@ mod tests {
             
             // Cas d'erreur 3: Tentative de transfert à une ONG non enregistrée
             let fake_ngo = 10;
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), fake_ngo, ActorType::Consumer).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), fake_ngo, ActorType::Consumer).unwrap();
             
             // Activer des tokens pour pouvoir tester le transfert
             assert_ok!(EtikaTokenSystem::activate_tokens(Origin::signed(consumer), 1000));
@@ -80,6 +80,21 @@ parameter_types! {
     pub const NGORate: u32 = 200; // 2.00%
     pub const MaxTokenBalance: Balance = 1_000_000_000;
     pub const MaxTransferHistoryEntries: u32 = 10;
+    pub const ConsumerLatentLifetime: u32 = 2;
+    pub const MerchantLatentLifetime: u32 = 0;
+    pub const SupplierLatentLifetime: u32 = 3;
+    pub const LatentExpiryWarning: u32 = 1;
+    pub const MaxLatentBatches: u32 = 4;
+    pub const MaxLatentExpiriesPerBlock: u32 = 2;
+    pub const MaxTrackedTokensPerAccount: u32 = 8;
+    pub const MaxTokenLineage: u32 = 4;
+    pub const MaxEscrowsPerTrigger: u32 = 2;
+    pub const EscrowDeposit: Balance = 10;
+    pub const MaxFeeRateChange: u32 = 100; // 1.00%
+    pub const FeeRateChangePeriod: u64 = 50;
+    pub const FeeRateNoticePeriod: u64 = 10;
+    pub const MaxNgoNameLength: u32 = 32;
+    pub const MaxNgoProjects: u32 = 2;
 }
 
 // Configuration du module token system
@@ -93,6 +108,23 @@ impl TokenSystemConfig for TestRuntime {
     type NGORate = NGORate;
     type MaxTokenBalance = MaxTokenBalance;
     type MaxTransferHistoryEntries = MaxTransferHistoryEntries;
+    type ConsumerLatentLifetime = ConsumerLatentLifetime;
+    type MerchantLatentLifetime = MerchantLatentLifetime;
+    type SupplierLatentLifetime = SupplierLatentLifetime;
+    type LatentExpiryWarning = LatentExpiryWarning;
+    type MaxLatentBatches = MaxLatentBatches;
+    type MaxLatentExpiriesPerBlock = MaxLatentExpiriesPerBlock;
+    type MaxTrackedTokensPerAccount = MaxTrackedTokensPerAccount;
+    type MaxTokenLineage = MaxTokenLineage;
+    type MaxEscrowsPerTrigger = MaxEscrowsPerTrigger;
+    type EscrowDeposit = EscrowDeposit;
+    type FeeRateOrigin = frame_system::EnsureRoot<u64>;
+    type ActorTypeOrigin = frame_system::EnsureRoot<u64>;
+    type MaxFeeRateChange = MaxFeeRateChange;
+    type FeeRateChangePeriod = FeeRateChangePeriod;
+    type FeeRateNoticePeriod = FeeRateNoticePeriod;
+    type MaxNgoNameLength = MaxNgoNameLength;
+    type MaxNgoProjects = MaxNgoProjects;
 }
 
 // Fonction utilitaire pour créer un environnement de test
@@ -138,7 +170,7 @@ fn setup_realistic_environment() -> Vec<TestActor> {
     
     // Configurer les types d'acteurs
     for actor in &actors {
-        EtikaTokenSystem::update_actor_type(Origin::signed(0), actor.id, actor.actor_type).unwrap();
+        EtikaTokenSystem::update_actor_type(Origin::root(), actor.id, actor.actor_type).unwrap();
     }
     
     // Configurer des soldes initiaux réalistes
@@ -406,7 +438,7 @@ mod scenario_tests {
             
             // Supposons que le modèle d'enchères est basé sur la quantité de tokens transférés à un compte spécial
             let auction_account_id = 9;
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), auction_account_id, ActorType::Admin).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), auction_account_id, ActorType::Admin).unwrap();
             
             // 1. Deux fournisseurs concurrents font des offres
             let supplier1_id = 5;
@@ -69,6 +69,21 @@ mod tests {
         pub const NGORate: u32 = 200; // 2.00%
         pub const MaxTokenBalance: Balance = 1_000_000_000;
         pub const MaxTransferHistoryEntries: u32 = 10;
+        pub const ConsumerLatentLifetime: u32 = 2;
+        pub const MerchantLatentLifetime: u32 = 0;
+        pub const SupplierLatentLifetime: u32 = 3;
+        pub const LatentExpiryWarning: u32 = 1;
+        pub const MaxLatentBatches: u32 = 4;
+        pub const MaxLatentExpiriesPerBlock: u32 = 2;
+        pub const MaxTrackedTokensPerAccount: u32 = 8;
+        pub const MaxTokenLineage: u32 = 4;
+        pub const MaxEscrowsPerTrigger: u32 = 2;
+        pub const EscrowDeposit: Balance = 10;
+        pub const MaxFeeRateChange: u32 = 100; // 1.00%
+        pub const FeeRateChangePeriod: u64 = 50;
+        pub const FeeRateNoticePeriod: u64 = 10;
+        pub const MaxNgoNameLength: u32 = 32;
+        pub const MaxNgoProjects: u32 = 2;
     }
     
     impl Config for Test {
@@ -81,6 +96,23 @@ mod tests {
         type NGORate = NGORate;
         type MaxTokenBalance = MaxTokenBalance;
         type MaxTransferHistoryEntries = MaxTransferHistoryEntries;
+        type ConsumerLatentLifetime = ConsumerLatentLifetime;
+        type MerchantLatentLifetime = MerchantLatentLifetime;
+        type SupplierLatentLifetime = SupplierLatentLifetime;
+        type LatentExpiryWarning = LatentExpiryWarning;
+        type MaxLatentBatches = MaxLatentBatches;
+        type MaxLatentExpiriesPerBlock = MaxLatentExpiriesPerBlock;
+        type MaxTrackedTokensPerAccount = MaxTrackedTokensPerAccount;
+        type MaxTokenLineage = MaxTokenLineage;
+        type MaxEscrowsPerTrigger = MaxEscrowsPerTrigger;
+        type EscrowDeposit = EscrowDeposit;
+        type FeeRateOrigin = frame_system::EnsureRoot<u64>;
+        type ActorTypeOrigin = frame_system::EnsureRoot<u64>;
+        type MaxFeeRateChange = MaxFeeRateChange;
+        type FeeRateChangePeriod = FeeRateChangePeriod;
+        type FeeRateNoticePeriod = FeeRateNoticePeriod;
+        type MaxNgoNameLength = MaxNgoNameLength;
+        type MaxNgoProjects = MaxNgoProjects;
     }
     
     // Fonction utilitaire pour créer un environnement de test
@@ -94,16 +126,15 @@ mod tests {
     
     // Fonction utilitaire pour configurer différents types d'acteurs pour les tests
     fn setup_actor_types() -> (u64, u64, u64, u64) {
-        let admin = 0;
         let consumer = 1;
         let merchant = 2;
         let supplier = 3;
         let ngo = 4;
         
-        EtikaTokenSystem::update_actor_type(Origin::signed(admin), consumer, ActorType::Consumer).unwrap();
-        EtikaTokenSystem::update_actor_type(Origin::signed(admin), merchant, ActorType::Merchant).unwrap();
-        EtikaTokenSystem::update_actor_type(Origin::signed(admin), supplier, ActorType::Supplier).unwrap();
-        EtikaTokenSystem::update_actor_type(Origin::signed(admin), ngo, ActorType::NGO).unwrap();
+        EtikaTokenSystem::update_actor_type(Origin::root(), consumer, ActorType::Consumer).unwrap();
+        EtikaTokenSystem::update_actor_type(Origin::root(), merchant, ActorType::Merchant).unwrap();
+        EtikaTokenSystem::update_actor_type(Origin::root(), supplier, ActorType::Supplier).unwrap();
+        EtikaTokenSystem::update_actor_type(Origin::root(), ngo, ActorType::NGO).unwrap();
         
         (consumer, merchant, supplier, ngo)
     }
@@ -275,8 +306,8 @@ mod tests {
             <ActiveTokenBalances<Test>>::insert(from, 1000);
             
             // Configurer l'ONG et un compte non-ONG
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), ngo, ActorType::NGO).unwrap();
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), not_ngo, ActorType::Consumer).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), ngo, ActorType::NGO).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), not_ngo, ActorType::Consumer).unwrap();
             
             // Transférer des tokens à l'ONG
             assert_ok!(EtikaTokenSystem::transfer_to_ngo(Origin::signed(from), ngo, 500));
@@ -371,15 +402,20 @@ mod tests {
     #[test]
     fn test_update_actor_type() {
         new_test_ext().execute_with(|| {
-            let admin = 0;
             let account = 1;
             
             // Vérifier la valeur initiale (par défaut)
             assert_eq!(EtikaTokenSystem::actor_types(account), ActorType::default());
             
+            // Un compte signé ne peut pas s'attribuer un type d'acteur
+            assert_noop!(
+                EtikaTokenSystem::update_actor_type(Origin::signed(account), account, ActorType::NGO),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            
             // Mettre à jour le type d'acteur
             assert_ok!(EtikaTokenSystem::update_actor_type(
-                Origin::signed(admin),
+                Origin::root(),
                 account,
                 ActorType::Consumer
             ));
@@ -389,7 +425,7 @@ mod tests {
             
             // Changer à un autre type
             assert_ok!(EtikaTokenSystem::update_actor_type(
-                Origin::signed(admin),
+                Origin::root(),
                 account,
                 ActorType::Merchant
             ));
@@ -11,7 +11,7 @@
 use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult,
-    ensure, traits::Get, Parameter,
+    ensure, traits::{EnsureOrigin, Get}, weights::Weight, Parameter,
 };
 use frame_system::{self as system, ensure_signed};
 use sp_runtime::{
@@ -22,9 +22,120 @@ use sp_std::prelude::*;
 
 // Import des structures de données définies dans etika-data-structure
 use etika_data_structure::{
-    AccountId, ActorProfile, ActorType, Balance, Moment, PoPTransaction, Token, TokenState, TokenSystem,
+    AccountId, ActorProfile, ActorType, Balance, Moment, OnPopFinalized, PoPTransaction, Token, TokenFeeGovernance,
+    TokenState, TokenSystem, TOKEN_TRANSFER_HISTORY_LIMIT, sweep_queue,
 };
 
+sp_api::decl_runtime_apis! {
+    /// Traçabilité des lots de tokens suivis
+    pub trait EtikaTokenLineageApi<AccountId> where AccountId: codec::Codec {
+        /// Lot de tokens suivi, avec son historique et sa lignée
+        fn tracked_token(id: [u8; 32]) -> Option<Token<AccountId>>;
+        
+        /// Lots de tokens suivis détenus par un compte, du plus ancien au plus récent
+        fn tracked_tokens_of(account: AccountId) -> Vec<Token<AccountId>>;
+    }
+}
+
+/// Lot de tokens latents issu d'une distribution, suivi jusqu'à son activation ou son expiration
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LatentBatch<BlockNumber> {
+    /// Bloc de distribution du lot
+    pub distributed_at: BlockNumber,
+    /// Montant du lot encore latent
+    pub amount: Balance,
+    /// Bloc d'expiration (None si les tokens de ce type d'acteur n'expirent pas)
+    pub expires_at: Option<BlockNumber>,
+    /// Indique si l'avertissement d'expiration a déjà été émis
+    pub warned: bool,
+}
+
+/// Condition de libération d'un séquestre
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum EscrowCondition<BlockNumber> {
+    /// Libération automatique au bloc indiqué
+    AtBlock(BlockNumber),
+    /// Libération à partir du moment indiqué (en secondes), sur demande
+    AtTime(Moment),
+    /// Libération lorsque la transaction PoP indiquée est définitive, remboursement si elle est annulée
+    PopFinalized([u8; 32]),
+}
+
+/// Transfert de tokens actifs en séquestre (paiement échelonné, acompte fournisseur)
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Escrow<AccountId, BlockNumber> {
+    /// Compte ayant mis les tokens en séquestre
+    pub sender: AccountId,
+    /// Compte destinataire à la libération
+    pub recipient: AccountId,
+    /// Montant en séquestre
+    pub amount: Balance,
+    /// Dépôt réservé sur l'expéditeur, rendu au règlement du séquestre
+    pub deposit: Balance,
+    /// Condition de libération
+    pub condition: EscrowCondition<BlockNumber>,
+    /// Bloc à partir duquel l'expéditeur peut récupérer les tokens si la condition n'est pas remplie
+    pub refund_after: Option<BlockNumber>,
+    /// Bloc de création du séquestre
+    pub created_at: BlockNumber,
+}
+
+/// Fiche d'une ONG inscrite au registre
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct NgoProfile<BlockNumber> {
+    /// Nom public de l'ONG
+    pub name: Vec<u8>,
+    /// Empreinte des statuts et informations publiques de l'ONG (conservés hors chaîne)
+    pub metadata_hash: [u8; 32],
+    /// Nombre de projets enregistrés
+    pub project_count: u32,
+    /// Bloc d'inscription
+    pub registered_at: BlockNumber,
+}
+
+/// Registre public des dons reçus et des dépenses déclarées
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct NgoLedger {
+    /// Montant total reçu
+    pub received: Balance,
+    /// Montant total des dépenses déclarées
+    pub spent: Balance,
+}
+
+/// Projet d'une ONG pouvant recevoir des dons affectés
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct NgoProject<BlockNumber> {
+    /// Empreinte de la description du projet (conservée hors chaîne)
+    pub description_hash: [u8; 32],
+    /// Dons affectés au projet et dépenses déclarées sur le projet
+    pub ledger: NgoLedger,
+    /// Bloc d'enregistrement du projet
+    pub created_at: BlockNumber,
+}
+
+/// Rapport de dépenses publié par une ONG
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SpendingReport<BlockNumber> {
+    /// Projet concerné (None pour les fonds non affectés)
+    pub project: Option<[u8; 32]>,
+    /// Montant dépensé
+    pub amount: Balance,
+    /// Empreinte du rapport détaillé (conservé hors chaîne)
+    pub report_hash: [u8; 32],
+    /// Bloc de publication
+    pub submitted_at: BlockNumber,
+}
+
+impl<AccountId, BlockNumber> Escrow<AccountId, BlockNumber> {
+    /// État des tokens suivis pendant le séquestre
+    fn locked_state(&self) -> TokenState {
+        match self.condition {
+            EscrowCondition::AtTime(unlock_time) => TokenState::Locked { unlock_time },
+            _ => TokenState::Locked { unlock_time: 0 },
+        }
+    }
+}
+
 /// Configuration du module token system
 pub trait Config: frame_system::Config {
     /// Type d'événement
@@ -43,9 +154,11 @@ pub trait Config: frame_system::Config {
     type SupplierDistributionAmount: Get<Balance>;
     
     /// Pourcentage de tokens brûlés lors de chaque transaction (en centièmes de pourcentage)
+    /// Valeur initiale, modifiable par la gouvernance
     type BurnRate: Get<u32>;
     
     /// Pourcentage de tokens transférés aux ONG lors de chaque transaction (en centièmes de pourcentage)
+    /// Valeur initiale, modifiable par la gouvernance
     type NGORate: Get<u32>;
     
     /// Nombre maximum de tokens qu'un compte peut détenir
@@ -53,6 +166,57 @@ pub trait Config: frame_system::Config {
     
     /// Nombre maximum d'entrées dans l'historique de transfert
     type MaxTransferHistoryEntries: Get<u32>;
+    
+    /// Durée de vie des tokens latents d'un consommateur (en périodes de distribution, 0 = sans expiration)
+    type ConsumerLatentLifetime: Get<u32>;
+    
+    /// Durée de vie des tokens latents d'un commerçant (en périodes de distribution, 0 = sans expiration)
+    type MerchantLatentLifetime: Get<u32>;
+    
+    /// Durée de vie des tokens latents d'un fournisseur (en périodes de distribution, 0 = sans expiration)
+    type SupplierLatentLifetime: Get<u32>;
+    
+    /// Nombre de périodes de distribution avant l'expiration à partir duquel un avertissement est émis
+    type LatentExpiryWarning: Get<u32>;
+    
+    /// Nombre maximum de lots de tokens latents suivis par compte
+    type MaxLatentBatches: Get<u32>;
+    
+    /// Nombre maximum de comptes dont les lots latents sont examinés par bloc
+    type MaxLatentExpiriesPerBlock: Get<u32>;
+    
+    /// Nombre maximum de tokens suivis (mode non fongible) détenus par compte
+    type MaxTrackedTokensPerAccount: Get<u32>;
+    
+    /// Nombre maximum d'identifiants conservés dans la lignée d'un token suivi
+    type MaxTokenLineage: Get<u32>;
+    
+    /// Nombre maximum de séquestres libérés par un même bloc ou une même transaction PoP
+    type MaxEscrowsPerTrigger: Get<u32>;
+    
+    /// Dépôt réservé par séquestre à bloc ou à transaction PoP, contre l'encombrement des déclencheurs
+    type EscrowDeposit: Get<Balance>;
+    
+    /// Origine autorisée à programmer de nouveaux taux (multisignature de gouvernance)
+    type FeeRateOrigin: EnsureOrigin<Self::Origin>;
+    
+    /// Origine autorisée à attribuer un type d'acteur (administration ou gouvernance)
+    type ActorTypeOrigin: EnsureOrigin<Self::Origin>;
+    
+    /// Variation maximale de chaque taux par modification (en centièmes de pourcentage)
+    type MaxFeeRateChange: Get<u32>;
+    
+    /// Délai minimum entre deux programmations de nouveaux taux (en blocs)
+    type FeeRateChangePeriod: Get<Self::BlockNumber>;
+    
+    /// Préavis entre la programmation de nouveaux taux et leur entrée en vigueur (en blocs)
+    type FeeRateNoticePeriod: Get<Self::BlockNumber>;
+    
+    /// Longueur maximale du nom d'une ONG inscrite au registre
+    type MaxNgoNameLength: Get<u32>;
+    
+    /// Nombre maximum de projets par ONG
+    type MaxNgoProjects: Get<u32>;
 }
 
 decl_storage! {
@@ -66,6 +230,9 @@ decl_storage! {
         /// Soldes de tokens verrouillés par compte
         LockedTokenBalances get(fn locked_token_balances): map hasher(blake2_128_concat) T::AccountId => Balance;
         
+        /// Soldes de tokens réservés par compte (ordres de vente sur la place de marché)
+        ReservedTokenBalances get(fn reserved_token_balances): map hasher(blake2_128_concat) T::AccountId => Balance;
+        
         /// Moment de déverrouillage des tokens verrouillés
         TokenUnlockTime get(fn token_unlock_time): map hasher(blake2_128_concat) T::AccountId => Moment;
         
@@ -86,7 +253,7 @@ decl_storage! {
         /// Montant total de tokens brûlés
         TotalBurnedTokens get(fn total_burned_tokens): Balance;
         
-        /// Montant total de tokens distribués
+        /// Montant total de tokens émis par distribution (hors tokens puisés dans la réserve de redistribution)
         TotalDistributedTokens get(fn total_distributed_tokens): Balance;
         
         /// Montant total de tokens transférés aux ONG
@@ -94,6 +261,75 @@ decl_storage! {
         
         /// Mapping des types d'acteur par compte
         ActorTypes get(fn actor_types): map hasher(blake2_128_concat) T::AccountId => ActorType;
+        
+        /// Lots de tokens latents par compte, du plus ancien au plus récent
+        LatentBatches get(fn latent_batches):
+            map hasher(blake2_128_concat) T::AccountId => Vec<LatentBatch<T::BlockNumber>>;
+        
+        /// Comptes dont un lot latent atteint son avertissement ou son échéance à chaque bloc
+        LatentExpiryQueue get(fn latent_expiry_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<T::AccountId>;
+        
+        /// Plus ancien compartiment de la file d'expiration latente non entièrement traité
+        LatentExpiryCursor get(fn latent_expiry_cursor): Option<T::BlockNumber>;
+        
+        /// Réserve de redistribution alimentée par les tokens latents expirés, puisée avant toute émission
+        RedistributionPool get(fn redistribution_pool): Balance;
+        
+        /// Montant total de tokens latents expirés
+        TotalExpiredTokens get(fn total_expired_tokens): Balance;
+        
+        /// Comptes ayant activé le suivi de leurs tokens (mode non fongible)
+        TokenTracking get(fn token_tracking): map hasher(blake2_128_concat) T::AccountId => bool;
+        
+        /// Tokens suivis par identifiant
+        TrackedTokens get(fn tracked_token): map hasher(identity) [u8; 32] => Option<Token<T::AccountId>>;
+        
+        /// Identifiants des tokens suivis détenus par compte, du plus ancien au plus récent
+        OwnedTrackedTokens get(fn owned_tracked_tokens): map hasher(blake2_128_concat) T::AccountId => Vec<[u8; 32]>;
+        
+        /// Compteur servant à dériver les identifiants des tokens suivis
+        TrackedTokenNonce: u64;
+        
+        /// Séquestres en cours par identifiant
+        Escrows get(fn escrows): map hasher(twox_64_concat) u64 => Option<Escrow<T::AccountId, T::BlockNumber>>;
+        
+        /// Prochain identifiant de séquestre
+        NextEscrowId get(fn next_escrow_id): u64;
+        
+        /// Soldes de tokens mis en séquestre par compte expéditeur
+        EscrowedTokenBalances get(fn escrowed_token_balances): map hasher(blake2_128_concat) T::AccountId => Balance;
+        
+        /// Séquestres à libérer à chaque bloc
+        EscrowReleaseQueue get(fn escrow_release_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<u64>;
+        
+        /// Séquestres en attente de la finalisation d'une transaction PoP
+        PopEscrows get(fn pop_escrows): map hasher(blake2_128_concat) [u8; 32] => Vec<u64>;
+        
+        /// Taux de brûlage et part ONG en vigueur, une fois modifiés par la gouvernance
+        FeeRatesOverride get(fn fee_rates_override): Option<(u32, u32)>;
+        
+        /// Taux programmés (brûlage, part ONG) et bloc de leur entrée en vigueur
+        PendingFeeRates get(fn pending_fee_rates): Option<(u32, u32, T::BlockNumber)>;
+        
+        /// Bloc de la dernière programmation de nouveaux taux
+        LastFeeRateChange get(fn last_fee_rate_change): Option<T::BlockNumber>;
+        
+        /// Registre des ONG
+        NgoRegistry get(fn ngo_registry): map hasher(blake2_128_concat) T::AccountId => Option<NgoProfile<T::BlockNumber>>;
+        
+        /// Dons reçus et dépenses déclarées par ONG
+        NgoLedgers get(fn ngo_ledger): map hasher(blake2_128_concat) T::AccountId => NgoLedger;
+        
+        /// Projets des ONG
+        NgoProjects get(fn ngo_project):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) [u8; 32] => Option<NgoProject<T::BlockNumber>>;
+        
+        /// Rapports de dépenses par ONG et numéro d'ordre
+        SpendingReports get(fn spending_report):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u32 => Option<SpendingReport<T::BlockNumber>>;
+        
+        /// Nombre de rapports de dépenses publiés par ONG
+        SpendingReportCount get(fn spending_report_count): map hasher(blake2_128_concat) T::AccountId => u32;
     }
 }
 
@@ -111,6 +347,10 @@ decl_event!(
         /// [compte, montant]
         TokensActivated(AccountId, Balance),
         
+        /// Tokens actifs remis à l'état latent (annulation d'une transaction)
+        /// [compte, montant]
+        TokensDeactivated(AccountId, Balance),
+        
         /// Tokens brûlés
         /// [compte, montant]
         TokensBurned(AccountId, Balance),
@@ -127,9 +367,69 @@ decl_event!(
         /// [compte, montant]
         TokensUnlocked(AccountId, Balance),
         
+        /// Tokens actifs réservés
+        /// [compte, montant]
+        TokensReserved(AccountId, Balance),
+        
+        /// Tokens réservés rendus disponibles
+        /// [compte, montant]
+        TokensUnreserved(AccountId, Balance),
+        
         /// Type d'acteur mis à jour
         /// [compte, nouveau type]
         ActorTypeUpdated(AccountId, ActorType),
+        
+        /// Tokens latents proches de leur expiration
+        /// [compte, montant, bloc d'expiration]
+        LatentTokensExpiring(AccountId, Balance, BlockNumber),
+        
+        /// Tokens latents expirés, versés à la réserve de redistribution
+        /// [compte, montant]
+        LatentTokensExpired(AccountId, Balance),
+        
+        /// Suivi des tokens activé ou désactivé pour un compte
+        /// [compte, actif]
+        TokenTrackingUpdated(AccountId, bool),
+        
+        /// Token suivi créé
+        /// [identifiant, propriétaire, valeur, lots parents]
+        TrackedTokenCreated([u8; 32], AccountId, Balance, Vec<[u8; 32]>),
+        
+        /// Tokens mis en séquestre
+        /// [séquestre, expéditeur, destinataire, montant]
+        EscrowCreated(u64, AccountId, AccountId, Balance),
+        
+        /// Séquestre libéré au profit du destinataire
+        /// [séquestre, destinataire, montant reçu]
+        EscrowReleased(u64, AccountId, Balance),
+        
+        /// Séquestre annulé et remboursé à l'expéditeur
+        /// [séquestre, expéditeur, montant]
+        EscrowRefunded(u64, AccountId, Balance),
+        
+        /// Nouveaux taux programmés, pour information des commerçants
+        /// [taux de brûlage, part ONG, bloc d'entrée en vigueur]
+        FeeRatesScheduled(u32, u32, BlockNumber),
+        
+        /// Nouveaux taux entrés en vigueur
+        /// [taux de brûlage, part ONG]
+        FeeRatesActivated(u32, u32),
+        
+        /// ONG inscrite au registre
+        /// [ONG, empreinte des informations publiques]
+        NgoRegistered(AccountId, [u8; 32]),
+        
+        /// Projet d'une ONG enregistré
+        /// [ONG, projet]
+        NgoProjectRegistered(AccountId, [u8; 32]),
+        
+        /// Don affecté à un projet
+        /// [donateur, ONG, projet, montant]
+        DonationEarmarked(AccountId, AccountId, [u8; 32], Balance),
+        
+        /// Rapport de dépenses publié
+        /// [ONG, numéro du rapport, montant, empreinte du rapport]
+        SpendingReported(AccountId, u32, Balance, [u8; 32]),
     }
 );
 
@@ -155,6 +455,54 @@ decl_error! {
         
         /// Dépassement arithmétique
         ArithmeticOverflow,
+        
+        /// Séquestre introuvable
+        EscrowNotFound,
+        
+        /// Condition de libération invalide ou sans échéance de remboursement
+        InvalidEscrowCondition,
+        
+        /// Condition de libération du séquestre non remplie
+        EscrowConditionNotMet,
+        
+        /// Seuls le destinataire, ou l'expéditeur après l'échéance, peuvent annuler le séquestre
+        EscrowCancellationNotAllowed,
+        
+        /// Trop de séquestres pour ce bloc ou cette transaction PoP
+        TooManyEscrows,
+        
+        /// Taux invalides (leur somme dépasse 100%)
+        InvalidFeeRates,
+        
+        /// Variation des taux supérieure au maximum autorisé
+        FeeRateChangeTooLarge,
+        
+        /// Délai minimum depuis la dernière programmation non écoulé
+        FeeRateChangeTooSoon,
+        
+        /// Des taux sont déjà programmés
+        FeeRateChangePending,
+        
+        /// ONG absente du registre
+        NgoNotInRegistry,
+        
+        /// ONG déjà inscrite au registre
+        NgoAlreadyRegistered,
+        
+        /// Nom de l'ONG trop long
+        NgoNameTooLong,
+        
+        /// Projet introuvable pour cette ONG
+        NgoProjectNotFound,
+        
+        /// Projet déjà enregistré
+        NgoProjectAlreadyExists,
+        
+        /// Nombre maximum de projets atteint
+        TooManyNgoProjects,
+        
+        /// Dépenses déclarées supérieures aux dons reçus
+        SpendingExceedsDonations,
     }
 }
 
@@ -167,17 +515,47 @@ decl_module! {
         fn deposit_event() = default;
         
         /// Distribution périodique de tokens au changement de bloc
-        fn on_initialize(n: T::BlockNumber) -> frame_support::weights::Weight {
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            // Appliquer les taux programmés arrivés à échéance
+            Self::activate_pending_fee_rates(n);
+            
+            // Les tokens latents expirés alimentent la réserve avant une éventuelle distribution
+            let (buckets, expired) = Self::process_latent_expiries(n);
+            
             // Vérifier si c'est le moment de distribuer des tokens
+            let mut distributed = 0;
             if n.saturating_sub(Self::last_distribution_block()) >= T::DistributionPeriod::get() {
-                Self::distribute_tokens(n);
+                distributed = Self::distribute_tokens(n);
                 <LastDistributionBlock<T>>::put(n);
             }
             
             // Déverrouiller les tokens si nécessaire
-            Self::process_token_unlocks();
-            
-            0
+            let unlocked = Self::process_token_unlocks();
+            
+            // Libérer les séquestres arrivés à leur bloc
+            let released = Self::process_escrow_releases(n);
+            
+            // Taux programmés, curseur latent, dernière distribution et file des séquestres, puis par
+            // élément: compte expiré, acteur distribué, compte verrouillé et séquestre libéré
+            T::DbWeight::get().reads_writes(
+                (4 + buckets as Weight)
+                    .saturating_add(6 * expired as Weight)
+                    .saturating_add(6 * distributed as Weight)
+                    .saturating_add(3 * unlocked as Weight)
+                    .saturating_add(8 * released as Weight),
+                (4 + buckets as Weight)
+                    .saturating_add(6 * expired as Weight)
+                    .saturating_add(7 * distributed as Weight)
+                    .saturating_add(2 * unlocked as Weight)
+                    .saturating_add(8 * released as Weight),
+            )
+        }
+        
+        /// Cohérence de la configuration
+        ///
+        /// La file d'expiration latente doit progresser d'au moins un élément par bloc.
+        fn integrity_test() {
+            assert!(T::MaxLatentExpiriesPerBlock::get() > 0, "MaxLatentExpiriesPerBlock doit être non nul");
         }
         
         /// Activer des tokens latents
@@ -203,6 +581,8 @@ decl_module! {
             ensure!(new_active_balance <= T::MaxTokenBalance::get(), Error::<T>::TokenBalanceOverflow);
             
             <ActiveTokenBalances<T>>::insert(&who, new_active_balance);
+            Self::consume_latent_batches(&who, amount);
+            Self::move_tracked_tokens(&who, &who, amount, TokenState::Latent, TokenState::Active);
             
             // Émettre un événement
             Self::deposit_event(RawEvent::TokensActivated(who, amount));
@@ -248,9 +628,10 @@ decl_module! {
             
             // Ajouter à l'historique des transferts
             Self::add_to_transfer_history(&from, &to, transfer_amount);
+            Self::transfer_tracked_tokens(&from, &to, amount, transfer_amount);
             
             // Émettre un événement
-            Self::deposit_event(RawEvent::TokensTransferred(from, to, transfer_amount));
+            Self::deposit_event(RawEvent::TokensTransferred(from.clone(), to, transfer_amount));
             
             // Brûler des tokens
             if burn_amount > 0 {
@@ -276,41 +657,126 @@ decl_module! {
         ) -> DispatchResult {
             let from = ensure_signed(origin)?;
             
-            // Vérifier que la destination est bien une ONG
-            let ngo_type = <ActorTypes<T>>::get(&ngo);
-            ensure!(ngo_type == ActorType::NGO, Error::<T>::NotRegisteredAsNGO);
+            Self::do_transfer_to_ngo(&from, &ngo, amount)
+        }
+        
+        /// Faire un don affecté à un projet d'une ONG inscrite au registre
+        #[weight = 10_000]
+        pub fn donate_to_ngo_project(
+            origin,
+            ngo: T::AccountId,
+            project_id: [u8; 32],
+            amount: Balance,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
             
-            // Vérifier que le compte a suffisamment de tokens actifs
-            let from_balance = <ActiveTokenBalances<T>>::get(&from);
-            ensure!(from_balance >= amount, Error::<T>::InsufficientActiveBalance);
+            let mut project = <NgoProjects<T>>::get(&ngo, project_id).ok_or(Error::<T>::NgoProjectNotFound)?;
             
-            // Pas de brûlage ni de taxation pour les dons aux ONG
+            Self::do_transfer_to_ngo(&from, &ngo, amount)?;
             
-            // Mettre à jour les soldes
-            <ActiveTokenBalances<T>>::insert(
-                &from,
-                from_balance.saturating_sub(amount)
-            );
+            project.ledger.received = project.ledger.received.saturating_add(amount);
+            <NgoProjects<T>>::insert(&ngo, project_id, project);
             
-            let ngo_balance = <ActiveTokenBalances<T>>::get(&ngo);
-            ensure!(
-                ngo_balance.saturating_add(amount) <= T::MaxTokenBalance::get(),
-                Error::<T>::TokenBalanceOverflow
-            );
+            // Émettre un événement
+            Self::deposit_event(RawEvent::DonationEarmarked(from, ngo, project_id, amount));
             
-            <ActiveTokenBalances<T>>::insert(
-                &ngo,
-                ngo_balance.saturating_add(amount)
-            );
+            Ok(())
+        }
+        
+        /// Inscrire au registre un compte de type ONG
+        #[weight = 10_000]
+        pub fn register_ngo(
+            origin,
+            name: Vec<u8>,
+            metadata_hash: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
             
-            // Ajouter à l'historique des transferts
-            Self::add_to_transfer_history(&from, &ngo, amount);
+            ensure!(<ActorTypes<T>>::get(&who) == ActorType::NGO, Error::<T>::NotRegisteredAsNGO);
+            ensure!(!<NgoRegistry<T>>::contains_key(&who), Error::<T>::NgoAlreadyRegistered);
+            ensure!(name.len() as u32 <= T::MaxNgoNameLength::get(), Error::<T>::NgoNameTooLong);
             
-            // Mettre à jour les compteurs
-            <TotalNGOTokens>::mutate(|total| *total = total.saturating_add(amount));
+            <NgoRegistry<T>>::insert(&who, NgoProfile {
+                name,
+                metadata_hash,
+                project_count: 0,
+                registered_at: <system::Module<T>>::block_number(),
+            });
             
             // Émettre un événement
-            Self::deposit_event(RawEvent::TokensTransferredToNGO(from, ngo, amount));
+            Self::deposit_event(RawEvent::NgoRegistered(who, metadata_hash));
+            
+            Ok(())
+        }
+        
+        /// Enregistrer un projet pouvant recevoir des dons affectés
+        #[weight = 10_000]
+        pub fn register_ngo_project(
+            origin,
+            project_id: [u8; 32],
+            description_hash: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let mut profile = <NgoRegistry<T>>::get(&who).ok_or(Error::<T>::NgoNotInRegistry)?;
+            ensure!(!<NgoProjects<T>>::contains_key(&who, project_id), Error::<T>::NgoProjectAlreadyExists);
+            ensure!(profile.project_count < T::MaxNgoProjects::get(), Error::<T>::TooManyNgoProjects);
+            
+            <NgoProjects<T>>::insert(&who, project_id, NgoProject {
+                description_hash,
+                ledger: NgoLedger::default(),
+                created_at: <system::Module<T>>::block_number(),
+            });
+            profile.project_count += 1;
+            <NgoRegistry<T>>::insert(&who, profile);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::NgoProjectRegistered(who, project_id));
+            
+            Ok(())
+        }
+        
+        /// Publier un rapport de dépenses, sur un projet ou sur les fonds non affectés
+        ///
+        /// Les dépenses déclarées ne peuvent dépasser les dons reçus.
+        #[weight = 10_000]
+        pub fn submit_spending_report(
+            origin,
+            project_id: Option<[u8; 32]>,
+            amount: Balance,
+            report_hash: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            ensure!(<NgoRegistry<T>>::contains_key(&who), Error::<T>::NgoNotInRegistry);
+            
+            let mut ledger = <NgoLedgers<T>>::get(&who);
+            let spent = ledger.spent.saturating_add(amount);
+            ensure!(spent <= ledger.received, Error::<T>::SpendingExceedsDonations);
+            
+            if let Some(project_id) = project_id {
+                let mut project = <NgoProjects<T>>::get(&who, project_id).ok_or(Error::<T>::NgoProjectNotFound)?;
+                let project_spent = project.ledger.spent.saturating_add(amount);
+                ensure!(project_spent <= project.ledger.received, Error::<T>::SpendingExceedsDonations);
+                
+                project.ledger.spent = project_spent;
+                <NgoProjects<T>>::insert(&who, project_id, project);
+            }
+            
+            ledger.spent = spent;
+            <NgoLedgers<T>>::insert(&who, ledger);
+            
+            let index = <SpendingReportCount<T>>::get(&who);
+            <SpendingReports<T>>::insert(&who, index, SpendingReport {
+                project: project_id,
+                amount,
+                report_hash,
+                submitted_at: <system::Module<T>>::block_number(),
+            });
+            <SpendingReportCount<T>>::insert(&who, index.wrapping_add(1));
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::SpendingReported(who, index, amount, report_hash));
             
             Ok(())
         }
@@ -365,77 +831,692 @@ decl_module! {
             if locked_balance == 0 {
                 return Ok(());
             }
-            
-            // Transférer les tokens verrouillés vers les tokens actifs
-            let active_balance = <ActiveTokenBalances<T>>::get(&who);
-            let new_active_balance = active_balance.saturating_add(locked_balance);
-            ensure!(new_active_balance <= T::MaxTokenBalance::get(), Error::<T>::TokenBalanceOverflow);
-            
-            <ActiveTokenBalances<T>>::insert(&who, new_active_balance);
-            <LockedTokenBalances<T>>::insert(&who, 0);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::TokensUnlocked(who, locked_balance));
-            
-            Ok(())
+            
+            // Transférer les tokens verrouillés vers les tokens actifs
+            let active_balance = <ActiveTokenBalances<T>>::get(&who);
+            let new_active_balance = active_balance.saturating_add(locked_balance);
+            ensure!(new_active_balance <= T::MaxTokenBalance::get(), Error::<T>::TokenBalanceOverflow);
+            
+            <ActiveTokenBalances<T>>::insert(&who, new_active_balance);
+            <LockedTokenBalances<T>>::insert(&who, 0);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::TokensUnlocked(who, locked_balance));
+            
+            Ok(())
+        }
+        
+        /// Mettre à jour le type d'acteur pour un compte
+        ///
+        /// Réservé à `ActorTypeOrigin`: le type d'acteur conditionne notamment l'inscription
+        /// au registre des ONG.
+        #[weight = 10_000]
+        pub fn update_actor_type(
+            origin,
+            account: T::AccountId,
+            actor_type: ActorType,
+        ) -> DispatchResult {
+            T::ActorTypeOrigin::ensure_origin(origin)?;
+            
+            <ActorTypes<T>>::insert(&account, actor_type);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::ActorTypeUpdated(account, actor_type));
+            
+            Ok(())
+        }
+        
+        /// Mettre des tokens actifs en séquestre au profit d'un destinataire
+        ///
+        /// Une condition liée à une transaction PoP exige une échéance de remboursement.
+        /// Les séquestres à bloc et à transaction PoP réservent en outre `EscrowDeposit`
+        /// sur l'expéditeur jusqu'à leur libération ou leur remboursement.
+        #[weight = 10_000]
+        pub fn create_escrow(
+            origin,
+            recipient: T::AccountId,
+            amount: Balance,
+            condition: EscrowCondition<T::BlockNumber>,
+            refund_after: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let now = <system::Module<T>>::block_number();
+            
+            ensure!(amount > 0 && sender != recipient, Error::<T>::InvalidEscrowCondition);
+            ensure!(refund_after.map_or(true, |block| block > now), Error::<T>::InvalidEscrowCondition);
+            
+            // Vérifier que le compte a suffisamment de tokens actifs, dépôt compris
+            let deposit = match condition {
+                EscrowCondition::AtTime(_) => 0,
+                _ => T::EscrowDeposit::get(),
+            };
+            let active_balance = <ActiveTokenBalances<T>>::get(&sender);
+            ensure!(active_balance >= amount.saturating_add(deposit), Error::<T>::InsufficientActiveBalance);
+            
+            // Inscrire le séquestre auprès de son déclencheur
+            let escrow_id = <NextEscrowId>::get();
+            match condition {
+                EscrowCondition::AtBlock(block) => {
+                    ensure!(block > now, Error::<T>::InvalidEscrowCondition);
+                    let mut queue = <EscrowReleaseQueue<T>>::get(block);
+                    ensure!((queue.len() as u32) < T::MaxEscrowsPerTrigger::get(), Error::<T>::TooManyEscrows);
+                    queue.push(escrow_id);
+                    <EscrowReleaseQueue<T>>::insert(block, queue);
+                }
+                EscrowCondition::AtTime(_) => {}
+                EscrowCondition::PopFinalized(transaction_id) => {
+                    ensure!(refund_after.is_some(), Error::<T>::InvalidEscrowCondition);
+                    let mut escrows = <PopEscrows>::get(transaction_id);
+                    ensure!((escrows.len() as u32) < T::MaxEscrowsPerTrigger::get(), Error::<T>::TooManyEscrows);
+                    escrows.push(escrow_id);
+                    <PopEscrows>::insert(transaction_id, escrows);
+                }
+            }
+            
+            // Mettre à jour les soldes
+            <ActiveTokenBalances<T>>::insert(&sender, active_balance.saturating_sub(amount));
+            <EscrowedTokenBalances<T>>::mutate(&sender, |escrowed| *escrowed = escrowed.saturating_add(amount));
+            if deposit > 0 {
+                Self::reserve_tokens(&sender, deposit)?;
+            }
+            
+            let escrow = Escrow {
+                sender: sender.clone(),
+                recipient: recipient.clone(),
+                amount,
+                deposit,
+                condition,
+                refund_after,
+                created_at: now,
+            };
+            Self::move_tracked_tokens(&sender, &sender, amount, TokenState::Active, escrow.locked_state());
+            
+            <Escrows<T>>::insert(escrow_id, escrow);
+            <NextEscrowId>::put(escrow_id.wrapping_add(1));
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::EscrowCreated(escrow_id, sender, recipient, amount));
+            
+            Ok(())
+        }
+        
+        /// Libérer un séquestre dont la condition est remplie
+        ///
+        /// Les séquestres à bloc et à transaction PoP sont libérés automatiquement; cet appel
+        /// sert aux séquestres à échéance horaire ou dont la libération automatique a échoué.
+        #[weight = 10_000]
+        pub fn release_escrow(
+            origin,
+            escrow_id: u64,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            
+            let escrow = <Escrows<T>>::get(escrow_id).ok_or(Error::<T>::EscrowNotFound)?;
+            let condition_met = match escrow.condition {
+                EscrowCondition::AtBlock(block) => <system::Module<T>>::block_number() >= block,
+                EscrowCondition::AtTime(unlock_time) => Self::get_current_timestamp() >= unlock_time,
+                EscrowCondition::PopFinalized(_) => false,
+            };
+            ensure!(condition_met, Error::<T>::EscrowConditionNotMet);
+            
+            Self::do_release_escrow(escrow_id, escrow)
+        }
+        
+        /// Annuler un séquestre et rembourser l'expéditeur
+        ///
+        /// Le destinataire peut renoncer à tout moment; l'expéditeur seulement après `refund_after`.
+        #[weight = 10_000]
+        pub fn cancel_escrow(
+            origin,
+            escrow_id: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let escrow = <Escrows<T>>::get(escrow_id).ok_or(Error::<T>::EscrowNotFound)?;
+            let now = <system::Module<T>>::block_number();
+            let allowed = who == escrow.recipient
+                || (who == escrow.sender && escrow.refund_after.map_or(false, |block| now >= block));
+            ensure!(allowed, Error::<T>::EscrowCancellationNotAllowed);
+            
+            Self::do_refund_escrow(escrow_id, escrow)
+        }
+        
+        /// Programmer de nouveaux taux de brûlage et de part ONG (en centièmes de pourcentage)
+        #[weight = 10_000]
+        pub fn schedule_fee_rates(
+            origin,
+            burn_rate: u32,
+            ngo_rate: u32,
+        ) -> DispatchResult {
+            T::FeeRateOrigin::ensure_origin(origin)?;
+            
+            Self::do_schedule_fee_rates(burn_rate, ngo_rate)?;
+            
+            Ok(())
+        }
+        
+        /// Activer ou désactiver le suivi non fongible des tokens reçus par le compte
+        #[weight = 10_000]
+        pub fn set_token_tracking(
+            origin,
+            enabled: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Les tokens déjà suivis le restent jusqu'à leur consommation
+            <TokenTracking<T>>::insert(&who, enabled);
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::TokenTrackingUpdated(who, enabled));
+            
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Distribuer les tokens à tous les acteurs
+    ///
+    /// Retourne le nombre d'acteurs parcourus.
+    fn distribute_tokens(current_block: T::BlockNumber) -> u32 {
+        let mut actors = 0;
+        
+        // Parcourir tous les acteurs et leur distribuer des tokens selon leur type
+        for (account, actor_type) in <ActorTypes<T>>::iter() {
+            actors += 1;
+            let distribution_amount = match actor_type {
+                ActorType::Consumer => T::ConsumerDistributionAmount::get(),
+                ActorType::Merchant => T::MerchantDistributionAmount::get(),
+                ActorType::Supplier => T::SupplierDistributionAmount::get(),
+                // Pas de distribution automatique pour les autres types d'acteurs
+                _ => 0,
+            };
+            
+            if distribution_amount > 0 {
+                // Distribuer des tokens latents
+                let current_balance = <LatentTokenBalances<T>>::get(&account);
+                let new_balance = current_balance.saturating_add(distribution_amount);
+                
+                if new_balance <= T::MaxTokenBalance::get() {
+                    let issued = Self::draw_from_redistribution_pool(distribution_amount);
+                    
+                    <LatentTokenBalances<T>>::insert(&account, new_balance);
+                    <DistributionHistory<T>>::insert(&account, current_block, distribution_amount);
+                    <TotalDistributedTokens>::mutate(|total| *total = total.saturating_add(issued));
+                    Self::add_latent_batch(&account, distribution_amount, current_block);
+                    if <TokenTracking<T>>::get(&account) {
+                        Self::mint_tracked_token(&account, distribution_amount, TokenState::Latent, Vec::new());
+                    }
+                    
+                    // Émettre un événement
+                    Self::deposit_event(RawEvent::TokensDistributed(account, distribution_amount, TokenState::Latent));
+                }
+            }
+        }
+        
+        actors
+    }
+    
+    /// Puiser une distribution d'abord dans la réserve de redistribution
+    ///
+    /// Retourne le complément à émettre.
+    fn draw_from_redistribution_pool(amount: Balance) -> Balance {
+        let redistributed = <RedistributionPool>::mutate(|pool| {
+            let redistributed = amount.min(*pool);
+            *pool -= redistributed;
+            redistributed
+        });
+        
+        amount - redistributed
+    }
+    
+    /// Durée de vie des tokens latents d'un compte, en blocs (None si sans expiration)
+    fn latent_lifetime(account: &T::AccountId) -> Option<T::BlockNumber> {
+        let periods = match <ActorTypes<T>>::get(account) {
+            ActorType::Consumer => T::ConsumerLatentLifetime::get(),
+            ActorType::Merchant => T::MerchantLatentLifetime::get(),
+            ActorType::Supplier => T::SupplierLatentLifetime::get(),
+            _ => 0,
+        };
+        
+        if periods == 0 {
+            return None;
+        }
+        
+        Some(T::DistributionPeriod::get().saturating_mul(periods.into()))
+    }
+    
+    /// Enregistrer un lot de tokens latents reçu par un compte
+    ///
+    /// Un nouveau lot soumis à expiration inscrit le compte dans la file d'expiration latente,
+    /// au bloc de son avertissement et au bloc de son échéance.
+    fn add_latent_batch(account: &T::AccountId, amount: Balance, now: T::BlockNumber) {
+        let expires_at = Self::latent_lifetime(account).map(|lifetime| now.saturating_add(lifetime));
+        
+        let added = <LatentBatches<T>>::mutate(account, |batches| {
+            // File pleine: le montant rejoint le lot le plus récent et conserve son échéance
+            if batches.len() as u32 >= T::MaxLatentBatches::get() {
+                if let Some(last) = batches.last_mut() {
+                    last.amount = last.amount.saturating_add(amount);
+                    return false;
+                }
+            }
+            
+            batches.push(LatentBatch {
+                distributed_at: now,
+                amount,
+                expires_at,
+                warned: false,
+            });
+            true
+        });
+        
+        if let (true, Some(expires_at)) = (added, expires_at) {
+            let warning = T::DistributionPeriod::get().saturating_mul(T::LatentExpiryWarning::get().into());
+            let warn_at = expires_at.saturating_sub(warning).max(now.saturating_add(1u32.into()));
+            if warn_at < expires_at {
+                <LatentExpiryQueue<T>>::append(warn_at, account.clone());
+            }
+            <LatentExpiryQueue<T>>::append(expires_at, account.clone());
+        }
+    }
+    
+    /// Retirer des tokens activés des lots latents, du plus ancien au plus récent
+    fn consume_latent_batches(account: &T::AccountId, amount: Balance) {
+        let mut batches = <LatentBatches<T>>::take(account);
+        let mut remaining = amount;
+        
+        for batch in batches.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            let consumed = batch.amount.min(remaining);
+            batch.amount -= consumed;
+            remaining -= consumed;
+        }
+        
+        batches.retain(|batch| batch.amount > 0);
+        if !batches.is_empty() {
+            <LatentBatches<T>>::insert(account, batches);
+        }
+    }
+    
+    /// Parcourir la file d'expiration latente, du plus ancien compartiment en attente au bloc courant
+    ///
+    /// Au plus `MaxLatentExpiriesPerBlock` compartiments sont lus et autant de comptes examinés;
+    /// les comptes non traités restent dans leur compartiment, d'où le parcours reprend au bloc suivant.
+    /// Retourne le nombre de compartiments lus et de comptes examinés.
+    fn process_latent_expiries(now: T::BlockNumber) -> (u32, u32) {
+        sweep_queue::<LatentExpiryQueue<T>, LatentExpiryCursor<T>, _, _>(
+            now,
+            T::MaxLatentExpiriesPerBlock::get(),
+            |account, _| Self::expire_latent_batches(&account, now),
+        )
+    }
+    
+    /// Faire expirer les lots latents d'un compte arrivés à échéance et avertir des expirations prochaines
+    fn expire_latent_batches(account: &T::AccountId, now: T::BlockNumber) {
+        let warning = T::DistributionPeriod::get().saturating_mul(T::LatentExpiryWarning::get().into());
+        let mut batches = <LatentBatches<T>>::get(account);
+        let mut expired: Balance = 0;
+        
+        for batch in batches.iter_mut() {
+            if let Some(expires_at) = batch.expires_at {
+                if expires_at <= now {
+                    expired = expired.saturating_add(batch.amount);
+                    batch.amount = 0;
+                } else if !batch.warned && expires_at.saturating_sub(warning) <= now {
+                    // Permettre aux portefeuilles de prévenir l'utilisateur
+                    batch.warned = true;
+                    Self::deposit_event(RawEvent::LatentTokensExpiring(account.clone(), batch.amount, expires_at));
+                }
+            }
+        }
+        
+        if expired > 0 {
+            let latent_balance = <LatentTokenBalances<T>>::get(account);
+            let expired = expired.min(latent_balance);
+            
+            <LatentTokenBalances<T>>::insert(account, latent_balance.saturating_sub(expired));
+            Self::take_tracked_tokens(account, expired, TokenState::Latent);
+            <RedistributionPool>::mutate(|pool| *pool = pool.saturating_add(expired));
+            <TotalExpiredTokens>::mutate(|total| *total = total.saturating_add(expired));
+            
+            // Émettre un événement
+            Self::deposit_event(RawEvent::LatentTokensExpired(account.clone(), expired));
+        }
+        
+        batches.retain(|batch| batch.amount > 0);
+        if batches.is_empty() {
+            <LatentBatches<T>>::remove(account);
+        } else {
+            <LatentBatches<T>>::insert(account, batches);
+        }
+    }
+    
+    /// Créer un token suivi pour un compte
+    ///
+    /// Au-delà de `MaxTrackedTokensPerAccount`, la valeur reste uniquement fongible.
+    fn mint_tracked_token(owner: &T::AccountId, value: Balance, state: TokenState, parents: Vec<Token<T::AccountId>>) {
+        let mut owned = <OwnedTrackedTokens<T>>::get(owner);
+        if value == 0 || owned.len() as u32 >= T::MaxTrackedTokensPerAccount::get() {
+            return;
+        }
+        
+        let nonce = <TrackedTokenNonce>::mutate(|nonce| {
+            *nonce = nonce.wrapping_add(1);
+            *nonce
+        });
+        let id = sp_io::hashing::blake2_256(
+            &(b"etika/tracked-token", owner, <system::Module<T>>::block_number(), nonce).encode()
+        );
+        let now = Self::get_current_timestamp();
+        
+        // Lignée: parents directs puis leurs propres ancêtres, sans doublon
+        let mut lineage: Vec<[u8; 32]> = parents.iter().map(|parent| parent.id).collect();
+        for ancestor in parents.iter().flat_map(|parent| parent.lineage.iter()) {
+            if !lineage.contains(ancestor) {
+                lineage.push(*ancestor);
+            }
+        }
+        lineage.truncate(T::MaxTokenLineage::get() as usize);
+        
+        // Historique: détenteurs précédents puis le nouveau propriétaire s'il change
+        let mut transfer_history: Vec<(T::AccountId, Moment)> = parents
+            .iter()
+            .flat_map(|parent| parent.transfer_history.iter().cloned())
+            .collect();
+        if transfer_history.last().map_or(true, |(holder, _)| holder != owner) {
+            transfer_history.push((owner.clone(), now));
+        }
+        if transfer_history.len() > TOKEN_TRANSFER_HISTORY_LIMIT {
+            transfer_history.drain(..transfer_history.len() - TOKEN_TRANSFER_HISTORY_LIMIT);
+        }
+        
+        let token = Token {
+            id,
+            state,
+            owner: owner.clone(),
+            created_at: now,
+            updated_at: now,
+            value,
+            transfer_history,
+            lineage: lineage.clone(),
+        };
+        
+        <TrackedTokens<T>>::insert(id, token);
+        owned.push(id);
+        <OwnedTrackedTokens<T>>::insert(owner, owned);
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::TrackedTokenCreated(id, owner.clone(), value, lineage));
+    }
+    
+    /// Retirer de la valeur aux tokens suivis d'un compte dans un état donné, du plus ancien au plus récent
+    ///
+    /// Retourne les portions prélevées, chacune avec la valeur retirée au token d'origine.
+    fn take_tracked_tokens(owner: &T::AccountId, amount: Balance, state: TokenState) -> Vec<Token<T::AccountId>> {
+        let mut owned = <OwnedTrackedTokens<T>>::get(owner);
+        let mut taken = Vec::new();
+        let mut remaining = amount;
+        
+        owned.retain(|id| {
+            if remaining == 0 {
+                return true;
+            }
+            let mut token = match <TrackedTokens<T>>::get(id) {
+                Some(token) if token.state == state => token,
+                Some(_) => return true,
+                None => return false,
+            };
+            
+            let portion = token.value.min(remaining);
+            remaining -= portion;
+            
+            let mut part = token.clone();
+            part.value = portion;
+            taken.push(part);
+            
+            if portion == token.value {
+                <TrackedTokens<T>>::remove(id);
+                false
+            } else {
+                token.value -= portion;
+                token.updated_at = Self::get_current_timestamp();
+                <TrackedTokens<T>>::insert(id, token);
+                true
+            }
+        });
+        
+        if owned.is_empty() {
+            <OwnedTrackedTokens<T>>::remove(owner);
+        } else {
+            <OwnedTrackedTokens<T>>::insert(owner, owned);
+        }
+        
+        taken
+    }
+    
+    /// Faire passer des tokens suivis d'un état à un autre, éventuellement vers un autre compte
+    fn move_tracked_tokens(from: &T::AccountId, to: &T::AccountId, amount: Balance, from_state: TokenState, to_state: TokenState) {
+        let parents = Self::take_tracked_tokens(from, amount, from_state);
+        let value = parents.iter().fold(0, |total: Balance, parent| total.saturating_add(parent.value));
+        Self::mint_tracked_token(to, value, to_state, parents);
+    }
+    
+    /// Suivre un transfert de tokens actifs
+    ///
+    /// Le destinataire reçoit un lot suivi s'il a activé le suivi ou s'il s'agit d'une ONG.
+    fn transfer_tracked_tokens(from: &T::AccountId, to: &T::AccountId, amount: Balance, received: Balance) {
+        let parents = Self::take_tracked_tokens(from, amount, TokenState::Active);
+        if parents.is_empty() || !(<TokenTracking<T>>::get(to) || <ActorTypes<T>>::get(to) == ActorType::NGO) {
+            return;
+        }
+        
+        let value = parents.iter().fold(0, |total: Balance, parent| total.saturating_add(parent.value));
+        Self::mint_tracked_token(to, value.min(received), TokenState::Active, parents);
+    }
+    
+    /// Transférer des tokens actifs à une ONG, sans brûlage ni part ONG, et les inscrire à son registre
+    fn do_transfer_to_ngo(from: &T::AccountId, ngo: &T::AccountId, amount: Balance) -> DispatchResult {
+        // Vérifier que la destination est bien une ONG
+        let ngo_type = <ActorTypes<T>>::get(ngo);
+        ensure!(ngo_type == ActorType::NGO, Error::<T>::NotRegisteredAsNGO);
+        
+        // Vérifier que le compte a suffisamment de tokens actifs
+        let from_balance = <ActiveTokenBalances<T>>::get(from);
+        ensure!(from_balance >= amount, Error::<T>::InsufficientActiveBalance);
+        
+        let ngo_balance = <ActiveTokenBalances<T>>::get(ngo);
+        ensure!(
+            ngo_balance.saturating_add(amount) <= T::MaxTokenBalance::get(),
+            Error::<T>::TokenBalanceOverflow
+        );
+        
+        // Mettre à jour les soldes
+        <ActiveTokenBalances<T>>::insert(from, from_balance.saturating_sub(amount));
+        <ActiveTokenBalances<T>>::insert(ngo, ngo_balance.saturating_add(amount));
+        
+        // Ajouter à l'historique des transferts
+        Self::add_to_transfer_history(from, ngo, amount);
+        
+        // Le don devient un lot suivi dont la lignée remonte aux lots du donateur
+        Self::transfer_tracked_tokens(from, ngo, amount, amount);
+        
+        // Mettre à jour les compteurs et le registre public de l'ONG
+        <TotalNGOTokens>::mutate(|total| *total = total.saturating_add(amount));
+        <NgoLedgers<T>>::mutate(ngo, |ledger| ledger.received = ledger.received.saturating_add(amount));
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::TokensTransferredToNGO(from.clone(), ngo.clone(), amount));
+        
+        Ok(())
+    }
+    
+    /// Retirer un séquestre de son déclencheur et du solde en séquestre de l'expéditeur, et rendre le dépôt
+    ///
+    /// Un déclencheur déjà vidé (`take`) n'est pas réécrit; une liste devenue vide est supprimée.
+    fn remove_escrow(escrow_id: u64, escrow: &Escrow<T::AccountId, T::BlockNumber>) {
+        match escrow.condition {
+            EscrowCondition::AtBlock(block) => {
+                <EscrowReleaseQueue<T>>::mutate_exists(block, |queue| {
+                    if let Some(ids) = queue {
+                        ids.retain(|id| *id != escrow_id);
+                        if ids.is_empty() {
+                            *queue = None;
+                        }
+                    }
+                });
+            }
+            EscrowCondition::AtTime(_) => {}
+            EscrowCondition::PopFinalized(transaction_id) => {
+                <PopEscrows>::mutate_exists(transaction_id, |escrows| {
+                    if let Some(ids) = escrows {
+                        ids.retain(|id| *id != escrow_id);
+                        if ids.is_empty() {
+                            *escrows = None;
+                        }
+                    }
+                });
+            }
+        }
+        
+        <EscrowedTokenBalances<T>>::mutate(&escrow.sender, |escrowed| *escrowed = escrowed.saturating_sub(escrow.amount));
+        if escrow.deposit > 0 {
+            let _ = Self::unreserve_tokens(&escrow.sender, escrow.deposit);
+        }
+        <Escrows<T>>::remove(escrow_id);
+    }
+    
+    /// Rendre les tokens actifs d'un séquestre à son expéditeur
+    fn do_refund_escrow(escrow_id: u64, escrow: Escrow<T::AccountId, T::BlockNumber>) -> DispatchResult {
+        let active_balance = <ActiveTokenBalances<T>>::get(&escrow.sender);
+        let new_active_balance = active_balance.saturating_add(escrow.amount);
+        ensure!(
+            new_active_balance.saturating_add(escrow.deposit) <= T::MaxTokenBalance::get(),
+            Error::<T>::TokenBalanceOverflow
+        );
+        
+        <ActiveTokenBalances<T>>::insert(&escrow.sender, new_active_balance);
+        Self::remove_escrow(escrow_id, &escrow);
+        Self::move_tracked_tokens(&escrow.sender, &escrow.sender, escrow.amount, escrow.locked_state(), TokenState::Active);
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::EscrowRefunded(escrow_id, escrow.sender, escrow.amount));
+        
+        Ok(())
+    }
+    
+    /// Verser un séquestre à son destinataire, aux mêmes conditions qu'un transfert
+    fn do_release_escrow(escrow_id: u64, escrow: Escrow<T::AccountId, T::BlockNumber>) -> DispatchResult {
+        let (burn_amount, ngo_amount, transfer_amount) = Self::calculate_token_distribution(escrow.amount);
+        
+        let recipient_balance = <ActiveTokenBalances<T>>::get(&escrow.recipient);
+        let new_recipient_balance = recipient_balance.saturating_add(transfer_amount);
+        ensure!(new_recipient_balance <= T::MaxTokenBalance::get(), Error::<T>::TokenBalanceOverflow);
+        
+        Self::remove_escrow(escrow_id, &escrow);
+        <ActiveTokenBalances<T>>::insert(&escrow.recipient, new_recipient_balance);
+        
+        // Mettre à jour les compteurs
+        <TotalBurnedTokens>::mutate(|total| *total = total.saturating_add(burn_amount));
+        <TotalNGOTokens>::mutate(|total| *total = total.saturating_add(ngo_amount));
+        
+        Self::add_to_transfer_history(&escrow.sender, &escrow.recipient, transfer_amount);
+        
+        // Les lots suivis verrouillés passent au destinataire
+        let parents = Self::take_tracked_tokens(&escrow.sender, escrow.amount, escrow.locked_state());
+        if !parents.is_empty() && <TokenTracking<T>>::get(&escrow.recipient) {
+            let value = parents.iter().fold(0, |total: Balance, parent| total.saturating_add(parent.value));
+            Self::mint_tracked_token(&escrow.recipient, value.min(transfer_amount), TokenState::Active, parents);
+        }
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::EscrowReleased(escrow_id, escrow.recipient, transfer_amount));
+        
+        Ok(())
+    }
+    
+    /// Libérer les séquestres programmés pour un bloc
+    ///
+    /// Un séquestre dont la libération échoue reste libérable par `release_escrow`.
+    /// Retourne le nombre de séquestres programmés.
+    fn process_escrow_releases(now: T::BlockNumber) -> u32 {
+        let escrow_ids = <EscrowReleaseQueue<T>>::take(now);
+        let count = escrow_ids.len() as u32;
+        
+        for escrow_id in escrow_ids {
+            if let Some(escrow) = <Escrows<T>>::get(escrow_id) {
+                let _ = Self::do_release_escrow(escrow_id, escrow);
+            }
         }
         
-        /// Mettre à jour le type d'acteur pour un compte
-        #[weight = 10_000]
-        pub fn update_actor_type(
-            origin,
-            account: T::AccountId,
-            actor_type: ActorType,
-        ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            
-            // Ici, on pourrait vérifier que le compte a les permissions pour effectuer cette opération
-            // Pour l'instant, nous supposons que cette fonction est accessible par les administrateurs
-            
-            <ActorTypes<T>>::insert(&account, actor_type);
-            
-            // Émettre un événement
-            Self::deposit_event(RawEvent::ActorTypeUpdated(account, actor_type));
-            
-            Ok(())
+        count
+    }
+    
+    /// Tokens suivis détenus par un compte (API runtime `EtikaTokenLineageApi`)
+    pub fn tracked_tokens_of(account: &T::AccountId) -> Vec<Token<T::AccountId>> {
+        <OwnedTrackedTokens<T>>::get(account)
+            .iter()
+            .filter_map(|id| <TrackedTokens<T>>::get(id))
+            .collect()
+    }
+    
+    /// Taux de brûlage et part ONG en vigueur (en centièmes de pourcentage)
+    pub fn fee_rates() -> (u32, u32) {
+        Self::fee_rates_override().unwrap_or_else(|| (T::BurnRate::get(), T::NGORate::get()))
+    }
+    
+    /// Programmer de nouveaux taux après le préavis, dans la limite de variation par période
+    fn do_schedule_fee_rates(burn_rate: u32, ngo_rate: u32) -> Result<T::BlockNumber, Error<T>> {
+        ensure!(burn_rate.saturating_add(ngo_rate) <= 10_000, Error::<T>::InvalidFeeRates);
+        ensure!(Self::pending_fee_rates().is_none(), Error::<T>::FeeRateChangePending);
+        
+        let now = <system::Module<T>>::block_number();
+        if let Some(last_change) = Self::last_fee_rate_change() {
+            ensure!(
+                now >= last_change.saturating_add(T::FeeRateChangePeriod::get()),
+                Error::<T>::FeeRateChangeTooSoon
+            );
         }
+        
+        // La variation est mesurée par rapport aux taux en vigueur
+        let (current_burn_rate, current_ngo_rate) = Self::fee_rates();
+        let max_change = T::MaxFeeRateChange::get();
+        ensure!(
+            burn_rate.max(current_burn_rate) - burn_rate.min(current_burn_rate) <= max_change
+                && ngo_rate.max(current_ngo_rate) - ngo_rate.min(current_ngo_rate) <= max_change,
+            Error::<T>::FeeRateChangeTooLarge
+        );
+        
+        let activation = now.saturating_add(T::FeeRateNoticePeriod::get());
+        <PendingFeeRates<T>>::put((burn_rate, ngo_rate, activation));
+        <LastFeeRateChange<T>>::put(now);
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::FeeRatesScheduled(burn_rate, ngo_rate, activation));
+        
+        Ok(activation)
     }
-}
-
-impl<T: Config> Module<T> {
-    /// Distribuer les tokens à tous les acteurs
-    fn distribute_tokens(current_block: T::BlockNumber) {
-        // Parcourir tous les acteurs et leur distribuer des tokens selon leur type
-        for (account, actor_type) in <ActorTypes<T>>::iter() {
-            let distribution_amount = match actor_type {
-                ActorType::Consumer => T::ConsumerDistributionAmount::get(),
-                ActorType::Merchant => T::MerchantDistributionAmount::get(),
-                ActorType::Supplier => T::SupplierDistributionAmount::get(),
-                // Pas de distribution automatique pour les autres types d'acteurs
-                _ => 0,
-            };
-            
-            if distribution_amount > 0 {
-                // Distribuer des tokens latents
-                let current_balance = <LatentTokenBalances<T>>::get(&account);
-                let new_balance = current_balance.saturating_add(distribution_amount);
+    
+    /// Appliquer les taux programmés dont le préavis est écoulé
+    fn activate_pending_fee_rates(now: T::BlockNumber) {
+        if let Some((burn_rate, ngo_rate, activation)) = Self::pending_fee_rates() {
+            if now >= activation {
+                <FeeRatesOverride>::put((burn_rate, ngo_rate));
+                <PendingFeeRates<T>>::kill();
                 
-                if new_balance <= T::MaxTokenBalance::get() {
-                    <LatentTokenBalances<T>>::insert(&account, new_balance);
-                    <DistributionHistory<T>>::insert(&account, current_block, distribution_amount);
-                    <TotalDistributedTokens>::mutate(|total| *total = total.saturating_add(distribution_amount));
-                    
-                    // Émettre un événement
-                    Self::deposit_event(RawEvent::TokensDistributed(account, distribution_amount, TokenState::Latent));
-                }
+                // Émettre un événement
+                Self::deposit_event(RawEvent::FeeRatesActivated(burn_rate, ngo_rate));
             }
         }
     }
     
     /// Calculer la répartition des tokens lors d'un transfert (montant brûlé, montant ONG, montant transféré)
     fn calculate_token_distribution(amount: Balance) -> (Balance, Balance, Balance) {
-        let burn_rate = T::BurnRate::get() as u128;
-        let ngo_rate = T::NGORate::get() as u128;
+        let (burn_rate, ngo_rate) = Self::fee_rates();
+        let burn_rate = burn_rate as u128;
+        let ngo_rate = ngo_rate as u128;
         
         // Calculer les montants (avec protection contre les overflow)
         let denominator = 10000u128; // 100.00%
@@ -470,12 +1551,16 @@ impl<T: Config> Module<T> {
     }
     
     /// Traiter les déverrouillages automatiques de tokens
-    fn process_token_unlocks() {
+    ///
+    /// Retourne le nombre de comptes parcourus.
+    fn process_token_unlocks() -> u32 {
         let current_time = Self::get_current_timestamp();
+        let mut accounts = 0;
         
         // On pourrait optimiser en utilisant une file d'attente ordonnée
         // Pour l'instant, on parcourt tous les comptes avec des tokens verrouillés
         for (account, locked_balance) in <LockedTokenBalances<T>>::iter() {
+            accounts += 1;
             if locked_balance > 0 {
                 let unlock_time = <TokenUnlockTime<T>>::get(&account);
                 
@@ -494,6 +1579,8 @@ impl<T: Config> Module<T> {
                 }
             }
         }
+        
+        accounts
     }
     
     /// Obtenir le timestamp actuel en secondes
@@ -515,8 +1602,15 @@ impl<T: Config> TokenSystem for Module<T> {
             return Err("Token balance overflow");
         }
         
+        // Comme la distribution périodique, puiser d'abord dans la réserve de redistribution
+        let issued = Self::draw_from_redistribution_pool(amount);
+        
         <LatentTokenBalances<T>>::insert(to, new_balance);
-        <TotalDistributedTokens>::mutate(|total| *total = total.saturating_add(amount));
+        <TotalDistributedTokens>::mutate(|total| *total = total.saturating_add(issued));
+        Self::add_latent_batch(to, amount, <system::Module<T>>::block_number());
+        if <TokenTracking<T>>::get(to) {
+            Self::mint_tracked_token(to, amount, TokenState::Latent, Vec::new());
+        }
         
         // Émettre un événement
         Self::deposit_event(RawEvent::TokensDistributed(to.clone(), amount, TokenState::Latent));
@@ -543,6 +1637,8 @@ impl<T: Config> TokenSystem for Module<T> {
         }
         
         <ActiveTokenBalances<T>>::insert(from, new_active_balance);
+        Self::consume_latent_batches(from, amount);
+        Self::move_tracked_tokens(from, from, amount, TokenState::Latent, TokenState::Active);
         
         // Émettre un événement
         Self::deposit_event(RawEvent::TokensActivated(from.clone(), amount));
@@ -550,6 +1646,27 @@ impl<T: Config> TokenSystem for Module<T> {
         Ok(())
     }
     
+    fn deactivate_tokens(from: &T::AccountId, amount: Balance) -> Result<(), &'static str> {
+        let active_balance = <ActiveTokenBalances<T>>::get(from);
+        if active_balance < amount {
+            return Err("Insufficient active balance");
+        }
+        
+        let latent_balance = <LatentTokenBalances<T>>::get(from);
+        let new_latent_balance = latent_balance.checked_add(amount).ok_or("Arithmetic overflow")?;
+        
+        <ActiveTokenBalances<T>>::insert(from, active_balance.saturating_sub(amount));
+        <LatentTokenBalances<T>>::insert(from, new_latent_balance);
+        // Les tokens repassés à l'état latent repartent pour une durée de vie complète
+        Self::add_latent_batch(from, amount, <system::Module<T>>::block_number());
+        Self::move_tracked_tokens(from, from, amount, TokenState::Active, TokenState::Latent);
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::TokensDeactivated(from.clone(), amount));
+        
+        Ok(())
+    }
+    
     fn burn_tokens(from: &T::AccountId, amount: Balance) -> Result<(), &'static str> {
         let active_balance = <ActiveTokenBalances<T>>::get(from);
         if active_balance < amount {
@@ -601,6 +1718,7 @@ impl<T: Config> TokenSystem for Module<T> {
         
         // Ajouter à l'historique des transferts
         Self::add_to_transfer_history(from, to, transfer_amount);
+        Self::transfer_tracked_tokens(from, to, amount, transfer_amount);
         
         // Émettre un événement
         Self::deposit_event(RawEvent::TokensTransferred(from.clone(), to.clone(), transfer_amount));
@@ -618,6 +1736,79 @@ impl<T: Config> TokenSystem for Module<T> {
         
         Ok(())
     }
+    
+    fn active_balance(account: &T::AccountId) -> Balance {
+        <ActiveTokenBalances<T>>::get(account)
+    }
+    
+    fn reserve_tokens(account: &T::AccountId, amount: Balance) -> Result<(), &'static str> {
+        let active_balance = <ActiveTokenBalances<T>>::get(account);
+        if active_balance < amount {
+            return Err("Insufficient active balance");
+        }
+        
+        <ActiveTokenBalances<T>>::insert(account, active_balance.saturating_sub(amount));
+        <ReservedTokenBalances<T>>::mutate(account, |reserved| *reserved = reserved.saturating_add(amount));
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::TokensReserved(account.clone(), amount));
+        
+        Ok(())
+    }
+    
+    fn unreserve_tokens(account: &T::AccountId, amount: Balance) -> Result<(), &'static str> {
+        let reserved_balance = <ReservedTokenBalances<T>>::get(account);
+        if reserved_balance < amount {
+            return Err("Insufficient reserved balance");
+        }
+        
+        let active_balance = <ActiveTokenBalances<T>>::get(account);
+        let new_active_balance = active_balance.checked_add(amount).ok_or("Arithmetic overflow")?;
+        
+        <ReservedTokenBalances<T>>::insert(account, reserved_balance.saturating_sub(amount));
+        <ActiveTokenBalances<T>>::insert(account, new_active_balance);
+        
+        // Émettre un événement
+        Self::deposit_event(RawEvent::TokensUnreserved(account.clone(), amount));
+        
+        Ok(())
+    }
+}
+
+/// Programmation des taux par une proposition DAO du fonds des consommateurs
+impl<T: Config> TokenFeeGovernance for Module<T> {
+    fn schedule_fee_rates(burn_rate: u32, ngo_rate: u32) -> Result<(), &'static str> {
+        Self::do_schedule_fee_rates(burn_rate, ngo_rate)
+            .map(|_| ())
+            .map_err(|error| match error {
+                Error::<T>::InvalidFeeRates => "Invalid fee rates",
+                Error::<T>::FeeRateChangeTooLarge => "Fee rate change too large",
+                Error::<T>::FeeRateChangeTooSoon => "Fee rate change too soon",
+                _ => "Fee rate change already scheduled",
+            })
+    }
+}
+
+/// Règlement des séquestres conditionnés à une transaction PoP: libération lorsque la
+/// transaction est définitive, remboursement lorsqu'elle est annulée
+impl<T: Config> OnPopFinalized for Module<T> {
+    fn on_pop_finalized(transaction_id: &[u8; 32]) {
+        for escrow_id in <PopEscrows>::take(transaction_id) {
+            if let Some(escrow) = <Escrows<T>>::get(escrow_id) {
+                // En cas d'échec, l'expéditeur pourra récupérer les tokens après l'échéance
+                let _ = Self::do_release_escrow(escrow_id, escrow);
+            }
+        }
+    }
+    
+    fn on_pop_reversed(transaction_id: &[u8; 32]) {
+        for escrow_id in <PopEscrows>::take(transaction_id) {
+            if let Some(escrow) = <Escrows<T>>::get(escrow_id) {
+                // En cas d'échec, l'expéditeur pourra récupérer les tokens après l'échéance
+                let _ = Self::do_refund_escrow(escrow_id, escrow);
+            }
+        }
+    }
 }
 
 /// Tests pour le module token system
@@ -687,6 +1878,21 @@ mod tests {
         pub const NGORate: u32 = 200; // 2.00%
         pub const MaxTokenBalance: Balance = 1_000_000_000;
         pub const MaxTransferHistoryEntries: u32 = 10;
+        pub const ConsumerLatentLifetime: u32 = 2;
+        pub const MerchantLatentLifetime: u32 = 0;
+        pub const SupplierLatentLifetime: u32 = 3;
+        pub const LatentExpiryWarning: u32 = 1;
+        pub const MaxLatentBatches: u32 = 4;
+        pub const MaxLatentExpiriesPerBlock: u32 = 2;
+        pub const MaxTrackedTokensPerAccount: u32 = 8;
+        pub const MaxTokenLineage: u32 = 4;
+        pub const MaxEscrowsPerTrigger: u32 = 2;
+        pub const EscrowDeposit: Balance = 10;
+        pub const MaxFeeRateChange: u32 = 100; // 1.00%
+        pub const FeeRateChangePeriod: u64 = 50;
+        pub const FeeRateNoticePeriod: u64 = 10;
+        pub const MaxNgoNameLength: u32 = 32;
+        pub const MaxNgoProjects: u32 = 2;
     }
     
     impl Config for Test {
@@ -699,6 +1905,23 @@ mod tests {
         type NGORate = NGORate;
         type MaxTokenBalance = MaxTokenBalance;
         type MaxTransferHistoryEntries = MaxTransferHistoryEntries;
+        type ConsumerLatentLifetime = ConsumerLatentLifetime;
+        type MerchantLatentLifetime = MerchantLatentLifetime;
+        type SupplierLatentLifetime = SupplierLatentLifetime;
+        type LatentExpiryWarning = LatentExpiryWarning;
+        type MaxLatentBatches = MaxLatentBatches;
+        type MaxLatentExpiriesPerBlock = MaxLatentExpiriesPerBlock;
+        type MaxTrackedTokensPerAccount = MaxTrackedTokensPerAccount;
+        type MaxTokenLineage = MaxTokenLineage;
+        type MaxEscrowsPerTrigger = MaxEscrowsPerTrigger;
+        type EscrowDeposit = EscrowDeposit;
+        type FeeRateOrigin = frame_system::EnsureRoot<u64>;
+        type ActorTypeOrigin = frame_system::EnsureRoot<u64>;
+        type MaxFeeRateChange = MaxFeeRateChange;
+        type FeeRateChangePeriod = FeeRateChangePeriod;
+        type FeeRateNoticePeriod = FeeRateNoticePeriod;
+        type MaxNgoNameLength = MaxNgoNameLength;
+        type MaxNgoProjects = MaxNgoProjects;
     }
     
     // Fonction utilitaire pour créer un environnement de test
@@ -718,9 +1941,9 @@ mod tests {
             let merchant = 2;
             let supplier = 3;
             
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), consumer, ActorType::Consumer).unwrap();
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), merchant, ActorType::Merchant).unwrap();
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), supplier, ActorType::Supplier).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), consumer, ActorType::Consumer).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), merchant, ActorType::Merchant).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), supplier, ActorType::Supplier).unwrap();
             
             // Avancer jusqu'au bloc de distribution
             System::set_block_number(100);
@@ -763,6 +1986,26 @@ mod tests {
         });
     }
     
+    #[test]
+    fn test_deactivate_tokens() {
+        new_test_ext().execute_with(|| {
+            let account = 1;
+            <ActiveTokenBalances<Test>>::insert(account, 700);
+            
+            // Les tokens actifs repassent à l'état latent
+            assert_ok!(<EtikaTokenSystem as TokenSystem>::deactivate_tokens(&account, 300));
+            assert_eq!(EtikaTokenSystem::active_token_balances(account), 400);
+            assert_eq!(EtikaTokenSystem::latent_token_balances(account), 300);
+            
+            // Impossible de désactiver plus que le solde actif
+            assert_eq!(
+                <EtikaTokenSystem as TokenSystem>::deactivate_tokens(&account, 401),
+                Err("Insufficient active balance")
+            );
+            assert_eq!(EtikaTokenSystem::active_token_balances(account), 400);
+        });
+    }
+    
     #[test]
     fn test_transfer_tokens() {
         new_test_ext().execute_with(|| {
@@ -802,7 +2045,7 @@ mod tests {
             <ActiveTokenBalances<Test>>::insert(from, 1000);
             
             // Configurer l'ONG
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), ngo, ActorType::NGO).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), ngo, ActorType::NGO).unwrap();
             
             // Transférer des tokens à l'ONG
             assert_ok!(EtikaTokenSystem::transfer_to_ngo(Origin::signed(from), ngo, 500));
@@ -814,7 +2057,7 @@ mod tests {
             
             // Tentative de transfert à un non-ONG
             let not_ngo = 3;
-            EtikaTokenSystem::update_actor_type(Origin::signed(0), not_ngo, ActorType::Consumer).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), not_ngo, ActorType::Consumer).unwrap();
             
             assert_noop!(
                 EtikaTokenSystem::transfer_to_ngo(Origin::signed(from), not_ngo, 100),
@@ -855,4 +2098,377 @@ mod tests {
             assert_eq!(EtikaTokenSystem::locked_token_balances(account), 0);
         });
     }
+    
+    #[test]
+    fn test_latent_tokens_expire_into_redistribution_pool() {
+        new_test_ext().execute_with(|| {
+            let consumer = 1;
+            let merchant = 2;
+            EtikaTokenSystem::update_actor_type(Origin::root(), consumer, ActorType::Consumer).unwrap();
+            EtikaTokenSystem::update_actor_type(Origin::root(), merchant, ActorType::Merchant).unwrap();
+            
+            // Première distribution: le lot du consommateur expire deux périodes plus tard
+            System::set_block_number(100);
+            EtikaTokenSystem::on_initialize(100);
+            assert_eq!(
+                EtikaTokenSystem::latent_batches(consumer),
+                vec![LatentBatch { distributed_at: 100, amount: 1000, expires_at: Some(300), warned: false }]
+            );
+            // Les tokens latents des commerçants n'expirent pas
+            assert_eq!(EtikaTokenSystem::latent_batches(merchant)[0].expires_at, None);
+            
+            // L'activation consomme le lot le plus ancien
+            assert_ok!(EtikaTokenSystem::activate_tokens(Origin::signed(consumer), 400));
+            assert_eq!(EtikaTokenSystem::latent_batches(consumer)[0].amount, 600);
+            
+            // Une période avant l'échéance, le lot est signalé
+            System::set_block_number(200);
+            EtikaTokenSystem::on_initialize(200);
+            let batches = EtikaTokenSystem::latent_batches(consumer);
+            assert_eq!(batches.len(), 2);
+            assert!(batches[0].warned);
+            assert!(!batches[1].warned);
+            assert_eq!(EtikaTokenSystem::latent_token_balances(consumer), 1600);
+            assert_eq!(EtikaTokenSystem::total_distributed_tokens(), 6000);
+            
+            // À l'échéance, le reliquat non activé rejoint la réserve avant la distribution
+            System::set_block_number(300);
+            EtikaTokenSystem::process_latent_expiries(300);
+            assert_eq!(EtikaTokenSystem::latent_token_balances(consumer), 1000);
+            assert_eq!(EtikaTokenSystem::redistribution_pool(), 600);
+            assert_eq!(EtikaTokenSystem::total_expired_tokens(), 600);
+            
+            // La distribution suivante puise dans la réserve et n'émet que le complément
+            EtikaTokenSystem::distribute_tokens(300);
+            assert_eq!(EtikaTokenSystem::redistribution_pool(), 0);
+            assert_eq!(EtikaTokenSystem::total_distributed_tokens(), 6000 + 3000 - 600);
+            assert_eq!(EtikaTokenSystem::latent_token_balances(consumer), 2000);
+            assert_eq!(EtikaTokenSystem::latent_token_balances(merchant), 6000);
+            assert_eq!(EtikaTokenSystem::active_token_balances(consumer), 400);
+        });
+    }
+    
+    #[test]
+    fn test_direct_distribution_draws_from_redistribution_pool() {
+        new_test_ext().execute_with(|| {
+            let consumer = 1;
+            System::set_block_number(10);
+            <RedistributionPool>::put(150);
+            
+            // La réserve couvre entièrement la première distribution
+            assert_ok!(<EtikaTokenSystem as TokenSystem>::distribute_tokens(&consumer, 100));
+            assert_eq!(EtikaTokenSystem::redistribution_pool(), 50);
+            assert_eq!(EtikaTokenSystem::total_distributed_tokens(), 0);
+            
+            // Seul le complément de la seconde est émis
+            assert_ok!(<EtikaTokenSystem as TokenSystem>::distribute_tokens(&consumer, 100));
+            assert_eq!(EtikaTokenSystem::redistribution_pool(), 0);
+            assert_eq!(EtikaTokenSystem::total_distributed_tokens(), 50);
+            assert_eq!(EtikaTokenSystem::latent_token_balances(consumer), 200);
+        });
+    }
+    
+    #[test]
+    fn test_latent_expiries_are_bounded_per_block() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(10);
+            for consumer in 1..=3 {
+                EtikaTokenSystem::update_actor_type(Origin::root(), consumer, ActorType::Consumer).unwrap();
+                assert_ok!(<EtikaTokenSystem as TokenSystem>::distribute_tokens(&consumer, 100));
+            }
+            assert_eq!(EtikaTokenSystem::latent_expiry_queue(210), vec![1, 2, 3]);
+            
+            // Deux comptes au plus sont examinés par bloc, le reste est reporté
+            System::set_block_number(210);
+            EtikaTokenSystem::process_latent_expiries(210);
+            assert_eq!(EtikaTokenSystem::redistribution_pool(), 200);
+            assert_eq!(EtikaTokenSystem::latent_token_balances(3), 100);
+            assert_eq!(EtikaTokenSystem::latent_expiry_cursor(), Some(210));
+            
+            System::set_block_number(211);
+            EtikaTokenSystem::process_latent_expiries(211);
+            assert_eq!(EtikaTokenSystem::redistribution_pool(), 300);
+            assert_eq!(EtikaTokenSystem::latent_token_balances(3), 0);
+            assert!(!<LatentBatches<Test>>::contains_key(3));
+            assert_eq!(EtikaTokenSystem::latent_expiry_cursor(), None);
+        });
+    }
+    
+    #[test]
+    fn test_latent_batches_are_bounded() {
+        new_test_ext().execute_with(|| {
+            let supplier = 3;
+            EtikaTokenSystem::update_actor_type(Origin::root(), supplier, ActorType::Supplier).unwrap();
+            
+            System::set_block_number(10);
+            for _ in 0..6 {
+                assert_ok!(<EtikaTokenSystem as TokenSystem>::distribute_tokens(&supplier, 100));
+            }
+            
+            // Au-delà de la limite, les montants rejoignent le lot le plus récent
+            let batches = EtikaTokenSystem::latent_batches(supplier);
+            assert_eq!(batches.len(), 4);
+            assert_eq!(batches[3].amount, 300);
+            assert_eq!(batches[3].expires_at, Some(310));
+            assert_eq!(EtikaTokenSystem::latent_token_balances(supplier), 600);
+        });
+    }
+    
+    #[test]
+    fn test_tracked_donation_lineage() {
+        new_test_ext().execute_with(|| {
+            let consumer = 1;
+            let ngo = 4;
+            EtikaTokenSystem::update_actor_type(Origin::root(), ngo, ActorType::NGO).unwrap();
+            System::set_block_number(1);
+            
+            // Sans suivi, les tokens restent uniquement fongibles
+            assert_ok!(<EtikaTokenSystem as TokenSystem>::distribute_tokens(&consumer, 200));
+            assert!(EtikaTokenSystem::owned_tracked_tokens(consumer).is_empty());
+            
+            // Le lot distribué après activation du suivi est identifié
+            assert_ok!(EtikaTokenSystem::set_token_tracking(Origin::signed(consumer), true));
+            assert_ok!(<EtikaTokenSystem as TokenSystem>::distribute_tokens(&consumer, 1000));
+            let origin_id = EtikaTokenSystem::owned_tracked_tokens(consumer)[0];
+            
+            // L'activation partielle détache un lot actif issu du lot d'origine
+            assert_ok!(<EtikaTokenSystem as TokenSystem>::activate_tokens(&consumer, 600));
+            let tokens = EtikaTokenSystem::tracked_tokens_of(&consumer);
+            assert_eq!(tokens.len(), 2);
+            assert_eq!((tokens[0].id, tokens[0].state.clone(), tokens[0].value), (origin_id, TokenState::Latent, 400));
+            assert_eq!((tokens[1].state.clone(), tokens[1].value), (TokenState::Active, 600));
+            assert_eq!(tokens[1].lineage, vec![origin_id]);
+            let active_id = tokens[1].id;
+            
+            // Le don à l'ONG remonte jusqu'au lot distribué au consommateur
+            assert_ok!(EtikaTokenSystem::transfer_to_ngo(Origin::signed(consumer), ngo, 500));
+            let donations = EtikaTokenSystem::tracked_tokens_of(&ngo);
+            assert_eq!(donations.len(), 1);
+            assert_eq!(donations[0].value, 500);
+            assert_eq!(donations[0].owner, ngo);
+            assert_eq!(donations[0].lineage, vec![active_id, origin_id]);
+            let holders: Vec<u64> = donations[0].transfer_history.iter().map(|(holder, _)| *holder).collect();
+            assert_eq!(holders, vec![consumer, ngo]);
+            assert_eq!(EtikaTokenSystem::tracked_token(active_id).unwrap().value, 100);
+            
+            // Les comptes fongibles font toujours foi
+            assert_eq!(EtikaTokenSystem::active_token_balances(ngo), 500);
+        });
+    }
+    
+    #[test]
+    fn test_escrow_released_at_block() {
+        new_test_ext().execute_with(|| {
+            let consumer = 1;
+            let merchant = 2;
+            <ActiveTokenBalances<Test>>::insert(consumer, 1000);
+            System::set_block_number(10);
+            
+            // Le dépôt s'ajoute au montant en séquestre
+            assert_noop!(
+                EtikaTokenSystem::create_escrow(
+                    Origin::signed(consumer), merchant, 995, EscrowCondition::AtBlock(20), None
+                ),
+                Error::<Test>::InsufficientActiveBalance
+            );
+            
+            // Paiement échelonné libéré au bloc 20
+            assert_ok!(EtikaTokenSystem::create_escrow(
+                Origin::signed(consumer), merchant, 400, EscrowCondition::AtBlock(20), None
+            ));
+            assert_eq!(EtikaTokenSystem::active_token_balances(consumer), 590);
+            assert_eq!(EtikaTokenSystem::escrowed_token_balances(consumer), 400);
+            assert_eq!(EtikaTokenSystem::reserved_token_balances(consumer), 10);
+            
+            // Ni libération anticipée, ni remboursement unilatéral sans échéance
+            assert_noop!(
+                EtikaTokenSystem::release_escrow(Origin::signed(merchant), 0),
+                Error::<Test>::EscrowConditionNotMet
+            );
+            assert_noop!(
+                EtikaTokenSystem::cancel_escrow(Origin::signed(consumer), 0),
+                Error::<Test>::EscrowCancellationNotAllowed
+            );
+            
+            // Libération automatique, avec brûlage et part ONG comme un transfert
+            System::set_block_number(20);
+            EtikaTokenSystem::on_initialize(20);
+            assert_eq!(EtikaTokenSystem::escrows(0), None);
+            assert_eq!(EtikaTokenSystem::escrowed_token_balances(consumer), 0);
+            assert_eq!(EtikaTokenSystem::active_token_balances(merchant), 400 - 20 - 8);
+            
+            // Le dépôt est rendu et la file du bloc ne laisse pas d'entrée vide
+            assert_eq!(EtikaTokenSystem::reserved_token_balances(consumer), 0);
+            assert_eq!(EtikaTokenSystem::active_token_balances(consumer), 600);
+            assert!(!<EscrowReleaseQueue<Test>>::contains_key(20));
+        });
+    }
+    
+    #[test]
+    fn test_escrow_on_pop_finalization_and_refund() {
+        new_test_ext().execute_with(|| {
+            let merchant = 2;
+            let supplier = 3;
+            let transaction_id = [9u8; 32];
+            <ActiveTokenBalances<Test>>::insert(merchant, 1000);
+            System::set_block_number(10);
+            
+            // Un acompte lié à une transaction PoP doit prévoir une échéance de remboursement
+            assert_noop!(
+                EtikaTokenSystem::create_escrow(
+                    Origin::signed(merchant), supplier, 300, EscrowCondition::PopFinalized(transaction_id), None
+                ),
+                Error::<Test>::InvalidEscrowCondition
+            );
+            assert_ok!(EtikaTokenSystem::create_escrow(
+                Origin::signed(merchant), supplier, 300, EscrowCondition::PopFinalized(transaction_id), Some(50)
+            ));
+            assert_ok!(EtikaTokenSystem::create_escrow(
+                Origin::signed(merchant), supplier, 200, EscrowCondition::PopFinalized([8u8; 32]), Some(50)
+            ));
+            
+            // La finalisation de la transaction libère l'acompte
+            <EtikaTokenSystem as OnPopFinalized>::on_pop_finalized(&transaction_id);
+            assert_eq!(EtikaTokenSystem::active_token_balances(supplier), 300 - 15 - 6);
+            assert_eq!(EtikaTokenSystem::active_token_balances(merchant), 1000 - 300 - 200 - 10);
+            assert!(!<PopEscrows>::contains_key(transaction_id));
+            
+            // Sans finalisation, l'expéditeur est remboursé après l'échéance
+            assert_noop!(
+                EtikaTokenSystem::cancel_escrow(Origin::signed(merchant), 1),
+                Error::<Test>::EscrowCancellationNotAllowed
+            );
+            System::set_block_number(50);
+            assert_ok!(EtikaTokenSystem::cancel_escrow(Origin::signed(merchant), 1));
+            assert_eq!(EtikaTokenSystem::active_token_balances(merchant), 700);
+            assert_eq!(EtikaTokenSystem::escrowed_token_balances(merchant), 0);
+            assert_eq!(EtikaTokenSystem::reserved_token_balances(merchant), 0);
+            assert!(!<PopEscrows>::contains_key([8u8; 32]));
+        });
+    }
+    
+    #[test]
+    fn test_escrow_refunded_on_pop_reversal() {
+        new_test_ext().execute_with(|| {
+            let merchant = 2;
+            let supplier = 3;
+            let transaction_id = [9u8; 32];
+            <ActiveTokenBalances<Test>>::insert(merchant, 1000);
+            System::set_block_number(10);
+            
+            assert_ok!(EtikaTokenSystem::create_escrow(
+                Origin::signed(merchant), supplier, 300, EscrowCondition::PopFinalized(transaction_id), Some(50)
+            ));
+            assert_eq!(EtikaTokenSystem::active_token_balances(merchant), 690);
+            
+            // L'annulation de la transaction contestée rembourse l'acompte avant l'échéance
+            <EtikaTokenSystem as OnPopFinalized>::on_pop_reversed(&transaction_id);
+            assert_eq!(EtikaTokenSystem::active_token_balances(merchant), 1000);
+            assert_eq!(EtikaTokenSystem::escrowed_token_balances(merchant), 0);
+            assert_eq!(EtikaTokenSystem::active_token_balances(supplier), 0);
+            assert!(!<PopEscrows>::contains_key(transaction_id));
+            assert!(EtikaTokenSystem::escrows(0).is_none());
+            
+            // Une finalisation ultérieure n'a plus rien à libérer
+            <EtikaTokenSystem as OnPopFinalized>::on_pop_finalized(&transaction_id);
+            assert_eq!(EtikaTokenSystem::active_token_balances(supplier), 0);
+        });
+    }
+    
+    #[test]
+    fn test_fee_rates_governance() {
+        new_test_ext().execute_with(|| {
+            let from = 1;
+            let to = 2;
+            <ActiveTokenBalances<Test>>::insert(from, 2000);
+            System::set_block_number(1);
+            
+            // Seule l'origine de gouvernance peut programmer des taux, dans la limite de variation
+            assert_noop!(
+                EtikaTokenSystem::schedule_fee_rates(Origin::signed(from), 600, 200),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_noop!(
+                EtikaTokenSystem::schedule_fee_rates(Origin::root(), 700, 200),
+                Error::<Test>::FeeRateChangeTooLarge
+            );
+            assert_ok!(EtikaTokenSystem::schedule_fee_rates(Origin::root(), 600, 200));
+            assert_eq!(EtikaTokenSystem::pending_fee_rates(), Some((600, 200, 11)));
+            
+            // Les anciens taux s'appliquent pendant le préavis
+            assert_ok!(EtikaTokenSystem::transfer_tokens(Origin::signed(from), to, 1000));
+            assert_eq!(EtikaTokenSystem::total_burned_tokens(), 50);
+            
+            // Entrée en vigueur au bloc programmé
+            System::set_block_number(11);
+            EtikaTokenSystem::on_initialize(11);
+            assert_eq!(EtikaTokenSystem::fee_rates(), (600, 200));
+            assert_eq!(EtikaTokenSystem::pending_fee_rates(), None);
+            assert_ok!(EtikaTokenSystem::transfer_tokens(Origin::signed(from), to, 1000));
+            assert_eq!(EtikaTokenSystem::total_burned_tokens(), 50 + 60);
+            
+            // Une seule modification par période, y compris par proposition DAO
+            System::set_block_number(20);
+            assert_eq!(
+                <EtikaTokenSystem as TokenFeeGovernance>::schedule_fee_rates(650, 200),
+                Err("Fee rate change too soon")
+            );
+            System::set_block_number(51);
+            assert_ok!(<EtikaTokenSystem as TokenFeeGovernance>::schedule_fee_rates(650, 200));
+        });
+    }
+    
+    #[test]
+    fn test_ngo_registry_and_spending_reports() {
+        new_test_ext().execute_with(|| {
+            let donor = 1;
+            let ngo = 4;
+            let project = [1u8; 32];
+            <ActiveTokenBalances<Test>>::insert(donor, 1000);
+            EtikaTokenSystem::update_actor_type(Origin::root(), ngo, ActorType::NGO).unwrap();
+            
+            // Seuls les comptes de type ONG peuvent s'inscrire au registre
+            assert_noop!(
+                EtikaTokenSystem::register_ngo(Origin::signed(donor), b"Pas une ONG".to_vec(), [0u8; 32]),
+                Error::<Test>::NotRegisteredAsNGO
+            );
+            assert_noop!(
+                EtikaTokenSystem::register_ngo_project(Origin::signed(ngo), project, [2u8; 32]),
+                Error::<Test>::NgoNotInRegistry
+            );
+            assert_ok!(EtikaTokenSystem::register_ngo(Origin::signed(ngo), b"Solidarite".to_vec(), [3u8; 32]));
+            assert_ok!(EtikaTokenSystem::register_ngo_project(Origin::signed(ngo), project, [2u8; 32]));
+            assert_eq!(EtikaTokenSystem::ngo_registry(ngo).unwrap().project_count, 1);
+            
+            // Dons affecté et non affecté
+            assert_noop!(
+                EtikaTokenSystem::donate_to_ngo_project(Origin::signed(donor), ngo, [9u8; 32], 100),
+                Error::<Test>::NgoProjectNotFound
+            );
+            assert_ok!(EtikaTokenSystem::donate_to_ngo_project(Origin::signed(donor), ngo, project, 300));
+            assert_ok!(EtikaTokenSystem::transfer_to_ngo(Origin::signed(donor), ngo, 200));
+            assert_eq!(EtikaTokenSystem::ngo_ledger(ngo), NgoLedger { received: 500, spent: 0 });
+            assert_eq!(EtikaTokenSystem::ngo_project(ngo, project).unwrap().ledger.received, 300);
+            assert_eq!(EtikaTokenSystem::active_token_balances(ngo), 500);
+            
+            // Les dépenses d'un projet sont bornées par les dons qui lui sont affectés
+            assert_ok!(EtikaTokenSystem::submit_spending_report(Origin::signed(ngo), Some(project), 250, [5u8; 32]));
+            assert_noop!(
+                EtikaTokenSystem::submit_spending_report(Origin::signed(ngo), Some(project), 100, [6u8; 32]),
+                Error::<Test>::SpendingExceedsDonations
+            );
+            
+            // Et l'ensemble des dépenses par le total reçu
+            assert_ok!(EtikaTokenSystem::submit_spending_report(Origin::signed(ngo), None, 200, [7u8; 32]));
+            assert_noop!(
+                EtikaTokenSystem::submit_spending_report(Origin::signed(ngo), None, 100, [8u8; 32]),
+                Error::<Test>::SpendingExceedsDonations
+            );
+            
+            assert_eq!(EtikaTokenSystem::ngo_ledger(ngo), NgoLedger { received: 500, spent: 450 });
+            assert_eq!(EtikaTokenSystem::spending_report_count(ngo), 2);
+            let report = EtikaTokenSystem::spending_report(ngo, 0).unwrap();
+            assert_eq!((report.project, report.amount, report.report_hash), (Some(project), 250, [5u8; 32]));
+        });
+    }
 }
\ No newline at end of file